@@ -13,6 +13,14 @@ species = Species
 background = Background
 alignment = Alignment
 xp = XP
+milestone = Milestone
+milestone-toggle = Milestone progression
+xp-award = Award XP
+xp-award-title = Experience awards
+xp-award-amount = XP
+xp-award-reason = Reason
+xp-award-empty = No awards yet
+xp-next-level = { $xp } XP for level { $level }
 total-level = Total Level
 prof-bonus = Prof. Bonus
 classes = Classes
@@ -36,6 +44,8 @@ share-loading = Loading shared character...
 share-not-found = Character not found or not shared
 copy-character = Copy character
 share-error = Failed to decode shared character
+spectate-link = Copy live view link
+spectate-banner = Watching { $name } live
 import-conflict-title = Character already exists
 import-conflict-message = You already have a newer version of "{$name}". Importing will overwrite it. See differences below.
 import-anyway = Import Anyway
@@ -46,6 +56,43 @@ diff-field = Field
 diff-local = Local
 diff-imported = Imported
 diff-no-differences = No visible differences
+diff-cloud = Cloud
+sync-conflict-title = Sync conflict
+sync-conflict-message = "{$name}" was changed both here and on another device since the last sync. Changes to different fields are merged; choose which version wins where both changed the same field.
+sync-conflict-none = There is no sync conflict to resolve.
+sync-conflict-banner = This character was changed on another device and the changes conflict with yours. Cloud sync is paused until you resolve it.
+sync-conflict-resolve = Resolve
+sync-keep-local = Keep Mine
+sync-keep-cloud = Keep Cloud
+diff-snapshot = Snapshot
+history-title = History
+history-checkpoint-name = Checkpoint name
+history-save-checkpoint = Save Checkpoint
+history-empty = No snapshots yet. They are taken before level ups, rests, replays, resets and imports.
+history-compare = Compare
+history-restore-action = Restore
+history-delete = Delete snapshot
+history-confirm-restore = Restore this snapshot? The current state is kept in the history.
+history-level-up = Before level up
+history-long-rest = Before long rest
+history-replay = Before replay
+history-reset = Before reset
+history-import = Before import
+history-restore = Before restore
+history-checkpoint = Checkpoint
+campaigns-title = Campaigns
+campaigns-hint = Create a campaign and share its invite code with your players. Each player joins from their character's sheet.
+campaign-title = Campaign
+campaign-name = Campaign name
+campaign-create = Create
+campaign-forget = Remove from this device
+campaign-invite-code = Invite code
+campaign-join = Join
+campaign-leave = Leave Campaign
+campaign-member-of = This character plays in campaign { $code }. Its GM sees its sheet, hit points, conditions and effects live.
+campaign-not-found = No campaign with this invite code, or you are not its GM.
+campaign-no-members = No characters have joined yet.
+passive-perception = Passive Perception
 no-class = No class
 level-prefix = Level
 
@@ -93,6 +140,14 @@ spell-slots = Spell Slots
 spells = Spells
 spellbook = Spellbook
 prepared-spells = Prepared Spells
+spells-prepared-count = Prepared { $count } / { $limit }
+spells-finish-preparing = Finish preparing
+spells-prepare-after-rest = change after a long rest
+spellbook-copy = Copy
+spellbook-copy-placeholder = Copy a spell into the book
+spellbook-copy-cost = { $cost } · { $hours } h
+spellbook-copy-unaffordable = Not enough gold
+spellbook-copied = copied: { $cost }, { $hours } h
 spell-name = Spell name
 free-uses = Free Uses
 
@@ -113,15 +168,75 @@ armor-type-medium = Medium
 armor-type-heavy = Heavy
 armor-type-shield = Shield
 armor-type-natural = Natural
+weapon-category-simple = Simple
+weapon-category-martial = Martial
+weapon-property-finesse = Finesse
+weapon-property-ranged = Ranged
+weapon-property-thrown = Thrown
+weapon-property-versatile = Versatile
+weapon-property-reach = Reach
+weapon-property-ammunition = Ammunition
+spell-school-abjuration = Abjuration
+spell-school-conjuration = Conjuration
+spell-school-divination = Divination
+spell-school-enchantment = Enchantment
+spell-school-evocation = Evocation
+spell-school-illusion = Illusion
+spell-school-necromancy = Necromancy
+spell-school-transmutation = Transmutation
+casting-time-action = Action
+casting-time-bonus-action = Bonus Action
+casting-time-reaction = Reaction
+casting-time-longer = 1 minute or longer
+spell-range-personal = Self
+spell-range-touch = Touch
+spell-range-ranged = Ranged
+spell-component-verbal = Verbal
+spell-component-somatic = Somatic
+spell-component-material = Material
+spell-component-verbal-abbr = V
+spell-component-somatic-abbr = S
+spell-component-material-abbr = M
+weapon-attack-bonus = Attack bonus (ability + proficiency + bonus)
+weapon-versatile-damage = Two-handed damage
+weapon-ammunition-item = Ammunition item
 items = Items
 item-name = Item name
 qty = Qty
+item-equipped = Equipped
+item-requires-attunement = Requires attunement
+item-attuned = Attuned
+item-expr = Effect (e.g. AC += 1)
+item-charges = Charges
+item-charges-max = Max charges
+item-charges-per-cast = Charges per cast
+item-charges-cost = { $cost } ch.
+item-recharge = Recharge at dawn (e.g. 1d6 + 1)
+item-single-use = Consumed on use
+item-spell-list = Spell list
+item-spell-stats = DC { $dc }, { $attack }
+btn-add-item-spell = Spell
 description = Description
 btn-add-item = + Add Item
 currency = Currency
 spend = Spend
 cast = Cast
 gain = Gain
+weight = Weight (lb)
+weight-per-unit = Weight of one unit (lb)
+cost-gp = Cost (gp)
+carrying = Carrying
+size = Size
+size-tiny = Tiny
+size-small = Small
+size-medium = Medium
+size-large = Large
+size-huge = Huge
+size-gargantuan = Gargantuan
+variant-encumbrance = Variant encumbrance
+encumbrance-encumbered = Encumbered
+encumbrance-heavily-encumbered = Heavily encumbered
+encumbrance-over-capacity = Over capacity
 add-item = Add item
 
 ## Features / Personality / Proficiencies
@@ -249,6 +364,12 @@ story-select = Select a story or create a new one
 story-retry = Retry
 level-up = Level Up
 level-up-choose-class = Choose class to level up
+level-up-hp-title = Hit points for level { $level }
+level-up-hp-average = Take average
+level-up-hp-roll = Roll hit die
+level-up-hp-rolled = Rolled value
+level-up-hp-kind = Hit points
+class-hp-history = Hit points gained per level
 session-cantrips = Cantrips
 session-no-weapons = No weapons
 session-no-items = No items
@@ -264,12 +385,34 @@ action-type-action = Action
 action-type-bonus-action = Bonus Action
 action-type-reaction = Reaction
 session-effects = Active Effects
+session-conditions = Conditions
+roll-attack = Attack roll
+roll-attack-two-handed = Attack roll (two-handed)
+roll-damage = Damage
+roll-crit = Critical hit!
+roll-log = Rolls
+roll-log-clear = Clear rolls
+roll-log-empty = No rolls yet
+roll-log-expand = Show history
+roll-log-collapse = Hide history
 effect-add = Add Effect
 effect-remove = Remove Effect
 effect-name = Effect name
 effect-expr = Expression (optional)
 effect-dice = Dice
 effect-reroll = Reroll dice
+effect-rounds = Rounds left
+combat-start = Start combat
+combat-next-turn = Next turn
+combat-end = End combat
+combat-round = Round { $round }
+effect-concentration = Concentration
+concentration-save = Concentration Save
+concentration-save-dc = DC { $dc }
+concentration-roll = Roll save
+concentration-kept = concentration kept
+concentration-lost = concentration lost
+concentration-end = End concentration
 roll-all-dice = Roll all dice
 dice-rolls-title = Dice Rolls
 btn-confirm = Confirm
@@ -301,6 +444,39 @@ feat-cat-generation = Generation
 feat-cat-faction = Faction
 feat-cat-dragonmark = Dragonmark
 feat-cat-all = All Categories
+ref-equipment = Equipment
+ref-equipment-all = All Equipment
+ref-equipment-gear = Adventuring Gear
+ref-search-equipment = Search equipment...
+ref-packs = Content Packs
+packs-hint = Homebrew classes, species, backgrounds, features, spells and effects imported from JSON files. Packs are stored in this browser and synced with your cloud account.
+packs-empty = No content packs installed.
+btn-import-pack = Import Pack
+confirm-remove-pack = Remove this content pack? Characters using it will lose its definitions.
+pack-invalid = Invalid content pack: { $error }
+pack-conflicts = Pack names clash with existing content: { $names }
+missing-packs = This character uses content packs that are not installed: { $packs }.
+pack-count-classes = { $count ->
+    [one] { $count } class
+   *[other] { $count } classes
+}
+pack-count-species = { $count } species
+pack-count-backgrounds = { $count ->
+    [one] { $count } background
+   *[other] { $count } backgrounds
+}
+pack-count-features = { $count ->
+    [one] { $count } feature
+   *[other] { $count } features
+}
+pack-count-spells = { $count ->
+    [one] { $count } spell
+   *[other] { $count } spells
+}
+pack-count-effects = { $count ->
+    [one] { $count } effect
+   *[other] { $count } effects
+}
 ref-spells = Spells
 ref-select-spell-list = Select a spell list to view spells
 ref-cantrips-level = cantrips
@@ -312,6 +488,16 @@ ref-spell-level = {$level ->
     }
 ref-spell-min-level = from level {$level}
 ref-spell-always-ready = always ready
+ref-spell-school = School
+ref-spell-casting-time = Casting time
+ref-spell-range = Range
+ref-spell-concentration = Concentration
+ref-spell-ritual = Ritual
+ref-spell-without = Without
+ref-spell-filter-any = Any
+ref-spell-filter-yes = Yes
+ref-spell-filter-no = No
+ref-spell-no-matches = No spells match the selected filters
 ref-prerequisites = Prerequisites
 ref-spell-list-link = Spell List
 ref-languages = Languages
@@ -13,6 +13,14 @@ species = Раса
 background = Предыстория
 alignment = Мировоззрение
 xp = Опыт
+milestone = По вехам
+milestone-toggle = Повышение уровня по вехам
+xp-award = Начислить опыт
+xp-award-title = Начисления опыта
+xp-award-amount = Опыт
+xp-award-reason = Причина
+xp-award-empty = Начислений пока нет
+xp-next-level = { $xp } опыта для { $level } уровня
 total-level = Общий уровень
 prof-bonus = Бонус мастерства
 classes = Классы
@@ -36,6 +44,8 @@ share-loading = Загрузка персонажа...
 share-not-found = Персонаж не найден или не опубликован
 copy-character = Копировать персонажа
 share-error = Не удалось декодировать персонажа
+spectate-link = Скопировать ссылку на просмотр в реальном времени
+spectate-banner = Вы наблюдаете за персонажем { $name } в реальном времени
 import-conflict-title = Персонаж уже существует
 import-conflict-message = У вас уже есть более новая версия «{$name}». Импорт перезапишет её. Различия указаны ниже.
 import-anyway = Импортировать
@@ -46,6 +56,43 @@ diff-field = Поле
 diff-local = Локальный
 diff-imported = Импортируемый
 diff-no-differences = Видимых различий нет
+diff-cloud = Облако
+sync-conflict-title = Конфликт синхронизации
+sync-conflict-message = «{$name}» изменён и здесь, и на другом устройстве после последней синхронизации. Изменения разных полей объединены; выберите, какая версия побеждает там, где изменены одни и те же поля.
+sync-conflict-none = Нет конфликта синхронизации.
+sync-conflict-banner = Персонаж изменён на другом устройстве, и изменения конфликтуют с вашими. Синхронизация приостановлена до разрешения конфликта.
+sync-conflict-resolve = Разрешить
+sync-keep-local = Оставить мою
+sync-keep-cloud = Взять из облака
+diff-snapshot = Снимок
+history-title = История
+history-checkpoint-name = Название точки сохранения
+history-save-checkpoint = Сохранить точку
+history-empty = Снимков пока нет. Они создаются перед повышением уровня, отдыхом, переприменением, сбросом и импортом.
+history-compare = Сравнить
+history-restore-action = Восстановить
+history-delete = Удалить снимок
+history-confirm-restore = Восстановить этот снимок? Текущее состояние сохранится в истории.
+history-level-up = Перед повышением уровня
+history-long-rest = Перед долгим отдыхом
+history-replay = Перед переприменением
+history-reset = Перед сбросом
+history-import = Перед импортом
+history-restore = Перед восстановлением
+history-checkpoint = Точка сохранения
+campaigns-title = Кампании
+campaigns-hint = Создайте кампанию и передайте код приглашения игрокам. Каждый игрок присоединяется со страницы своего персонажа.
+campaign-title = Кампания
+campaign-name = Название кампании
+campaign-create = Создать
+campaign-forget = Убрать с этого устройства
+campaign-invite-code = Код приглашения
+campaign-join = Присоединиться
+campaign-leave = Покинуть кампанию
+campaign-member-of = Персонаж участвует в кампании { $code }. Мастер видит его лист, хиты, состояния и эффекты в реальном времени.
+campaign-not-found = Кампании с таким кодом нет, или вы не её мастер.
+campaign-no-members = Пока никто не присоединился.
+passive-perception = Пассивная внимательность
 no-class = Нет класса
 level-prefix = Уровень
 
@@ -93,6 +140,14 @@ spell-slots = Ячейки заклинаний
 spells = Заклинания
 spellbook = Книга заклинаний
 prepared-spells = Подготовленные заклинания
+spells-prepared-count = Подготовлено { $count } / { $limit }
+spells-finish-preparing = Завершить подготовку
+spells-prepare-after-rest = менять после продолжительного отдыха
+spellbook-copy = Переписать
+spellbook-copy-placeholder = Переписать заклинание в книгу
+spellbook-copy-cost = { $cost } · { $hours } ч
+spellbook-copy-unaffordable = Недостаточно золота
+spellbook-copied = переписано: { $cost }, { $hours } ч
 spell-name = Название заклинания
 free-uses = Своб. исп.
 
@@ -113,15 +168,75 @@ armor-type-medium = Средний
 armor-type-heavy = Тяжёлый
 armor-type-shield = Щит
 armor-type-natural = Естественный
+weapon-category-simple = Простое
+weapon-category-martial = Воинское
+weapon-property-finesse = Фехтовальное
+weapon-property-ranged = Дальнобойное
+weapon-property-thrown = Метательное
+weapon-property-versatile = Универсальное
+weapon-property-reach = Досягаемость
+weapon-property-ammunition = Боеприпасы
+spell-school-abjuration = Ограждение
+spell-school-conjuration = Вызов
+spell-school-divination = Прорицание
+spell-school-enchantment = Очарование
+spell-school-evocation = Воплощение
+spell-school-illusion = Иллюзия
+spell-school-necromancy = Некромантия
+spell-school-transmutation = Преобразование
+casting-time-action = Действие
+casting-time-bonus-action = Бонусное действие
+casting-time-reaction = Реакция
+casting-time-longer = 1 минута и дольше
+spell-range-personal = На себя
+spell-range-touch = Касание
+spell-range-ranged = На расстоянии
+spell-component-verbal = Вербальный
+spell-component-somatic = Соматический
+spell-component-material = Материальный
+spell-component-verbal-abbr = В
+spell-component-somatic-abbr = С
+spell-component-material-abbr = М
+weapon-attack-bonus = Бонус атаки (характеристика + мастерство + бонус)
+weapon-versatile-damage = Урон двумя руками
+weapon-ammunition-item = Предмет-боеприпас
 items = Предметы
 item-name = Название предмета
 qty = Кол-во
+item-equipped = Надет
+item-requires-attunement = Требует настройки
+item-attuned = Настроен
+item-expr = Эффект (напр. AC += 1)
+item-charges = Заряды
+item-charges-max = Макс. зарядов
+item-charges-per-cast = Зарядов за сотворение
+item-charges-cost = { $cost } зар.
+item-recharge = Восстановление на рассвете (напр. 1d6 + 1)
+item-single-use = Расходуется при использовании
+item-spell-list = Список заклинаний
+item-spell-stats = СЛ { $dc }, { $attack }
+btn-add-item-spell = Заклинание
 description = Описание
 btn-add-item = + Добавить предмет
 currency = Валюта
 spend = Потратить
 cast = Сотворить
 gain = Получить
+weight = Вес (фнт)
+weight-per-unit = Вес одной штуки (фнт)
+cost-gp = Цена (зм)
+carrying = Переноска
+size = Размер
+size-tiny = Крошечный
+size-small = Маленький
+size-medium = Средний
+size-large = Большой
+size-huge = Огромный
+size-gargantuan = Громадный
+variant-encumbrance = Вариант: нагрузка
+encumbrance-encumbered = Нагружен
+encumbrance-heavily-encumbered = Сильно нагружен
+encumbrance-over-capacity = Перегружен
 add-item = Добавить предмет
 
 ## Способности / Личность / Владения
@@ -249,6 +364,12 @@ story-select = Выберите историю или создайте нову
 story-retry = Повторить
 level-up = Повысить уровень
 level-up-choose-class = Выберите класс для повышения
+level-up-hp-title = Хиты за { $level } уровень
+level-up-hp-average = Взять среднее
+level-up-hp-roll = Бросить кость хитов
+level-up-hp-rolled = Выпавшее значение
+level-up-hp-kind = Хиты
+class-hp-history = Хиты, полученные на каждом уровне
 session-cantrips = Заговоры
 session-no-weapons = Нет оружия
 session-no-items = Нет предметов
@@ -264,12 +385,34 @@ action-type-action = Действие
 action-type-bonus-action = Бонусное действие
 action-type-reaction = Реакция
 session-effects = Активные эффекты
+session-conditions = Состояния
+roll-attack = Бросок атаки
+roll-attack-two-handed = Бросок атаки (двумя руками)
+roll-damage = Урон
+roll-crit = Критическое попадание!
+roll-log = Броски
+roll-log-clear = Очистить броски
+roll-log-empty = Бросков пока нет
+roll-log-expand = Показать историю
+roll-log-collapse = Скрыть историю
 effect-add = Добавить эффект
 effect-remove = Удалить эффект
 effect-name = Название эффекта
 effect-expr = Выражение (опционально)
 effect-dice = Кости
 effect-reroll = Перебросить кости
+effect-rounds = Осталось раундов
+combat-start = Начать бой
+combat-next-turn = Следующий ход
+combat-end = Завершить бой
+combat-round = Раунд { $round }
+effect-concentration = Концентрация
+concentration-save = Спасбросок концентрации
+concentration-save-dc = СЛ { $dc }
+concentration-roll = Бросить спасбросок
+concentration-kept = концентрация сохранена
+concentration-lost = концентрация потеряна
+concentration-end = Прервать концентрацию
 roll-all-dice = Бросить все кости
 dice-rolls-title = Броски костей
 btn-confirm = Подтвердить
@@ -301,12 +444,40 @@ feat-cat-generation = Генерация
 feat-cat-faction = Фракция
 feat-cat-dragonmark = Знак дракона
 feat-cat-all = Все категории
+ref-equipment = Снаряжение
+ref-equipment-all = Всё снаряжение
+ref-equipment-gear = Походное снаряжение
+ref-search-equipment = Поиск снаряжения...
+ref-packs = Наборы контента
+packs-hint = Самодельные классы, виды, предыстории, черты, заклинания и эффекты, импортированные из JSON-файлов. Наборы хранятся в этом браузере и синхронизируются с облачной учётной записью.
+packs-empty = Наборы контента не установлены.
+btn-import-pack = Импортировать набор
+confirm-remove-pack = Удалить этот набор контента? Персонажи, использующие его, потеряют его определения.
+pack-invalid = Некорректный набор контента: { $error }
+pack-conflicts = Имена из набора совпадают с существующим контентом: { $names }
+missing-packs = Персонаж использует неустановленные наборы контента: { $packs }.
+pack-count-classes = классов: { $count }
+pack-count-species = видов: { $count }
+pack-count-backgrounds = предысторий: { $count }
+pack-count-features = черт: { $count }
+pack-count-spells = заклинаний: { $count }
+pack-count-effects = эффектов: { $count }
 ref-spells = Заклинания
 ref-select-spell-list = Выберите список заклинаний для просмотра
 ref-cantrips-level = заговоры
 ref-spell-level = {$level}-й уровень
 ref-spell-min-level = с {$level}-го уровня
 ref-spell-always-ready = всегда подготовлено
+ref-spell-school = Школа
+ref-spell-casting-time = Время накладывания
+ref-spell-range = Дистанция
+ref-spell-concentration = Концентрация
+ref-spell-ritual = Ритуал
+ref-spell-without = Без компонента
+ref-spell-filter-any = Любое
+ref-spell-filter-yes = Да
+ref-spell-filter-no = Нет
+ref-spell-no-matches = Нет заклинаний, подходящих под фильтры
 ref-prerequisites = Требования
 ref-spell-list-link = Список заклинаний
 ref-languages = Языки
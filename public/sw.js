@@ -21,6 +21,8 @@ async function buildPrecacheList() {
       new URL('data/index.json', BASE).href,
       new URL('data/features.json', BASE).href,
       new URL('data/effects.json', BASE).href,
+      new URL('data/conditions.json', BASE).href,
+      new URL('data/equipment.json', BASE).href,
       new URL('data/names.json', BASE).href,
     );
 
@@ -46,6 +48,8 @@ async function buildPrecacheList() {
         new URL(`${locale}/index.json`, BASE).href,
         new URL(`${locale}/features.json`, BASE).href,
         new URL(`${locale}/effects.json`, BASE).href,
+        new URL(`${locale}/conditions.json`, BASE).href,
+        new URL(`${locale}/equipment.json`, BASE).href,
       );
     }
   } catch (e) {
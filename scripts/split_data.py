@@ -16,6 +16,9 @@ from pathlib import Path
 ROOT = Path(__file__).resolve().parent.parent / "public"
 LOCALES = ["en", "ru"]
 TEXT_FIELDS = {"label", "description", "short"}
+# Spells additionally carry free-text casting time, range, duration and
+# material components alongside their mechanics.
+SPELL_TEXT_FIELDS = TEXT_FIELDS | {"casting_time_text", "range_text", "duration", "materials"}
 
 
 def extract_text(obj, text_fields=TEXT_FIELDS):
@@ -32,9 +35,8 @@ def extract_text(obj, text_fields=TEXT_FIELDS):
                 # description is always a string; include if non-empty
                 if val:
                     text[field] = val
-            elif field == "short":
-                if val is not None:
-                    text[field] = val
+            elif val is not None:
+                text[field] = val
     return text
 
 
@@ -236,10 +238,10 @@ def process_spell_list(data):
     for spell in data:
         sname = spell.get("name", "")
         if sname:
-            spell_text = extract_text(spell)
+            spell_text = extract_text(spell, SPELL_TEXT_FIELDS)
             if spell_text:
                 locale_map[sname] = spell_text
-        cleaned.append(strip_text(spell))
+        cleaned.append(strip_text(spell, SPELL_TEXT_FIELDS))
 
     return cleaned, locale_map
 
@@ -274,7 +276,7 @@ def process_index(data):
 
 
 def process_effects(data):
-    """Process effects.json."""
+    """Process effects.json, conditions.json or equipment.json (flat name-keyed lists)."""
     locale_map = OrderedDict()
     cleaned = []
 
@@ -336,7 +338,7 @@ def process_directory(category, processor):
 
 
 def process_single_file(filename, processor):
-    """Process a single file (index.json, effects.json)."""
+    """Process a single file (index.json, effects.json, conditions.json, equipment.json)."""
     data_written = False
     for locale in LOCALES:
         locale_file = ROOT / locale / filename
@@ -386,6 +388,14 @@ def main():
     process_single_file("effects.json", process_effects)
     print()
 
+    print("[conditions]")
+    process_single_file("conditions.json", process_effects)
+    print()
+
+    print("[equipment]")
+    process_single_file("equipment.json", process_effects)
+    print()
+
     print("Done!")
 
 
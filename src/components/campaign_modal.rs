@@ -0,0 +1,105 @@
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::move_tr;
+use reactive_stores::Store;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::{
+    components::modal::Modal,
+    model::{Character, CharacterStoreFields},
+    storage::campaign::{self, CampaignError},
+};
+
+/// Join a campaign with an invite code, or leave the current one.
+#[component]
+pub fn CampaignModal(show: RwSignal<bool>) -> impl IntoView {
+    let store = expect_context::<Store<Character>>();
+    let code = RwSignal::new(String::new());
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(Option::<String>::None);
+    let i18n = expect_context::<leptos_fluent::I18n>();
+
+    let report = move |failure: CampaignError| {
+        log::warn!("Campaign request failed: {failure}");
+        let message = match failure {
+            CampaignError::NotFound => i18n.tr("campaign-not-found"),
+            other => other.to_string(),
+        };
+        error.set(Some(message));
+    };
+
+    let on_join = move |event: web_sys::SubmitEvent| {
+        event.prevent_default();
+        let invite = campaign::normalize_code(&code.get_untracked());
+        if invite.is_empty() || busy.get_untracked() {
+            return;
+        }
+        let char_id = store.read_untracked().id;
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match campaign::join_campaign(&invite, char_id).await {
+                Ok(joined) => {
+                    store.campaign().set(Some(joined.code));
+                    code.set(String::new());
+                    show.set(false);
+                }
+                Err(failure) => report(failure),
+            }
+            busy.set(false);
+        });
+    };
+
+    let on_leave = move |_| {
+        let Some(current) = store.campaign().get_untracked() else {
+            return;
+        };
+        let char_id = store.read_untracked().id;
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match campaign::leave_campaign(&current, char_id).await {
+                Ok(()) => {
+                    store.campaign().set(None);
+                    show.set(false);
+                }
+                Err(failure) => report(failure),
+            }
+            busy.set(false);
+        });
+    };
+
+    view! {
+        <Modal show title=move_tr!("campaign-title")>
+            <div class="modal-body campaign-modal">
+                {move || match store.campaign().get() {
+                    Some(current) => Either::Left(view! {
+                        <p>{move_tr!("campaign-member-of", { "code" => current.clone() })}</p>
+                        <div class="modal-actions">
+                            <button
+                                class="btn-primary btn-danger"
+                                disabled=move || busy.get()
+                                on:click=on_leave
+                            >
+                                {move_tr!("campaign-leave")}
+                            </button>
+                        </div>
+                    }),
+                    None => Either::Right(view! {
+                        <form class="campaign-join" on:submit=on_join>
+                            <input
+                                type="text"
+                                placeholder=move_tr!("campaign-invite-code")
+                                prop:value=move || code.get()
+                                on:input=move |event| code.set(event_target_value(&event))
+                            />
+                            <button type="submit" class="btn-primary" disabled=move || busy.get()>
+                                {move_tr!("campaign-join")}
+                            </button>
+                        </form>
+                    }),
+                }}
+                {move || error.get().map(|message| view! { <p class="campaign-error">{message}</p> })}
+            </div>
+        </Modal>
+    }
+}
@@ -2,7 +2,7 @@ use std::{collections::BTreeMap, time::Duration};
 
 use leptos::{leptos_dom::helpers::set_timeout, prelude::*};
 use leptos_fluent::{move_tr, tr};
-use leptos_router::hooks::use_navigate;
+use leptos_router::{components::A, hooks::use_navigate};
 use reactive_stores::Store;
 use strum::IntoEnumIterator;
 use uuid::Uuid;
@@ -14,10 +14,13 @@ use crate::{
         apply_field_section::ApplyFieldSection,
         args_modal::ArgsModalCtx,
         background_field::BackgroundField,
+        campaign_modal::CampaignModal,
         classes_section::ClassesSection,
         icon::Icon,
+        level_up_modal::LevelUpModal,
         menu_modal::{MenuItem, MenuModal},
         species_field::SpeciesField,
+        xp_award_modal::XpAwardModal,
     },
     firebase,
     model::{
@@ -32,7 +35,11 @@ use crate::{
         },
         feature::FeatureDefinition,
     },
-    share, storage,
+    share,
+    storage::{
+        self,
+        history::{self, SnapshotReason},
+    },
 };
 
 fn export_character(character: &Character) {
@@ -82,6 +89,11 @@ fn export_character(character: &Character) {
     let _ = web_sys::Url::revoke_object_url(&url);
 }
 
+async fn copy_to_clipboard(text: &str) {
+    let clipboard = leptos::prelude::window().navigator().clipboard();
+    let _ = wasm_bindgen_futures::JsFuture::from(clipboard.write_text(text)).await;
+}
+
 pub fn split_resolved(input: String, resolved: Option<String>) -> (String, Option<String>) {
     match resolved {
         Some(name) => (name, Some(input)),
@@ -91,8 +103,9 @@ pub fn split_resolved(input: String, resolved: Option<String>) -> (String, Optio
 
 fn import_character(store: Store<Character>) {
     storage::pick_character_from_file(move |mut imported| {
-        let current_id = store.get_untracked().id;
-        imported.id = current_id;
+        let current = store.get_untracked();
+        history::snapshot_character(&current, SnapshotReason::Import);
+        imported.id = current.id;
         store.set(imported);
     });
 }
@@ -175,6 +188,7 @@ pub fn apply_level(store: Store<Character>, registry: RulesRegistry) {
         registry,
         pending,
         move |character, pending, inputs, fi| {
+            history::snapshot_character(character, SnapshotReason::LevelUp);
             // Mark species/background as applied if they had pending features
             if !character.identity.species_applied && !character.identity.species.is_empty() {
                 character.identity.species_applied = true;
@@ -232,6 +246,7 @@ pub fn apply_single_level(
         registry,
         pending,
         move |character, pending, inputs, fi| {
+            history::snapshot_character(character, SnapshotReason::LevelUp);
             if let Some(class_level) = character.identity.classes.get_mut(class_index) {
                 class_level.applied_levels.insert(level);
                 registry.classes().with(&class_level.class, |def| {
@@ -257,12 +272,20 @@ pub fn CharacterHeader() -> impl IntoView {
     let show_level_up = RwSignal::new(false);
     let i18n = expect_context::<leptos_fluent::I18n>();
 
+    let show_level_up_hp = RwSignal::new(false);
+    let level_up_index = RwSignal::new(0usize);
+
     let level_up_class = move |class_idx: usize| {
-        classes.write()[class_idx].level += 1;
-        apply_level(store, registry);
+        level_up_index.set(class_idx);
+        show_level_up_hp.set(true);
     };
 
-    let on_level_up = move |_| {
+    let show_xp_award = RwSignal::new(false);
+
+    let on_level_up = move |()| {
+        if !store.read_untracked().can_level_up() {
+            return;
+        }
         let count = classes.read().len();
         if count == 1 {
             level_up_class(0);
@@ -298,6 +321,7 @@ pub fn CharacterHeader() -> impl IntoView {
     };
 
     let share_copied = RwSignal::new(false);
+    let show_campaign = RwSignal::new(false);
 
     let on_share = move |_| {
         wasm_bindgen_futures::spawn_local(async move {
@@ -319,14 +343,32 @@ pub fn CharacterHeader() -> impl IntoView {
                 format!("{origin}{BASE_URL}/s/{encoded}")
             };
 
-            let clipboard = leptos::prelude::window().navigator().clipboard();
-            let promise = clipboard.write_text(&url);
-            let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+            copy_to_clipboard(&url).await;
             share_copied.set(true);
         });
         set_timeout(move || share_copied.set(false), Duration::from_secs(2));
     };
 
+    let live_copied = RwSignal::new(false);
+    let on_live_link = move |_| {
+        let Some(uid) = firebase::current_uid() else {
+            return;
+        };
+        let origin = leptos::prelude::window()
+            .location()
+            .origin()
+            .unwrap_or_default();
+        let url = format!(
+            "{origin}{BASE_URL}/s/{uid}/{}/live",
+            store.read_untracked().id
+        );
+        wasm_bindgen_futures::spawn_local(async move {
+            copy_to_clipboard(&url).await;
+            live_copied.set(true);
+        });
+        set_timeout(move || live_copied.set(false), Duration::from_secs(2));
+    };
+
     let on_copy = move |_| {
         let mut character = store.get_untracked();
         character.id = Uuid::new_v4();
@@ -454,18 +496,44 @@ pub fn CharacterHeader() -> impl IntoView {
                             .collect_view()}
                     </select>
                 </div>
-                <div class="header-field level-field">
-                    <label>{move_tr!("xp")}</label>
-                    <input
-                        type="number"
-                        min="0"
-                        prop:value=move || store.identity().experience_points().get().to_string()
-                        on:input=move |e| {
-                            if let Ok(value) = event_target_value(&e).parse::<u32>() {
-                                store.identity().experience_points().set(value);
+                <div class="header-field level-field xp-field">
+                    <label>
+                        {move_tr!("xp")}
+                        <input
+                            type="checkbox"
+                            title=move_tr!("milestone-toggle")
+                            prop:checked=move || store.identity().milestone().get()
+                            on:change=move |e| {
+                                store.identity().milestone().set(event_target_checked(&e));
                             }
-                        }
-                    />
+                        />
+                    </label>
+                    <Show
+                        when=move || !store.identity().milestone().get()
+                        fallback=|| view! { <span class="computed-value">{move_tr!("milestone")}</span> }
+                    >
+                        <div class="level-value-row">
+                            <input
+                                type="number"
+                                min="0"
+                                prop:value=move || store.identity().experience_points().get().to_string()
+                                on:change=move |e| {
+                                    if let Ok(value) = event_target_value(&e).parse::<u32>()
+                                        && store.write().set_experience_points(value)
+                                    {
+                                        on_level_up(());
+                                    }
+                                }
+                            />
+                            <button
+                                class="btn-level-up"
+                                title=move_tr!("xp-award")
+                                on:click=move |_| show_xp_award.set(true)
+                            >
+                                <Icon name="plus" size=14 />
+                            </button>
+                        </div>
+                    </Show>
                 </div>
                 <div class="header-field level-field">
                     <label>{move_tr!("total-level")}</label>
@@ -474,8 +542,9 @@ pub fn CharacterHeader() -> impl IntoView {
                         <Show when=move || store.read().can_level_up()>
                             <button
                                 class="btn-level-up"
+                                class:ready=move || store.read().xp_level_up_ready()
                                 title=move_tr!("level-up")
-                                on:click=on_level_up
+                                on:click=move |_| on_level_up(())
                             >
                                 <Icon name="arrow-up" size=14 />
                             </button>
@@ -504,10 +573,26 @@ pub fn CharacterHeader() -> impl IntoView {
                 <button class="btn-primary" title=move_tr!("share-link") on:click=on_share>
                     <Icon name=move || if share_copied.get() { "check" } else { "share-2" } size=18 />
                 </button>
+                <Show when=move || store.shared().get()>
+                    <button class="btn-primary" title=move_tr!("spectate-link") on:click=on_live_link>
+                        <Icon name=move || if live_copied.get() { "check" } else { "eye" } size=18 />
+                    </button>
+                </Show>
                 <button class="btn-primary" title=move_tr!("export-json") on:click=on_export><Icon name="download" size=18 /></button>
                 <button class="btn-primary" title=move_tr!("import-json") on:click=on_import><Icon name="upload" size=18 /></button>
                 <button class="btn-primary" title=move_tr!("copy-character") on:click=on_copy><Icon name="copy" size=18 /></button>
                 <button class="btn-primary" title=move_tr!("refill-from-registry") on:click=on_refill><Icon name="book-up" size=18 /></button>
+                <button
+                    class="btn-primary"
+                    class:active=move || store.campaign().read().is_some()
+                    title=move_tr!("campaign-title")
+                    on:click=move |_| show_campaign.set(true)
+                >
+                    <Icon name="users" size=18 />
+                </button>
+                <A href=format!("{BASE_URL}/c/{}/history", store.read_untracked().id) attr:class="btn-primary" attr:title=move_tr!("history-title")>
+                    <Icon name="history" size=18 />
+                </A>
                 <button
                     class="btn-primary btn-danger"
                     title=move_tr!("reset-character")
@@ -515,7 +600,10 @@ pub fn CharacterHeader() -> impl IntoView {
                         let msg = tr!("confirm-reset");
                         let window = leptos::prelude::window();
                         if window.confirm_with_message(&msg).unwrap_or(false) {
-                            store.update(|c| c.clear());
+                            store.update(|c| {
+                                history::snapshot_character(c, SnapshotReason::Reset);
+                                c.clear();
+                            });
                         }
                     }
                 >
@@ -528,6 +616,9 @@ pub fn CharacterHeader() -> impl IntoView {
                 items=level_up_items
                 on_select=Callback::new(level_up_class)
             />
+            <LevelUpModal show=show_level_up_hp class_index=level_up_index />
+            <XpAwardModal show=show_xp_award on_level_up=Callback::new(on_level_up) />
+            <CampaignModal show=show_campaign />
         </div>
     }
 }
@@ -17,8 +17,13 @@ pub fn ClassesSection() -> impl IntoView {
 
     let classes = store.identity().classes();
 
+    // A new class starts at level 0 and gains its first level through the
+    // level-up prompt, which asks to roll or take the average hit points.
     let add_class = move |_| {
-        classes.write().push(ClassLevel::default());
+        classes.write().push(ClassLevel {
+            level: 0,
+            ..ClassLevel::default()
+        });
     };
 
     // All classes (for first class — no prerequisites).
@@ -81,6 +86,10 @@ pub fn ClassesSection() -> impl IntoView {
                             }
 
                             let class_loaded = registry.classes().has(&class_key);
+                            let hp_history = (1..=current_level)
+                                .map(|lvl| cl.level_hp(lvl, i == 0).to_string())
+                                .collect::<Vec<_>>()
+                                .join(" · ");
 
                             let next_unapplied: Option<u32> = if class_loaded {
                                 (1..=current_level)
@@ -224,6 +233,9 @@ pub fn ClassesSection() -> impl IntoView {
                                             }
                                         }
                                     />
+                                    <span class="class-hp-history" title=move_tr!("class-hp-history")>
+                                        {hp_history}
+                                    </span>
                                     <Show when={move || classes.read().len() > 1}>
                                         <button
                                             class="btn-remove"
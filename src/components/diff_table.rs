@@ -0,0 +1,443 @@
+use std::collections::BTreeSet;
+
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::move_tr;
+use strum::IntoEnumIterator;
+
+use crate::model::{Ability, Character, Item, Proficiency, Skill, Translatable};
+
+pub struct DiffRow {
+    section: &'static str,
+    label: &'static str,
+    local: String,
+    imported: String,
+}
+
+fn push_if_diff(
+    rows: &mut Vec<DiffRow>,
+    section: &'static str,
+    label: &'static str,
+    local: String,
+    imported: String,
+) {
+    if local != imported {
+        rows.push(DiffRow {
+            section,
+            label,
+            local,
+            imported,
+        });
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        let truncated: String = s.chars().take(max).collect();
+        format!("{truncated}...")
+    }
+}
+
+fn format_names<T>(items: &[T], name_fn: impl Fn(&T) -> &str) -> String {
+    let names: Vec<&str> = items
+        .iter()
+        .map(&name_fn)
+        .filter(|name| !name.is_empty())
+        .collect();
+    if names.is_empty() {
+        "\u{2014}".to_string()
+    } else {
+        format!("{} ({})", names.len(), names.join(", "))
+    }
+}
+
+fn format_items(items: &[Item]) -> String {
+    let entries: Vec<String> = items
+        .iter()
+        .filter(|item| !item.name.is_empty())
+        .map(|item| item.to_string())
+        .collect();
+    if entries.is_empty() {
+        "\u{2014}".to_string()
+    } else {
+        format!("{} ({})", entries.len(), entries.join(", "))
+    }
+}
+
+fn format_spell_slots(ch: &Character, i18n: leptos_fluent::I18n) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for pool in ch.active_pools() {
+        let slots: Vec<String> = ch
+            .all_spell_slots_for_pool(pool)
+            .filter(|(_, slot)| slot.total > 0)
+            .map(|(level, slot)| format!("L{level}: {}", slot.total))
+            .collect();
+        if !slots.is_empty() {
+            parts.push(format!("{}: {}", i18n.tr(pool.tr_key()), slots.join(", ")));
+        }
+    }
+    if parts.is_empty() {
+        "\u{2014}".to_string()
+    } else {
+        parts.join(" | ")
+    }
+}
+
+pub fn group_diff_rows(rows: Vec<DiffRow>) -> Vec<(&'static str, Vec<DiffRow>)> {
+    let mut sections: Vec<(&'static str, Vec<DiffRow>)> = Vec::new();
+    for row in rows {
+        if sections.last().is_none_or(|(key, _)| *key != row.section) {
+            sections.push((row.section, Vec::new()));
+        }
+        sections.last_mut().unwrap().1.push(row);
+    }
+    sections
+}
+
+impl Character {
+    pub fn diff(&self, imported: &Character, i18n: leptos_fluent::I18n) -> Vec<DiffRow> {
+        let mut rows = Vec::new();
+
+        // --- Identity ---
+        let sec = "diff-section-identity";
+        push_if_diff(
+            &mut rows,
+            sec,
+            "character-name",
+            self.identity.name.clone(),
+            imported.identity.name.clone(),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "species",
+            self.identity.species.clone(),
+            imported.identity.species.clone(),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "background",
+            self.identity.background.clone(),
+            imported.identity.background.clone(),
+        );
+        if self.identity.alignment != imported.identity.alignment {
+            rows.push(DiffRow {
+                section: sec,
+                label: "alignment",
+                local: i18n.tr(self.identity.alignment.tr_key()),
+                imported: i18n.tr(imported.identity.alignment.tr_key()),
+            });
+        }
+        push_if_diff(
+            &mut rows,
+            sec,
+            "xp",
+            self.identity.experience_points.to_string(),
+            imported.identity.experience_points.to_string(),
+        );
+        let local_classes = self.class_summary();
+        let imported_classes = imported.class_summary();
+        if local_classes != imported_classes {
+            rows.push(DiffRow {
+                section: sec,
+                label: "classes",
+                local: local_classes,
+                imported: imported_classes,
+            });
+        }
+
+        // --- Ability Scores ---
+        let sec = "panel-ability-scores";
+        for ability in Ability::iter() {
+            let local_score = self.ability_score(ability);
+            let imported_score = imported.ability_score(ability);
+            if local_score != imported_score {
+                rows.push(DiffRow {
+                    section: sec,
+                    label: ability.tr_key(),
+                    local: local_score.to_string(),
+                    imported: imported_score.to_string(),
+                });
+            }
+        }
+
+        // --- Combat (skip death saves & temp HP — stripped during sharing) ---
+        let sec = "panel-combat";
+        push_if_diff(
+            &mut rows,
+            sec,
+            "armor-class",
+            self.armor_class().to_string(),
+            imported.armor_class().to_string(),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "speed",
+            self.speed().to_string(),
+            imported.speed().to_string(),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "hp-max",
+            self.hp_max().to_string(),
+            imported.hp_max().to_string(),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "current-hp",
+            self.hp_current().to_string(),
+            imported.hp_current().to_string(),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "initiative",
+            self.initiative().to_string(),
+            imported.initiative().to_string(),
+        );
+
+        // --- Saving Throws ---
+        let sec = "panel-saving-throws";
+        for ability in Ability::iter() {
+            let local_has = self.proficient_with(ability);
+            let imported_has = imported.proficient_with(ability);
+            if local_has != imported_has {
+                rows.push(DiffRow {
+                    section: sec,
+                    label: ability.tr_abbr_key(),
+                    local: (if local_has { "\u{25CF}" } else { "\u{25CB}" }).to_string(),
+                    imported: (if imported_has { "\u{25CF}" } else { "\u{25CB}" }).to_string(),
+                });
+            }
+        }
+
+        // --- Skills ---
+        let sec = "panel-skills";
+        for skill in Skill::iter() {
+            let local_level = self.skill_proficiency(skill);
+            let imported_level = imported.skill_proficiency(skill);
+            if local_level != imported_level {
+                rows.push(DiffRow {
+                    section: sec,
+                    label: skill.tr_key(),
+                    local: local_level.symbol().to_string(),
+                    imported: imported_level.symbol().to_string(),
+                });
+            }
+        }
+
+        // --- Features (names only, descriptions stripped during sharing) ---
+        let sec = "panel-features";
+        let local_val = format_names(self.features(), |f| &f.name);
+        let imported_val = format_names(imported.features(), |f| &f.name);
+        push_if_diff(&mut rows, sec, "panel-features", local_val, imported_val);
+
+        // --- Equipment ---
+        let sec = "panel-equipment";
+        let local_val = format_names(&self.equipment.weapons, |w| &w.name);
+        let imported_val = format_names(&imported.equipment.weapons, |w| &w.name);
+        push_if_diff(&mut rows, sec, "weapons", local_val, imported_val);
+
+        let local_val = format_items(&self.equipment.items);
+        let imported_val = format_items(&imported.equipment.items);
+        push_if_diff(&mut rows, sec, "items", local_val, imported_val);
+
+        if self.equipment.currency != imported.equipment.currency {
+            rows.push(DiffRow {
+                section: sec,
+                label: "currency",
+                local: self.equipment.currency.to_string(),
+                imported: imported.equipment.currency.to_string(),
+            });
+        }
+
+        // --- Spellcasting ---
+        let sec = "panel-spellcasting";
+        push_if_diff(
+            &mut rows,
+            sec,
+            "spell-slots",
+            format_spell_slots(self, i18n),
+            format_spell_slots(imported, i18n),
+        );
+        {
+            let all_keys: BTreeSet<&String> = self
+                .feature_data
+                .keys()
+                .chain(imported.feature_data.keys())
+                .collect();
+            for key in all_keys {
+                let local_sc = self.feature_data.get(key).and_then(|e| e.spells.as_ref());
+                let imported_sc = imported
+                    .feature_data
+                    .get(key)
+                    .and_then(|e| e.spells.as_ref());
+                match (local_sc, imported_sc) {
+                    (Some(local_sc), Some(imported_sc)) => {
+                        if local_sc.casting_ability != imported_sc.casting_ability {
+                            rows.push(DiffRow {
+                                section: sec,
+                                label: "casting-ability",
+                                local: i18n.tr(local_sc.casting_ability.tr_key()),
+                                imported: i18n.tr(imported_sc.casting_ability.tr_key()),
+                            });
+                        }
+                        let local_val = format_names(&local_sc.spells, |spell| &spell.name);
+                        let imported_val = format_names(&imported_sc.spells, |spell| &spell.name);
+                        push_if_diff(&mut rows, sec, "spells", local_val, imported_val);
+                    }
+                    (Some(local_sc), None) => {
+                        rows.push(DiffRow {
+                            section: sec,
+                            label: "enable-spellcasting",
+                            local: i18n.tr(local_sc.casting_ability.tr_key()),
+                            imported: "\u{2014}".to_string(),
+                        });
+                    }
+                    (None, Some(imported_sc)) => {
+                        rows.push(DiffRow {
+                            section: sec,
+                            label: "enable-spellcasting",
+                            local: "\u{2014}".to_string(),
+                            imported: i18n.tr(imported_sc.casting_ability.tr_key()),
+                        });
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+
+        // --- Proficiencies & Languages ---
+        let sec = "panel-proficiencies";
+        for prof in Proficiency::iter() {
+            let local_has = self.proficiencies.contains(&prof);
+            let imported_has = imported.proficiencies.contains(&prof);
+            if local_has != imported_has {
+                rows.push(DiffRow {
+                    section: sec,
+                    label: prof.tr_key(),
+                    local: (if local_has { "\u{25CF}" } else { "\u{25CB}" }).to_string(),
+                    imported: (if imported_has { "\u{25CF}" } else { "\u{25CB}" }).to_string(),
+                });
+            }
+        }
+        let local_val = if self.languages.is_empty() {
+            "\u{2014}".to_string()
+        } else {
+            self.languages.join(", ")
+        };
+        let imported_val = if imported.languages.is_empty() {
+            "\u{2014}".to_string()
+        } else {
+            imported.languages.join(", ")
+        };
+        push_if_diff(&mut rows, sec, "languages", local_val, imported_val);
+
+        // --- Personality ---
+        let sec = "panel-personality";
+        push_if_diff(
+            &mut rows,
+            sec,
+            "history",
+            truncate(&self.personality.history, 50),
+            truncate(&imported.personality.history, 50),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "personality-traits",
+            truncate(&self.personality.personality_traits, 50),
+            truncate(&imported.personality.personality_traits, 50),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "ideals",
+            truncate(&self.personality.ideals, 50),
+            truncate(&imported.personality.ideals, 50),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "bonds",
+            truncate(&self.personality.bonds, 50),
+            truncate(&imported.personality.bonds, 50),
+        );
+        push_if_diff(
+            &mut rows,
+            sec,
+            "flaws",
+            truncate(&self.personality.flaws, 50),
+            truncate(&imported.personality.flaws, 50),
+        );
+
+        // --- Notes ---
+        let sec = "panel-notes";
+        push_if_diff(
+            &mut rows,
+            sec,
+            "panel-notes",
+            truncate(&self.notes, 50),
+            truncate(&imported.notes, 50),
+        );
+
+        rows
+    }
+}
+
+#[component]
+pub fn DiffTable(
+    sections: Vec<(&'static str, Vec<DiffRow>)>,
+    #[prop(into)] imported_label: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<leptos_fluent::I18n>();
+
+    if sections.is_empty() {
+        return Either::Right(view! {
+            <p class="diff-no-differences">{move_tr!("diff-no-differences")}</p>
+        });
+    }
+
+    Either::Left(view! {
+        <table class="diff-table">
+            <thead>
+                <tr>
+                    <th>{move_tr!("diff-field")}</th>
+                    <th class="diff-local">{move_tr!("diff-local")}</th>
+                    <th class="diff-imported">{imported_label}</th>
+                </tr>
+            </thead>
+            <tbody>
+                {sections
+                    .into_iter()
+                    .map(|(section_key, rows)| {
+                        let section_title = untrack(|| i18n.tr(section_key));
+                        view! {
+                            <tr class="diff-section">
+                                <td colspan="3">{section_title}</td>
+                            </tr>
+                            {rows
+                                .into_iter()
+                                .map(|row| {
+                                    let label = untrack(|| i18n.tr(row.label));
+                                    view! {
+                                        <tr>
+                                            <td>{label}</td>
+                                            <td class="diff-local">{row.local}</td>
+                                            <td class="diff-imported">{row.imported}</td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()}
+                        }
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+    })
+}
@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 
 use leptos::prelude::*;
-use leptos_fluent::move_tr;
+use leptos_fluent::{I18n, move_tr};
 use reactive_stores::Store;
 
 use crate::{
@@ -15,7 +15,7 @@ use crate::{
     expr::{self, DicePool, Expr, Op},
     model::{
         ActiveEffect, ActiveEffects, Attribute, Character, EffectDefinition, EffectDuration,
-        EffectRange, FeatureData, FeatureValue,
+        EffectRange, FeatureData, FeatureValue, RollLog, RollRecord,
     },
 };
 
@@ -137,6 +137,22 @@ fn build_self_expr(
     }
 }
 
+/// Caster effects that outlast the cast and become an `ActiveEffect`.
+fn persistent_effects(effects: &[EffectDefinition]) -> impl Iterator<Item = &EffectDefinition> {
+    effects.iter().filter(|effect| {
+        effect.range == EffectRange::Caster && effect.duration != EffectDuration::Instant
+    })
+}
+
+/// Round limit for the persistent part of the Caster effects: the longest
+/// `Rounds` duration, or `None` if any of them lasts indefinitely.
+fn persistent_rounds(effects: &[EffectDefinition]) -> Option<u32> {
+    persistent_effects(effects)
+        .map(|effect| effect.duration.rounds())
+        .try_fold(0, |max, rounds| rounds.map(|rounds| max.max(rounds)))
+        .filter(|&rounds| rounds > 0)
+}
+
 /// Replace contextual PushVar ops with PushConst so the expression is
 /// self-contained when stored as an ActiveEffect.
 fn bind_extra_vars(
@@ -196,6 +212,8 @@ pub fn apply_self_effects_now(
             pool: None,
             enabled: true,
             scope,
+            rounds: persistent_rounds(effects),
+            concentration: persistent_effects(effects).any(|effect| effect.concentration),
         };
         active_effects.update(|active| active.add(effect, &store.read()));
     }
@@ -211,6 +229,8 @@ pub fn EffectsCalcModal(
     let store = expect_context::<Store<Character>>();
     let eff = expect_context::<EffectiveCharacter>();
     let effects = eff.effects();
+    let rolls = expect_context::<RwSignal<RollLog>>();
+    let i18n = expect_context::<I18n>();
 
     let title = Signal::derive(move || {
         info.with_value(|info| info.as_ref().map(|i| i.title.clone()).unwrap_or_default())
@@ -273,6 +293,9 @@ pub fn EffectsCalcModal(
             };
             let instant_expr = StoredValue::new(instant_expr);
             let persistent_expr = StoredValue::new(persistent_expr);
+            let rounds = persistent_rounds(&info.effects);
+            let concentration =
+                persistent_effects(&info.effects).any(|effect| effect.concentration);
             let extra_vars_copy = StoredValue::new(info.extra_vars.clone());
             let spell_name = StoredValue::new(info.spell_name.clone());
             // Use explicit scope from effect definition if set, otherwise feature_name
@@ -413,21 +436,37 @@ pub fn EffectsCalcModal(
 
                     // Instant effects: apply directly to character with extra vars
                     if let Some(expr) = instant_expr.get_value() {
-                        extra_vars_copy.with_value(|extra_vars| {
+                        let roll = extra_vars_copy.with_value(|extra_vars| {
+                            let mut roll = None;
                             store.update(|character| {
                                 let mut ctx = ApplyContext {
                                     character,
                                     extra_vars,
                                 };
+                                // Typed-in dice were rolled at the table; only
+                                // dice thrown here go to the roll log.
                                 let result = match &pool {
-                                    Some(pool) => expr.apply_with_dice(&mut ctx, pool),
-                                    None => expr.apply(&mut ctx),
+                                    Some(pool) => expr.apply_with_dice(&mut ctx, pool).map(|_| None),
+                                    None => expr.apply_roll(&mut ctx).map(Some),
                                 };
-                                if let Err(error) = result {
-                                    log::error!("Instant effect error: {error}");
+                                match result {
+                                    Ok(rolled) => roll = rolled,
+                                    Err(error) => log::error!("Instant effect error: {error}"),
                                 }
                             });
+                            roll
                         });
+                        if let Some(roll) = roll.filter(|roll| !roll.dice.is_empty()) {
+                            rolls.update(|rolls| {
+                                rolls.push(RollRecord::new(
+                                    title.get_untracked(),
+                                    i18n.tr("effect-dice"),
+                                    expr.to_string(),
+                                    roll,
+                                    false,
+                                ))
+                            });
+                        }
                     }
 
                     // Persistent effects: create ActiveEffect with substituted vars
@@ -452,6 +491,8 @@ pub fn EffectsCalcModal(
                             pool,
                             enabled: true,
                             scope,
+                            rounds,
+                            concentration,
                         };
 
                         effects.update(|active| active.add(effect, &store.read()));
@@ -139,7 +139,8 @@ impl FormBuilder {
                 self.0
                     .push(view! { <>"in("{a}", "{b}", "{c}")"</> }.into_any());
             }
-            Op::Eval(_) | Op::EvalIf(_, _) => {} // intercepted by form_block
+            // intercepted by form_block
+            Op::Eval(_) | Op::EvalIf(_, _) | Op::Store(_) | Op::Load(_) => {}
         }
         Ok(())
     }
@@ -288,6 +289,12 @@ fn form_block_ops(
                     arg_ref(signal)
                 });
             }
+            Op::Store(slot) => {
+                let val = fb.pop()?;
+                let name = expr.local_name(slot).to_string();
+                fb.push_view(view! { <>"let "{name}" = "{val}</> }.into_any());
+            }
+            Op::Load(slot) => fb.push_text(expr.local_name(slot)),
             Op::Eval(idx) => {
                 let sub = form_block(expr, idx, ctx, true)?;
                 fb.push_view(sub);
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use reactive_stores::Store;
+
+use super::character_header::apply_level;
+use crate::{
+    components::{icon::Icon, modal::Modal},
+    expr::DicePool,
+    model::{Character, RollLog, RollRecord},
+    rules::RulesRegistry,
+};
+
+/// How the hit points for a new class level are decided.
+#[derive(Clone, Copy)]
+enum HpChoice {
+    Average,
+    Roll,
+    Rolled(u32),
+}
+
+/// Asks how many hit points a new level of the class at `class_index` grants,
+/// then raises the level, records the result and applies the level.
+#[component]
+pub fn LevelUpModal(show: RwSignal<bool>, class_index: RwSignal<usize>) -> impl IntoView {
+    let store = expect_context::<Store<Character>>();
+    let registry = expect_context::<RulesRegistry>();
+    let rolls = expect_context::<RwSignal<RollLog>>();
+    let i18n = expect_context::<leptos_fluent::I18n>();
+    let rolled = RwSignal::new(String::new());
+
+    let class_info = move || {
+        store.with(|character| {
+            character.identity.classes.get(class_index.get()).map(|cl| {
+                (
+                    cl.to_string(),
+                    cl.level + 1,
+                    cl.hit_die_sides,
+                    cl.average_hp(),
+                )
+            })
+        })
+    };
+    let sides = move || class_info().map_or(0, |(_, _, sides, _)| sides);
+
+    let title = Signal::derive(move || {
+        let level = class_info().map_or(0, |(_, level, _, _)| level);
+        tr!("level-up-hp-title", { "level" => level })
+    });
+
+    let choose = move |choice: HpChoice| {
+        let idx = class_index.get_untracked();
+        let mut record = None;
+        store.update(|character| {
+            let Some(cl) = character.identity.classes.get_mut(idx) else {
+                return;
+            };
+            cl.level += 1;
+            let level = cl.level;
+            let sides = cl.hit_die_sides;
+            let label = cl.to_string();
+            let result = match choice {
+                HpChoice::Average => {
+                    let average = cl.average_hp();
+                    cl.hp_rolls.insert(level, average);
+                    return;
+                }
+                HpChoice::Roll => character.roll_hit_points(idx, level, None),
+                HpChoice::Rolled(value) => {
+                    let pool = DicePool::from(BTreeMap::from([(sides, vec![value])]));
+                    character.roll_hit_points(idx, level, Some(&pool))
+                }
+            };
+            match result {
+                Ok(roll) => record = Some((label, sides, roll)),
+                Err(error) => log::error!("Hit point roll failed: {error}"),
+            }
+        });
+        if let Some((label, sides, roll)) = record {
+            rolls.update(|rolls| {
+                rolls.push(RollRecord::new(
+                    label,
+                    i18n.tr("level-up-hp-kind"),
+                    format!("1d{sides}"),
+                    roll,
+                    false,
+                ))
+            });
+        }
+        rolled.set(String::new());
+        show.set(false);
+        apply_level(store, registry);
+    };
+
+    let rolled_value = move || {
+        rolled
+            .get()
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|value| (1..=sides()).contains(value))
+    };
+
+    view! {
+        <Modal show title>
+            <div class="level-up-hp">
+                <p>{move || class_info().map(|(label, ..)| label).unwrap_or_default()}</p>
+                <div class="menu-modal-choices">
+                    <button class="menu-modal-choice" on:click=move |_| choose(HpChoice::Average)>
+                        <span>{move_tr!("level-up-hp-average")}</span>
+                        <span class="menu-modal-detail">
+                            {move || class_info().map(|(.., average)| format!("+{average}"))}
+                        </span>
+                    </button>
+                    <button class="menu-modal-choice" on:click=move |_| choose(HpChoice::Roll)>
+                        <span>{move_tr!("level-up-hp-roll")}</span>
+                        <span class="menu-modal-detail">{move || format!("1d{}", sides())}</span>
+                    </button>
+                </div>
+                <div class="level-up-hp-rolled">
+                    <label>{move_tr!("level-up-hp-rolled")}</label>
+                    <input
+                        type="number"
+                        min="1"
+                        max=move || sides().to_string()
+                        prop:value=rolled
+                        on:input=move |e| rolled.set(event_target_value(&e))
+                    />
+                    <button
+                        type="button"
+                        class="btn-confirm"
+                        disabled=move || rolled_value().is_none()
+                        on:click=move |_| {
+                            if let Some(value) = rolled_value() {
+                                choose(HpChoice::Rolled(value));
+                            }
+                        }
+                    >
+                        <Icon name="check" size=16 />
+                        " " {move_tr!("btn-confirm")}
+                    </button>
+                </div>
+            </div>
+        </Modal>
+    }
+}
@@ -2,18 +2,21 @@ pub mod ability_score_block;
 pub mod apply_field_section;
 pub mod args_modal;
 pub mod background_field;
+pub mod campaign_modal;
 pub mod cast_button;
 pub mod character_card;
 pub mod character_header;
 pub mod class_field;
 pub mod classes_section;
 pub mod datalist_input;
+pub mod diff_table;
 pub mod effects_calc_modal;
 pub mod entity_field;
 pub mod expr_args_input;
 pub mod expr_view;
 pub mod icon;
 pub mod language_switcher;
+pub mod level_up_modal;
 pub mod logo;
 pub mod menu_modal;
 pub mod modal;
@@ -30,3 +33,4 @@ pub mod species_field;
 pub mod spinner;
 pub mod sync_indicator;
 pub mod toggle_button;
+pub mod xp_award_modal;
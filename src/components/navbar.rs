@@ -32,6 +32,12 @@ fn RefLinks() -> impl IntoView {
         <A href=format!("{BASE_URL}/r/feature") attr:class="navbar-link">
             {move_tr!("ref-features")}
         </A>
+        <A href=format!("{BASE_URL}/r/equipment") attr:class="navbar-link">
+            {move_tr!("ref-equipment")}
+        </A>
+        <A href=format!("{BASE_URL}/r/packs") attr:class="navbar-link">
+            {move_tr!("ref-packs")}
+        </A>
     }
 }
 
@@ -55,6 +61,8 @@ pub fn Navbar() -> impl IntoView {
             "background" => Some("ref-backgrounds"),
             "spell" => Some("ref-spells"),
             "feature" => Some("ref-features"),
+            "equipment" => Some("ref-equipment"),
+            "packs" => Some("ref-packs"),
             _ => None,
         }
     };
@@ -97,6 +105,10 @@ pub fn Navbar() -> impl IntoView {
                         </div>
                     </Show>
                 </div>
+                <A href=format!("{BASE_URL}/campaigns") attr:class="navbar-link navbar-link-campaigns">
+                    <Icon name="users" size=16 />
+                    <span class="navbar-link-label">{move_tr!("campaigns-title")}</span>
+                </A>
                 <Show when=on_ref_page>
                     <div class="navbar-links navbar-ref-inline">
                         <RefLinks />
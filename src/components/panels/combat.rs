@@ -12,6 +12,7 @@ use crate::{
         RulesRegistry,
         apply::{PendingFeature, replay},
     },
+    storage::history::{self, SnapshotReason},
 };
 
 #[component]
@@ -229,7 +230,10 @@ pub fn CombatPanel() -> impl IntoView {
                 <button
                     class="btn-rest"
                     on:click=move |_| {
-                        store.update(|ch| ch.long_rest());
+                        store.update(|ch| {
+                            history::snapshot_character(ch, SnapshotReason::LongRest);
+                            ch.long_rest();
+                        });
                     }
                 >
                     <Icon name="list-restart" size=14 />
@@ -266,6 +270,7 @@ pub fn CombatPanel() -> impl IntoView {
                             pending.sort_by_key(|p| p.source.added_at_level());
 
                             apply_with_modal(store, registry, pending, move |character, pending, inputs, fi| {
+                                history::snapshot_character(character, SnapshotReason::Replay);
                                 replay(fi, character, pending, inputs);
                             });
                         }
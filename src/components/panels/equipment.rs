@@ -4,16 +4,22 @@ use reactive_stores::Store;
 use strum::IntoEnumIterator;
 
 use crate::{
-    components::{icon::Icon, panel::Panel, toggle_button::ToggleButton},
+    components::{
+        datalist_input::DatalistInput, icon::Icon, panel::Panel, toggle_button::ToggleButton,
+    },
     model::{
-        Armor, ArmorType, Character, CharacterStoreFields, CurrencyStoreFields, DamageType,
-        EquipmentStoreFields, Item, Translatable, Weapon, WeaponEffect,
+        Armor, ArmorType, Character, CharacterIdentityStoreFields, CharacterStoreFields,
+        CurrencyStoreFields, DamageType, Equipment, EquipmentStoreFields, Item, ItemCharges,
+        ItemSpell, Money, Size, Translatable, Weapon, WeaponCategory, WeaponEffect, WeaponProperty,
+        format_bonus, format_weight,
     },
+    rules::{EquipmentKind, RulesRegistry, SpellList},
 };
 
 #[component]
 pub fn EquipmentPanel() -> impl IntoView {
     let store = expect_context::<Store<Character>>();
+    let registry = expect_context::<RulesRegistry>();
 
     let i18n = expect_context::<leptos_fluent::I18n>();
 
@@ -23,8 +29,20 @@ pub fn EquipmentPanel() -> impl IntoView {
     let items = equipment.items();
     let currency = equipment.currency();
 
+    let catalog_datalist = move |kind: EquipmentKind| {
+        registry.with_equipment_index(|idx| {
+            idx.values()
+                .filter(|def| def.kind() == kind)
+                .map(|def| view! { <option value=def.label().to_string() /> })
+                .collect_view()
+        })
+    };
+
     view! {
         <Panel title=move_tr!("panel-equipment") class="equipment-panel">
+            <datalist id="equipment-catalog-weapon">{move || catalog_datalist(EquipmentKind::Weapon)}</datalist>
+            <datalist id="equipment-catalog-armor">{move || catalog_datalist(EquipmentKind::Armor)}</datalist>
+            <datalist id="equipment-catalog-gear">{move || catalog_datalist(EquipmentKind::Gear)}</datalist>
 
             <div class="section-header">
                 <h4>{move_tr!("weapons")}</h4>
@@ -48,6 +66,20 @@ pub fn EquipmentPanel() -> impl IntoView {
                                 .map(|(i, weapon)| {
                                     let name = weapon.name.clone();
                                     let atk = weapon.attack_bonus.to_string();
+                                    let weight = weight_str(weapon.weight);
+                                    let cost = weapon.cost.to_gp_str();
+                                    let category = weapon.category.map(|c| c as u8);
+                                    let derived_atk = weapon
+                                        .category
+                                        .is_some()
+                                        .then(|| format_bonus(store.read().weapon_attack_bonus(weapon)));
+                                    let properties = weapon.properties.clone();
+                                    let versatile = weapon
+                                        .has(WeaponProperty::Versatile)
+                                        .then(|| weapon.versatile.as_ref().map(|e| e.to_string()).unwrap_or_default());
+                                    let ammunition = weapon
+                                        .has(WeaponProperty::Ammunition)
+                                        .then(|| weapon.ammunition.clone());
                                     let effects: Vec<_> = weapon.effects.iter().enumerate().map(|(j, effect)| {
                                         let eff_name = effect.name.clone();
                                         let eff_expr = effect.expr.to_string();
@@ -62,9 +94,15 @@ pub fn EquipmentPanel() -> impl IntoView {
                                                     type="text"
                                                     class="entry-name"
                                                     placeholder=move_tr!("name")
+                                                    list="equipment-catalog-weapon"
                                                     prop:value=name
-                                                    on:input=move |e| {
-                                                        weapons.write()[i].name = event_target_value(&e);
+                                                    on:input=move |e| weapons.write()[i].name = event_target_value(&e)
+                                                    // Only a committed name (a picked catalog entry) fills the row,
+                                                    // so typing past a catalog name keeps hand-edited values.
+                                                    on:change=move |e| {
+                                                        if let Some(def) = registry.find_equipment(EquipmentKind::Weapon, &event_target_value(&e)) {
+                                                            def.fill_weapon(&mut weapons.write()[i]);
+                                                        }
                                                     }
                                                 />
                                                 <input
@@ -76,6 +114,32 @@ pub fn EquipmentPanel() -> impl IntoView {
                                                         weapons.write()[i].attack_bonus = event_target_value(&e).parse().unwrap_or(0);
                                                     }
                                                 />
+                                                <input
+                                                    type="number"
+                                                    placeholder=move_tr!("weight")
+                                                    class="short-input"
+                                                    min="0"
+                                                    step="0.1"
+                                                    prop:value=weight
+                                                    on:input=move |e| {
+                                                        weapons.write()[i].weight = event_target_value(&e).parse().unwrap_or(0.0);
+                                                    }
+                                                />
+                                                <input
+                                                    type="text"
+                                                    inputmode="decimal"
+                                                    placeholder=move_tr!("cost-gp")
+                                                    class="short-input"
+                                                    prop:value=cost.clone()
+                                                    on:input=move |e| {
+                                                        if let Some(cost) = Money::from_gp_str(&event_target_value(&e)) {
+                                                            weapons.write()[i].cost = cost;
+                                                        }
+                                                    }
+                                                />
+                                                {derived_atk.map(|atk| view! {
+                                                    <span class="entry-badge" title=move_tr!("weapon-attack-bonus")>{atk}</span>
+                                                })}
                                             </div>
                                             <div class="entry-actions">
                                                 <button
@@ -89,6 +153,77 @@ pub fn EquipmentPanel() -> impl IntoView {
                                                     <Icon name="x" size=14 />
                                                 </button>
                                             </div>
+                                            <div class="entry-full-row weapon-properties-row">
+                                                <select class="select-fixed"
+                                                    prop:value=category.map(|c| c.to_string()).unwrap_or_default()
+                                                    on:change=move |e| {
+                                                        weapons.write()[i].category = WeaponCategory::from_u8_str(&event_target_value(&e));
+                                                    }
+                                                >
+                                                    <option value="" selected=category.is_none()>"\u{2014}"</option>
+                                                    {WeaponCategory::iter()
+                                                        .map(|wc| {
+                                                            let option_value = (wc as u8).to_string();
+                                                            let selected = category == Some(wc as u8);
+                                                            let label = Signal::derive(move || i18n.tr(wc.tr_key()));
+                                                            view! {
+                                                                <option value=option_value selected=selected>
+                                                                    {label}
+                                                                </option>
+                                                            }
+                                                        })
+                                                        .collect_view()}
+                                                </select>
+                                                {WeaponProperty::iter()
+                                                    .map(|property| {
+                                                        let checked = properties.contains(&property);
+                                                        view! {
+                                                            <label class="weapon-property">
+                                                                <input
+                                                                    type="checkbox"
+                                                                    prop:checked=checked
+                                                                    on:change=move |e| {
+                                                                        let mut weapons = weapons.write();
+                                                                        if event_target_checked(&e) {
+                                                                            weapons[i].properties.insert(property);
+                                                                        } else {
+                                                                            weapons[i].properties.remove(&property);
+                                                                        }
+                                                                    }
+                                                                />
+                                                                {move || i18n.tr(property.tr_key())}
+                                                            </label>
+                                                        }
+                                                    })
+                                                    .collect_view()}
+                                                {versatile.map(|expr| view! {
+                                                    <input
+                                                        type="text"
+                                                        placeholder=move_tr!("weapon-versatile-damage")
+                                                        class="damage-input"
+                                                        prop:value=expr
+                                                        on:change=move |e| {
+                                                            let value = event_target_value(&e);
+                                                            if value.trim().is_empty() {
+                                                                weapons.write()[i].versatile = None;
+                                                            } else if let Ok(expr) = value.parse() {
+                                                                weapons.write()[i].versatile = Some(expr);
+                                                            }
+                                                        }
+                                                    />
+                                                })}
+                                                {ammunition.map(|name| view! {
+                                                    <input
+                                                        type="text"
+                                                        placeholder=move_tr!("weapon-ammunition-item")
+                                                        class="effect-name-input"
+                                                        prop:value=name
+                                                        on:change=move |e| {
+                                                            weapons.write()[i].ammunition = event_target_value(&e);
+                                                        }
+                                                    />
+                                                })}
+                                            </div>
                                             {effects.into_iter().map(|(j, eff_name, eff_expr, eff_dmg_type)| {
                                                 view! {
                                                     <div class="entry-full-row weapon-effect-row">
@@ -200,6 +335,8 @@ pub fn EquipmentPanel() -> impl IntoView {
                                 .map(|(i, armor)| {
                                     let name = armor.name.clone();
                                     let base_ac = armor.base_ac.to_string();
+                                    let weight = weight_str(armor.weight);
+                                    let cost = armor.cost.to_gp_str();
                                     let armor_type = armor.armor_type as u8;
                                     let is_natural = armor.armor_type == ArmorType::Natural;
                                     let ac_expr_str = armor.ac_expr.as_ref().map(|e| e.to_string()).unwrap_or_default();
@@ -237,9 +374,13 @@ pub fn EquipmentPanel() -> impl IntoView {
                                                         type="text"
                                                         class="entry-name"
                                                         placeholder=move_tr!("name")
+                                                        list="equipment-catalog-armor"
                                                         prop:value=name
-                                                        on:input=move |e| {
-                                                            armors.write()[i].name = event_target_value(&e);
+                                                        on:input=move |e| armors.write()[i].name = event_target_value(&e)
+                                                        on:change=move |e| {
+                                                            if let Some(def) = registry.find_equipment(EquipmentKind::Armor, &event_target_value(&e)) {
+                                                                def.fill_armor(&mut armors.write()[i]);
+                                                            }
                                                         }
                                                     />
                                                     <input
@@ -262,6 +403,29 @@ pub fn EquipmentPanel() -> impl IntoView {
                                                             }
                                                         }
                                                     />
+                                                    <input
+                                                        type="number"
+                                                        placeholder=move_tr!("weight")
+                                                        class="short-input"
+                                                        min="0"
+                                                        step="0.1"
+                                                        prop:value=weight
+                                                        on:input=move |e| {
+                                                            armors.write()[i].weight = event_target_value(&e).parse().unwrap_or(0.0);
+                                                        }
+                                                    />
+                                                    <input
+                                                        type="text"
+                                                        inputmode="decimal"
+                                                        placeholder=move_tr!("cost-gp")
+                                                        class="short-input"
+                                                        prop:value=cost.clone()
+                                                        on:input=move |e| {
+                                                            if let Some(cost) = Money::from_gp_str(&event_target_value(&e)) {
+                                                                armors.write()[i].cost = cost;
+                                                            }
+                                                        }
+                                                    />
                                                     <select class="select-fixed"
                                                         prop:value=armor_type.to_string()
                                                         on:change=move |e| {
@@ -340,7 +504,12 @@ pub fn EquipmentPanel() -> impl IntoView {
             </button>
 
             <div class="section-header">
-                <h4>{move_tr!("items")}</h4>
+                <h4>
+                    {move_tr!("items")}
+                    <span class="entry-badge" title=move_tr!("item-attuned")>
+                        {move || equipment.read().attuned_count()} "/" {Equipment::MAX_ATTUNED}
+                    </span>
+                </h4>
                 <button
                     class="btn-toggle-desc"
                     on:click=move |_| {
@@ -361,7 +530,15 @@ pub fn EquipmentPanel() -> impl IntoView {
                                 .map(|(i, item)| {
                                     let name = item.name.clone();
                                     let qty = item.quantity.to_string();
+                                    let weight = weight_str(item.weight);
+                                    let cost = item.cost.to_gp_str();
                                     let desc = item.description.clone();
+                                    let equipped = item.equipped;
+                                    let requires_attunement = item.requires_attunement;
+                                    let attuned = item.attuned;
+                                    let item_expr = item.expr.as_ref().map(|e| e.to_string()).unwrap_or_default();
+                                    let item_spells = item.spells.clone();
+                                    let item_charges = item.charges.clone();
                                     view! {
                                         <div class="entry-item">
                                             <ToggleButton />
@@ -370,9 +547,13 @@ pub fn EquipmentPanel() -> impl IntoView {
                                                     type="text"
                                                     class="entry-name"
                                                     placeholder=move_tr!("item-name")
+                                                    list="equipment-catalog-gear"
                                                     prop:value=name
-                                                    on:input=move |e| {
-                                                        items.write()[i].name = event_target_value(&e);
+                                                    on:input=move |e| items.write()[i].name = event_target_value(&e)
+                                                    on:change=move |e| {
+                                                        if let Some(def) = registry.find_equipment(EquipmentKind::Gear, &event_target_value(&e)) {
+                                                            def.fill_item(&mut items.write()[i]);
+                                                        }
                                                     }
                                                 />
                                                 <input
@@ -387,6 +568,30 @@ pub fn EquipmentPanel() -> impl IntoView {
                                                         }
                                                     }
                                                 />
+                                                <input
+                                                    type="number"
+                                                    class="short-input"
+                                                    placeholder=move_tr!("weight")
+                                                    title=move_tr!("weight-per-unit")
+                                                    min="0"
+                                                    step="0.1"
+                                                    prop:value=weight
+                                                    on:input=move |e| {
+                                                        items.write()[i].weight = event_target_value(&e).parse().unwrap_or(0.0);
+                                                    }
+                                                />
+                                                <input
+                                                    type="text"
+                                                    inputmode="decimal"
+                                                    placeholder=move_tr!("cost-gp")
+                                                    class="short-input"
+                                                    prop:value=cost.clone()
+                                                    on:input=move |e| {
+                                                        if let Some(cost) = Money::from_gp_str(&event_target_value(&e)) {
+                                                            items.write()[i].cost = cost;
+                                                        }
+                                                    }
+                                                />
                                             </div>
                                             <div class="entry-actions">
                                                 <button
@@ -400,6 +605,77 @@ pub fn EquipmentPanel() -> impl IntoView {
                                                     <Icon name="x" size=14 />
                                                 </button>
                                             </div>
+                                            <div class="entry-full-row item-magic-row">
+                                                <label class="weapon-property">
+                                                    <input
+                                                        type="checkbox"
+                                                        prop:checked=equipped
+                                                        on:change=move |e| {
+                                                            let checked = event_target_checked(&e);
+                                                            store.update(|ch| {
+                                                                ch.equipment.items[i].equipped = checked;
+                                                                registry.compute(ch);
+                                                            });
+                                                        }
+                                                    />
+                                                    {move_tr!("item-equipped")}
+                                                </label>
+                                                <label class="weapon-property">
+                                                    <input
+                                                        type="checkbox"
+                                                        prop:checked=requires_attunement
+                                                        on:change=move |e| {
+                                                            let checked = event_target_checked(&e);
+                                                            store.update(|ch| {
+                                                                let item = &mut ch.equipment.items[i];
+                                                                item.requires_attunement = checked;
+                                                                item.attuned = false;
+                                                                registry.compute(ch);
+                                                            });
+                                                        }
+                                                    />
+                                                    {move_tr!("item-requires-attunement")}
+                                                </label>
+                                                {requires_attunement.then(|| view! {
+                                                    <label class="weapon-property">
+                                                        <input
+                                                            type="checkbox"
+                                                            prop:checked=attuned
+                                                            disabled=move || { !attuned && equipment.read().attuned_count() >= Equipment::MAX_ATTUNED }
+                                                            on:change=move |e| {
+                                                                let checked = event_target_checked(&e);
+                                                                store.update(|ch| {
+                                                                    if ch.equipment.set_attuned(i, checked) {
+                                                                        registry.compute(ch);
+                                                                    }
+                                                                });
+                                                            }
+                                                        />
+                                                        {move_tr!("item-attuned")}
+                                                    </label>
+                                                })}
+                                                <input
+                                                    type="text"
+                                                    placeholder=move_tr!("item-expr")
+                                                    class="damage-input"
+                                                    prop:value=item_expr
+                                                    on:change=move |e| {
+                                                        let value = event_target_value(&e);
+                                                        let expr = if value.trim().is_empty() {
+                                                            None
+                                                        } else if let Ok(expr) = value.parse() {
+                                                            Some(expr)
+                                                        } else {
+                                                            return;
+                                                        };
+                                                        store.update(|ch| {
+                                                            ch.equipment.items[i].expr = expr;
+                                                            registry.compute(ch);
+                                                        });
+                                                    }
+                                                />
+                                            </div>
+                                            <ItemSpellsEditor index=i spells=item_spells charges=item_charges />
                                             <textarea
                                                 class="entry-desc"
                                                 placeholder=move_tr!("description")
@@ -558,6 +834,283 @@ pub fn EquipmentPanel() -> impl IntoView {
                     />
                 </div>
             </div>
+
+            <h4>{move_tr!("carrying")}</h4>
+            <div class="carrying-row">
+                <span class="carrying-weight">
+                    {move || {
+                        let ch = store.read();
+                        format!("{} / {} lb", format_weight(ch.equipment.total_weight()), format_weight(ch.carrying_capacity()))
+                    }}
+                </span>
+                <select class="select-fixed"
+                    title=move_tr!("size")
+                    prop:value=move || (store.identity().size().get() as u8).to_string()
+                    on:change=move |e| {
+                        if let Some(size) = Size::from_u8_str(&event_target_value(&e)) {
+                            store.identity().size().set(size);
+                        }
+                    }
+                >
+                    {Size::iter()
+                        .map(|size| {
+                            let label = Signal::derive(move || i18n.tr(size.tr_key()));
+                            view! {
+                                <option
+                                    value=(size as u8).to_string()
+                                    selected=move || store.identity().size().get() == size
+                                >
+                                    {label}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <label class="weapon-property">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || equipment.variant_encumbrance().get()
+                        on:change=move |e| {
+                            equipment.variant_encumbrance().set(event_target_checked(&e));
+                        }
+                    />
+                    {move_tr!("variant-encumbrance")}
+                </label>
+                {move || {
+                    store.read().encumbrance().tr_key().map(|key| view! {
+                        <span class="entry-badge encumbrance-badge">{i18n.tr(key)}</span>
+                    })
+                }}
+            </div>
         </Panel>
     }
 }
+
+/// Format a weight in pounds, leaving zero blank so the placeholder shows.
+fn weight_str(weight: f32) -> String {
+    if weight > 0.0 {
+        format_weight(weight)
+    } else {
+        String::new()
+    }
+}
+
+/// Spells and charges of the backpack item at `index`, for scrolls, wands
+/// and staffs.
+#[component]
+fn ItemSpellsEditor(
+    index: usize,
+    spells: Vec<ItemSpell>,
+    charges: Option<ItemCharges>,
+) -> impl IntoView {
+    let store = expect_context::<Store<Character>>();
+    let registry = expect_context::<RulesRegistry>();
+    let items = store.equipment().items();
+
+    let has_charges = charges.is_some();
+    let charges_max = charges.as_ref().map_or(0, |c| c.max).to_string();
+    let recharge = charges
+        .as_ref()
+        .and_then(|c| c.recharge.as_ref())
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+
+    let update_spell = move |j: usize, f: &dyn Fn(&mut ItemSpell)| {
+        if let Some(spell) = items.write()[index].spells.get_mut(j) {
+            f(spell);
+        }
+    };
+
+    let spell_rows = spells
+        .into_iter()
+        .enumerate()
+        .map(|(j, spell)| {
+            let list = spell.list.clone();
+            let spell_options = Signal::derive(move || {
+                registry.track_spell_cache();
+                registry.with_spell_list(&SpellList::Ref { from: list.clone() }, |spell_map| {
+                    spell_map
+                        .values()
+                        .map(|sd| {
+                            (
+                                sd.name.clone(),
+                                sd.label().to_string(),
+                                sd.description.clone(),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+            });
+            let spell_name = spell.name.clone();
+            let value = Signal::derive(move || {
+                spell_options
+                    .get()
+                    .into_iter()
+                    .find(|(name, _, _)| *name == spell_name)
+                    .map_or_else(|| spell_name.clone(), |(_, label, _)| label)
+            });
+            let selected_list = spell.list.clone();
+            let list_options = registry.with_spell_entries(|entries| {
+                entries
+                    .values()
+                    .map(|entry| {
+                        let url = entry.url.clone();
+                        let selected = url == selected_list;
+                        let label = entry.label.clone().unwrap_or_else(|| entry.name.clone());
+                        view! { <option value=url selected=selected>{label}</option> }
+                    })
+                    .collect_view()
+            });
+            let number_input = move |value: String, placeholder, f: fn(&mut ItemSpell, &str)| {
+                view! {
+                    <input
+                        type="number"
+                        class="short-input"
+                        placeholder=placeholder
+                        title=placeholder
+                        prop:value=value
+                        on:change=move |e| {
+                            let value = event_target_value(&e);
+                            update_spell(j, &|spell| f(spell, &value));
+                        }
+                    />
+                }
+            };
+
+            view! {
+                <div class="entry-full-row item-spell-row">
+                    <select on:change=move |e| {
+                        let list = event_target_value(&e);
+                        update_spell(j, &|spell| {
+                            spell.list = list.clone();
+                            spell.name.clear();
+                        });
+                    }>
+                        <option value="" selected=spell.list.is_empty()>
+                            {move_tr!("item-spell-list")}
+                        </option>
+                        {list_options}
+                    </select>
+                    <DatalistInput
+                        value=value
+                        placeholder=move_tr!("spell-name")
+                        class="entry-name"
+                        options=spell_options
+                        on_input=move |input, resolved| {
+                            update_spell(j, &|spell| {
+                                let level = resolved.as_deref().and_then(|name| {
+                                    registry.with_spell_list(
+                                        &SpellList::Ref { from: spell.list.clone() },
+                                        |spell_map| spell_map.get(name).map(|sd| sd.level),
+                                    )
+                                });
+                                spell.name = resolved.clone().unwrap_or_else(|| input.clone());
+                                if let Some(level) = level {
+                                    spell.level = spell.level.max(level);
+                                }
+                            });
+                        }
+                    />
+                    {number_input(spell.level.to_string(), move_tr!("level"), |spell, v| {
+                        if let Ok(v) = v.parse() {
+                            spell.level = v;
+                        }
+                    })}
+                    {number_input(spell.save_dc.to_string(), move_tr!("spell-save-dc"), |spell, v| {
+                        if let Ok(v) = v.parse() {
+                            spell.save_dc = v;
+                        }
+                    })}
+                    {number_input(spell.attack_bonus.to_string(), move_tr!("spell-attack"), |spell, v| {
+                        if let Ok(v) = v.parse() {
+                            spell.attack_bonus = v;
+                        }
+                    })}
+                    {has_charges.then(|| number_input(spell.cost.to_string(), move_tr!("item-charges-per-cast"), |spell, v| {
+                        if let Ok(v) = v.parse() {
+                            spell.cost = v;
+                        }
+                    }))}
+                    <button
+                        class="btn-remove"
+                        on:click=move |_| {
+                            let mut items = items.write();
+                            if j < items[index].spells.len() {
+                                items[index].spells.remove(j);
+                            }
+                        }
+                    >
+                        <Icon name="x" size=14 />
+                    </button>
+                </div>
+            }
+        })
+        .collect_view();
+
+    view! {
+        {spell_rows}
+        <div class="entry-full-row item-spell-row">
+            <button
+                class="btn-add-item-spell"
+                on:click=move |_| {
+                    items.write()[index].spells.push(ItemSpell {
+                        level: 1,
+                        ..Default::default()
+                    });
+                }
+            >
+                <Icon name="wand" size=14 />
+                " " {move_tr!("btn-add-item-spell")}
+            </button>
+            <label class="weapon-property">
+                <input
+                    type="checkbox"
+                    prop:checked=has_charges
+                    on:change=move |e| {
+                        let checked = event_target_checked(&e);
+                        items.write()[index].charges = checked.then(ItemCharges::default);
+                    }
+                />
+                {move_tr!("item-charges")}
+            </label>
+            {has_charges.then(|| view! {
+                <input
+                    type="number"
+                    class="short-input"
+                    min="0"
+                    placeholder=move_tr!("item-charges-max")
+                    title=move_tr!("item-charges-max")
+                    prop:value=charges_max
+                    on:change=move |e| {
+                        if let Ok(value) = event_target_value(&e).parse::<u32>()
+                            && let Some(charges) = &mut items.write()[index].charges
+                        {
+                            charges.max = value;
+                            charges.used = charges.used.min(value);
+                        }
+                    }
+                />
+                <input
+                    type="text"
+                    class="damage-input"
+                    placeholder=move_tr!("item-recharge")
+                    title=move_tr!("item-recharge")
+                    prop:value=recharge
+                    on:change=move |e| {
+                        let value = event_target_value(&e);
+                        let recharge = if value.trim().is_empty() {
+                            None
+                        } else if let Ok(expr) = value.parse() {
+                            Some(expr)
+                        } else {
+                            return;
+                        };
+                        if let Some(charges) = &mut items.write()[index].charges {
+                            charges.recharge = recharge;
+                        }
+                    }
+                />
+            })}
+        </div>
+    }
+}
@@ -1,5 +1,5 @@
 use leptos::{either::Either, prelude::*};
-use leptos_fluent::move_tr;
+use leptos_fluent::{move_tr, tr};
 use reactive_stores::Store;
 use strum::IntoEnumIterator;
 
@@ -8,8 +8,8 @@ use crate::{
         datalist_input::DatalistInput, icon::Icon, panel::Panel, toggle_button::ToggleButton,
     },
     model::{
-        Ability, Character, CharacterStoreFields, Spell, SpellData, SpellSlotPool, Translatable,
-        format_bonus,
+        Ability, Character, CharacterStoreFields, Spell, SpellCopy, SpellData, SpellSlotPool,
+        Translatable, format_bonus,
     },
     rules::RulesRegistry,
 };
@@ -35,9 +35,7 @@ fn update_spell(
     f: impl FnOnce(&mut Spell),
 ) {
     update_spells(fname, store, |sc| {
-        if let Some(spell) = sc.spells.get_mut(index) {
-            f(spell);
-        }
+        sc.update_spell(index, f);
     });
 }
 
@@ -141,6 +139,21 @@ fn FeatureSpellcastingSection(
         })
     });
 
+    // (prepared count, limit, preparing) for casters who prepare spells
+    let preparation = Memo::new(move |_| {
+        fname.with_value(|key| {
+            store
+                .feature_data()
+                .read()
+                .get(key)
+                .and_then(|e| e.spells.as_ref())
+                .and_then(|sc| {
+                    sc.prepare_limit
+                        .map(|limit| (sc.prepared_count(), limit, sc.preparing))
+                })
+        })
+    });
+
     // Per-level spell suggestions from registry (for spellbook / single-tier)
     let spell_suggestions: [RwSignal<Vec<(String, String, String)>>; 10] =
         std::array::from_fn(|_| RwSignal::new(Vec::new()));
@@ -152,6 +165,75 @@ fn FeatureSpellcastingSection(
         }
     });
 
+    // Spellbook copying: class-list spells up to the highest level the class
+    // itself casts that aren't in the book yet
+    let is_spellbook = fname.with_value(|key| {
+        registry
+            .with_feature(key, |feat| {
+                feat.spells.as_ref().is_some_and(|s| s.spellbook)
+            })
+            .unwrap_or(false)
+    });
+    let copy_options = Memo::new(move |_| {
+        let character = store.read();
+        let Some(sc) = fname.with_value(|key| {
+            character
+                .feature_data
+                .get(key)
+                .and_then(|e| e.spells.as_ref())
+        }) else {
+            return Vec::new();
+        };
+        let max_level = fname.with_value(|key| character.class_spell_level(key)) as usize;
+        let in_book = |name: &str| sc.known.iter().flatten().any(|s| s.name == name);
+        (1..=max_level.min(9))
+            .flat_map(|level| {
+                spell_suggestions[level]
+                    .get()
+                    .into_iter()
+                    .map(move |option| (option, level as u32))
+            })
+            .filter(|((name, _, _), _)| !in_book(name))
+            .collect::<Vec<_>>()
+    });
+    let copy_input = RwSignal::new(String::new());
+    let copy_choice = RwSignal::new(None::<((String, String, String), u32)>);
+    let copy_cost = move || {
+        copy_choice
+            .get()
+            .map(|(_, level)| SpellCopy::for_level(level))
+    };
+    let can_afford_copy = move || {
+        copy_cost().is_some_and(|copy| store.read().equipment.currency.as_money() >= copy.cost)
+    };
+    let copy_spell = move |_| {
+        let Some(((name, label, description), level)) = copy_choice.get_untracked() else {
+            return;
+        };
+        let spell = Spell {
+            name,
+            label: Some(label),
+            level,
+            description,
+            ..Default::default()
+        };
+        let on_class_list = |spell: &Spell| {
+            spell_suggestions
+                .get(spell.level as usize)
+                .is_some_and(|options| {
+                    options.with_untracked(|options| {
+                        options.iter().any(|(name, _, _)| *name == spell.name)
+                    })
+                })
+        };
+        let copied =
+            fname.with_value(|key| store.write().copy_into_spellbook(key, spell, on_class_list));
+        if copied {
+            copy_input.set(String::new());
+            copy_choice.set(None);
+        }
+    };
+
     // Per-level suggestions from known (spellbook) entries for prepared spells
     let known_suggestions: [RwSignal<Vec<(String, String, String)>>; 10] =
         std::array::from_fn(|_| RwSignal::new(Vec::new()));
@@ -261,6 +343,7 @@ fn FeatureSpellcastingSection(
                                 let spell_name = spell.label().to_string();
                                 let spell_level = spell.level.to_string();
                                 let spell_sticky = spell.sticky;
+                                let copied = spell.copied;
                                 let options = spell_suggestions[spell.level.min(9) as usize];
                                 view! {
                                     <div class="entry-item">
@@ -312,6 +395,14 @@ fn FeatureSpellcastingSection(
                                                     }
                                                 }
                                             />
+                                            {copied.map(|copy| view! {
+                                                <span class="entry-sublabel">
+                                                    {move_tr!("spellbook-copied", {
+                                                        "cost" => copy.cost.to_string(),
+                                                        "hours" => copy.hours,
+                                                    })}
+                                                </span>
+                                            })}
                                         </div>
                                         <div class="entry-actions">
                                             <Show when=move || !spell_sticky>
@@ -358,11 +449,71 @@ fn FeatureSpellcastingSection(
                 >
                     {move_tr!("btn-add-spell")}
                 </button>
+                {is_spellbook.then(|| view! {
+                    <div class="spellbook-copy">
+                        <DatalistInput
+                            value=copy_input
+                            placeholder=move_tr!("spellbook-copy-placeholder")
+                            class="entry-name"
+                            options=Signal::derive(move || {
+                                copy_options.get().into_iter().map(|(option, _)| option).collect()
+                            })
+                            on_input=move |input, resolved| {
+                                copy_input.set(input);
+                                copy_choice.set(resolved.and_then(|name| {
+                                    copy_options
+                                        .get_untracked()
+                                        .into_iter()
+                                        .find(|((n, _, _), _)| *n == name)
+                                }));
+                            }
+                        />
+                        <span class="entry-sublabel">
+                            {move || copy_cost().map(|copy| {
+                                tr!("spellbook-copy-cost", {
+                                    "cost" => copy.cost.to_string(),
+                                    "hours" => copy.hours,
+                                })
+                            })}
+                        </span>
+                        <button
+                            class="btn-confirm"
+                            disabled=move || !can_afford_copy()
+                            title=move || {
+                                (copy_choice.get().is_some() && !can_afford_copy())
+                                    .then(|| i18n.tr("spellbook-copy-unaffordable"))
+                            }
+                            on:click=copy_spell
+                        >
+                            {move_tr!("spellbook-copy")}
+                        </button>
+                    </div>
+                })}
             </Show>
 
             // Prepared spells section (or single-tier spell list)
             <div class="section-header">
                 <h4>{move || if is_two_tier.get() { move_tr!("prepared-spells") } else { move_tr!("spells") }}</h4>
+                {move || preparation.get().map(|(count, limit, preparing)| view! {
+                    <span class="spell-prepared-count" class:full={count >= limit} class:over={count > limit}>
+                        {move_tr!("spells-prepared-count", {"count" => count, "limit" => limit})}
+                    </span>
+                    {if preparing {
+                        Either::Left(view! {
+                            <button
+                                class="btn-finish-preparing"
+                                title=move_tr!("spells-finish-preparing")
+                                on:click=move |_| update_spells(fname, store, |sc| sc.preparing = false)
+                            >
+                                <Icon name="check" size=14 />
+                            </button>
+                        })
+                    } else {
+                        Either::Right(view! {
+                            <span class="entry-sublabel">{move_tr!("spells-prepare-after-rest")}</span>
+                        })
+                    }}
+                })}
                 <button
                     class="btn-toggle-desc"
                     on:click=move |_| {
@@ -397,6 +548,10 @@ fn FeatureSpellcastingSection(
                             let spell_level = spell.level.to_string();
                             let spell_sticky = spell.sticky;
                             let has_free_uses = spell.free_uses.is_some();
+                            // Prepared spells wait for the next long rest
+                            let locked = !spell_sticky && !sc.can_change(spell);
+                            // Other rows stay cantrips unless one more spell may be prepared
+                            let max_level = if spell.level == 0 && !sc.can_prepare_more() { "0" } else { "9" };
                             // Two-tier: autocomplete from spellbook; single-tier/cantrips: from registry
                             let options = if two_tier && spell.level > 0 {
                                 known_suggestions[spell.level.min(9) as usize]
@@ -407,7 +562,7 @@ fn FeatureSpellcastingSection(
                                 <div class="entry-item">
                                     <ToggleButton />
                                     <div class="entry-content">
-                                        {if spell_sticky {
+                                        {if spell_sticky || locked {
                                             Either::Left(view! {
                                                 <span class="entry-name entry-name-readonly">{spell_name.clone()}</span>
                                             })
@@ -427,22 +582,25 @@ fn FeatureSpellcastingSection(
                                                             })
                                                         }).unwrap_or_default();
                                                         update_spells(fname, store, |sc| {
-                                                            let Some(spell) = sc.spells.get_mut(i) else { return };
-                                                            if let Some(name) = resolved {
-                                                                let known_spell = sc.known.as_ref()
-                                                                    .and_then(|k| k.iter().find(|s| s.name == name));
-                                                                spell.description = known_spell
-                                                                    .map(|s| s.description.clone())
-                                                                    .filter(|d| !d.is_empty())
-                                                                    .unwrap_or(desc);
-                                                                spell.label = known_spell
-                                                                    .and_then(|s| s.label.clone())
-                                                                    .or(Some(input));
-                                                                spell.name = name;
-                                                            } else {
-                                                                spell.set_label(input);
-                                                                spell.description.clear();
-                                                            }
+                                                            let known_spell = resolved.as_ref().and_then(|name| {
+                                                                sc.known.as_ref()?.iter().find(|s| s.name == *name).cloned()
+                                                            });
+                                                            sc.update_spell(i, |spell| {
+                                                                if let Some(name) = resolved {
+                                                                    spell.description = known_spell
+                                                                        .as_ref()
+                                                                        .map(|s| s.description.clone())
+                                                                        .filter(|d| !d.is_empty())
+                                                                        .unwrap_or(desc);
+                                                                    spell.label = known_spell
+                                                                        .and_then(|s| s.label)
+                                                                        .or(Some(input));
+                                                                    spell.name = name;
+                                                                } else {
+                                                                    spell.set_label(input);
+                                                                    spell.description.clear();
+                                                                }
+                                                            });
                                                         });
                                                     }
                                                 />
@@ -452,9 +610,9 @@ fn FeatureSpellcastingSection(
                                             type="number"
                                             class="short-input"
                                             min="0"
-                                            max="9"
+                                            max=max_level
                                             placeholder="Lv"
-                                            disabled=spell_sticky
+                                            disabled=spell_sticky || locked
                                             prop:value=spell_level
                                             on:change=move |e| {
                                                 if let Ok(value) = event_target_value(&e).parse::<u32>() {
@@ -464,7 +622,7 @@ fn FeatureSpellcastingSection(
                                         />
                                     </div>
                                     <div class="entry-actions">
-                                        <Show when=move || !spell_sticky>
+                                        <Show when=move || !spell_sticky && !locked>
                                             <button
                                                 class="btn-remove"
                                                 on:click=move |_| {
@@ -4,12 +4,13 @@ use reactive_stores::Store;
 
 use crate::{
     components::{icon::Icon, toggle_button::ToggleButton},
-    model::{Character, CharacterStoreFields, EquipmentStoreFields, Item, Money},
+    model::{Character, CharacterStoreFields, EquipmentStoreFields, Item, Money, format_weight},
 };
 
 #[component]
 pub fn BackpackBlock() -> impl IntoView {
     let store = expect_context::<Store<Character>>();
+    let i18n = expect_context::<leptos_fluent::I18n>();
     let equipment = store.equipment();
     let money_input: NodeRef<html::Input> = NodeRef::new();
 
@@ -55,6 +56,22 @@ pub fn BackpackBlock() -> impl IntoView {
                 </div>
             </div>
 
+            // -- Carried weight --
+            <div class="session-carrying">
+                <label>{move_tr!("carrying")}</label>
+                <span>
+                    {move || {
+                        let ch = store.read();
+                        format!("{} / {} lb", format_weight(ch.equipment.total_weight()), format_weight(ch.carrying_capacity()))
+                    }}
+                </span>
+                {move || {
+                    store.read().encumbrance().tr_key().map(|key| view! {
+                        <span class="entry-badge encumbrance-badge">{i18n.tr(key)}</span>
+                    })
+                }}
+            </div>
+
             // -- Add item --
             <div class="entry-item">
                 <button class="btn-icon btn-icon--success" title=move_tr!("add-item")
@@ -78,6 +95,7 @@ pub fn BackpackBlock() -> impl IntoView {
                             name,
                             quantity,
                             description,
+                            ..Item::default()
                         });
 
                         name_el.set_value("");
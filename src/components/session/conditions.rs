@@ -0,0 +1,74 @@
+use leptos::prelude::*;
+use leptos_fluent::move_tr;
+use reactive_stores::Store;
+
+use crate::{
+    components::icon::Icon, effective::EffectiveCharacter, model::Character, rules::RulesRegistry,
+};
+
+#[component]
+pub fn ConditionsBlock() -> impl IntoView {
+    let store = expect_context::<Store<Character>>();
+    let effects = expect_context::<EffectiveCharacter>().effects();
+    let registry = expect_context::<RulesRegistry>();
+
+    let set_level = move |name: &str, level: u32| {
+        let expr = registry.with_conditions_index(|index| {
+            index
+                .get(name)
+                .map(|condition| condition.expr.clone())
+                .filter(|expr| !expr.is_empty())
+        });
+        effects.update(|active| active.set_condition(name, level, expr, &store.read()));
+    };
+
+    let conditions_view = move || {
+        registry.with_conditions_index(|index| {
+            index
+                .values()
+                .map(|condition| {
+                    let name = condition.name.clone();
+                    let label = condition.label().to_owned();
+                    let description = condition.description.clone();
+                    let max_level = condition.max_level.unwrap_or(1);
+                    let level = {
+                        let name = name.clone();
+                        Memo::new(move |_| effects.read().condition_level(&name))
+                    };
+
+                    if condition.is_leveled() {
+                        let dec_name = name.clone();
+                        view! {
+                            <span class="condition-pill" class:active=move || { level.get() > 0 } title=description>
+                                <button class="btn-icon"
+                                    disabled=move || { level.get() == 0 }
+                                    on:click=move |_| set_level(&dec_name, level.get_untracked().saturating_sub(1))
+                                ><Icon name="minus" size=12 /></button>
+                                {label} " " {move || level.get()}
+                                <button class="btn-icon"
+                                    disabled=move || { level.get() >= max_level }
+                                    on:click=move |_| set_level(&name, (level.get_untracked() + 1).min(max_level))
+                                ><Icon name="plus" size=12 /></button>
+                            </span>
+                        }
+                        .into_any()
+                    } else {
+                        view! {
+                            <button class="condition-pill" class:active=move || { level.get() > 0 } title=description
+                                on:click=move |_| set_level(&name, u32::from(level.get_untracked() == 0))
+                            >{label}</button>
+                        }
+                        .into_any()
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <div class="session-section session-section-conditions" id="session-conditions">
+            <h3 class="session-section-title">{move_tr!("session-conditions")}</h3>
+            <div class="condition-list">{conditions_view}</div>
+        </div>
+    }
+}
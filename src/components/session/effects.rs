@@ -1,5 +1,5 @@
 use leptos::{html, prelude::*};
-use leptos_fluent::move_tr;
+use leptos_fluent::{I18n, move_tr};
 use reactive_stores::Store;
 
 use crate::{
@@ -12,7 +12,7 @@ use crate::{
     },
     effective::EffectiveCharacter,
     expr::{DicePool, Expr},
-    model::{ActiveEffect, Attribute, Character},
+    model::{ActiveEffect, Attribute, Character, RollLog, RollRecord},
     rules::RulesRegistry,
 };
 
@@ -31,11 +31,15 @@ pub fn EffectsBlock() -> impl IntoView {
     let eff = expect_context::<EffectiveCharacter>();
     let effects = eff.effects();
     let registry = expect_context::<RulesRegistry>();
+    let rolls = expect_context::<RwSignal<RollLog>>();
+    let i18n = expect_context::<I18n>();
 
     let effect_label = RwSignal::new(String::new());
     let effect_key = RwSignal::new(Option::<String>::None);
     let effect_desc = RwSignal::new(String::new());
     let effect_scope = RwSignal::new(Option::<Box<str>>::None);
+    let effect_rounds = RwSignal::new(Option::<u32>::None);
+    let effect_concentration = RwSignal::new(false);
     let expr_input: NodeRef<html::Input> = NodeRef::new();
 
     // Dice modal state: stores (expr, pending_effect_or_index)
@@ -59,6 +63,8 @@ pub fn EffectsBlock() -> impl IntoView {
         effect_key.set(None);
         effect_desc.set(String::new());
         effect_scope.set(None);
+        effect_rounds.set(None);
+        effect_concentration.set(false);
         if let Some(el) = expr_input.get() {
             el.set_value("");
         }
@@ -79,10 +85,37 @@ pub fn EffectsBlock() -> impl IntoView {
         })
     });
 
+    let combat_round = Memo::new(move |_| effects.read().combat_round());
+
     view! {
         <div class="session-section session-section-effects" id="session-effects">
             <h3 class="session-section-title">{move_tr!("session-effects")}</h3>
 
+            // -- Combat clock --
+            <div class="combat-clock">
+                {move || match combat_round.get() {
+                    None => view! {
+                        <button class="btn-icon"
+                            title=move_tr!("combat-start")
+                            on:click=move |_| effects.update(|active| active.start_combat())
+                        ><Icon name="swords" size=14 /></button>
+                    }.into_any(),
+                    Some(round) => view! {
+                        <span class="combat-clock-round">
+                            {move_tr!("combat-round", {"round" => round})}
+                        </span>
+                        <button class="btn-icon"
+                            title=move_tr!("combat-next-turn")
+                            on:click=move |_| effects.update(|active| active.next_turn(&store.read()))
+                        ><Icon name="skip-forward" size=14 /></button>
+                        <button class="btn-icon btn-icon--danger"
+                            title=move_tr!("combat-end")
+                            on:click=move |_| effects.update(|active| active.end_combat())
+                        ><Icon name="x" size=14 /></button>
+                    }.into_any(),
+                }}
+            </div>
+
             // -- Add effect form --
             <div class="entry-item effect-add-form">
                 <button class="btn-icon btn-icon--success"
@@ -114,6 +147,8 @@ pub fn EffectsBlock() -> impl IntoView {
                             pool: None,
                             enabled: true,
                             scope,
+                            rounds: effect_rounds.get_untracked(),
+                            concentration: effect_concentration.get_untracked(),
                         };
 
                         // Check if expression has dice rolls
@@ -145,6 +180,8 @@ pub fn EffectsBlock() -> impl IntoView {
                                         }
                                         effect_desc.set(eff.description.clone());
                                         effect_scope.set(eff.scope.clone());
+                                        effect_rounds.set(eff.rounds);
+                                        effect_concentration.set(eff.concentration);
                                     }
                                 });
                             }
@@ -174,6 +211,8 @@ pub fn EffectsBlock() -> impl IntoView {
                             let description = effect.description.clone();
                             let scope = effect.scope.clone();
                             let enabled = effect.enabled;
+                            let concentration = effect.concentration;
+                            let rounds_str = effect.rounds.map(|rounds| rounds.to_string()).unwrap_or_default();
                             let effect_expr = effect.expr.clone();
                             view! {
                                 <div class="entry-item" class:disabled=!enabled>
@@ -214,6 +253,9 @@ pub fn EffectsBlock() -> impl IntoView {
                                     </div>
                                     {scope.map(|s| view! {
                                         <span class="entry-sublabel">{s.to_string()}</span>
+                                    })}
+                                    {concentration.then(|| view! {
+                                        <span class="entry-sublabel">{move_tr!("effect-concentration")}</span>
                                     })}
                                         <div class="entry-full-row session-item-expr-row">
                                             <input
@@ -242,6 +284,18 @@ pub fn EffectsBlock() -> impl IntoView {
                                                     }
                                                 }
                                             />
+                                            <input
+                                                type="number"
+                                                min="0"
+                                                class="session-qty-input"
+                                                title=move_tr!("effect-rounds")
+                                                placeholder="\u{221e}"
+                                                prop:value=rounds_str
+                                                on:change=move |ev| {
+                                                    let rounds = event_target_value(&ev).trim().parse::<u32>().ok();
+                                                    effects.update(|e| e.update_field(i, |eff| eff.rounds = rounds));
+                                                }
+                                            />
                                             {(!dice_rolls.is_empty()).then(|| {
                                                 let effect_expr = effect_expr.clone();
                                                 view! {
@@ -292,12 +346,40 @@ pub fn EffectsBlock() -> impl IntoView {
                         dice_parts.set_value(Some(parts));
                     };
 
+                    let roll_expr = expr.clone();
                     let on_submit = move |event: web_sys::SubmitEvent| {
                         event.prevent_default();
-                        let pool: DicePool = dice_parts
+                        let mut pool: DicePool = dice_parts
                             .with_value(|parts| parts.as_ref().map(|p| p.collect_dice()))
                             .unwrap_or_default();
 
+                        // Nothing typed in: roll the dice here and log them
+                        if pool.is_empty() {
+                            match roll_expr.roll_lenient(&*store.read_untracked()) {
+                                Ok(roll) => {
+                                    pool = roll.pool();
+                                    let title = match reroll_index.get_untracked() {
+                                        Some(index) => effects.with_untracked(|active| {
+                                            active.effects().get(index).map(|eff| eff.label().to_owned())
+                                        }),
+                                        None => pending_effect.with_untracked(|pending| {
+                                            pending.as_ref().map(|eff| eff.label().to_owned())
+                                        }),
+                                    };
+                                    rolls.update(|rolls| {
+                                        rolls.push(RollRecord::new(
+                                            title.unwrap_or_default(),
+                                            i18n.tr("effect-dice"),
+                                            roll_expr.to_string(),
+                                            roll,
+                                            false,
+                                        ))
+                                    });
+                                }
+                                Err(error) => log::error!("Effect dice roll failed: {error}"),
+                            }
+                        }
+
                         if let Some(effect_index) = reroll_index.get_untracked() {
                             // Re-roll existing effect
                             effects.update(|active| {
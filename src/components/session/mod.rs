@@ -1,20 +1,24 @@
 mod backpack;
 mod choices;
+mod conditions;
 mod damage_modifiers;
 mod effects;
 mod languages;
 mod resources;
+mod roll_log;
 mod spells;
 mod stats;
 mod weapons;
 
 pub use backpack::BackpackBlock;
 pub use choices::ChoicesBlock;
+pub use conditions::ConditionsBlock;
 pub use damage_modifiers::DamageModifiersBlock;
 pub use effects::EffectsBlock;
 pub use languages::LanguagesBlock;
 use leptos::prelude::*;
 pub use resources::ResourcesBlock;
+pub use roll_log::RollLogBlock;
 pub use spells::SpellsBlock;
 pub use stats::{StatsBlock, adv_icon};
 pub use weapons::WeaponsBlock;
@@ -0,0 +1,81 @@
+use leptos::prelude::*;
+use leptos_fluent::{I18n, move_tr};
+
+use crate::{
+    components::icon::Icon,
+    model::{RollLog, RollRecord},
+};
+
+fn record_view(record: &RollRecord) -> impl IntoView + use<> {
+    let dice = record
+        .dice
+        .iter()
+        .map(|die| {
+            view! {
+                <span class="roll-die" class:dropped=!die.kept title=format!("d{}", die.sides)>
+                    {die.value}
+                </span>
+            }
+        })
+        .collect_view();
+    let crit = record.crit;
+    view! {
+        <div class="roll-entry" class:crit=crit>
+            <span class="roll-entry-total">{record.total}</span>
+            <span class="roll-entry-title">
+                {record.title.clone()} " \u{2014} " {record.kind.clone()}
+                {crit.then(|| view! {
+                    " " <span class="roll-entry-crit">{move_tr!("roll-crit")}</span>
+                })}
+            </span>
+            <span class="entry-sublabel">{record.expr.clone()} " " {dice}</span>
+        </div>
+    }
+}
+
+/// Collapsible roll history: the latest roll is always visible, the rest of
+/// the log expands on demand.
+#[component]
+pub fn RollLogBlock() -> impl IntoView {
+    let rolls = expect_context::<RwSignal<RollLog>>();
+    let i18n = expect_context::<I18n>();
+    let expanded = RwSignal::new(false);
+
+    let entries = move || {
+        let expanded = expanded.get();
+        rolls.with(|rolls| {
+            rolls
+                .iter()
+                .take(if expanded { RollLog::LIMIT } else { 1 })
+                .map(record_view)
+                .collect_view()
+        })
+    };
+
+    view! {
+        <div class="session-section session-section-rolls" id="session-rolls">
+            <h3 class="session-section-title">
+                {move_tr!("roll-log")}
+                <span class="roll-log-actions">
+                    <button class="btn-icon"
+                        title=move || i18n.tr(if expanded.get() { "roll-log-collapse" } else { "roll-log-expand" })
+                        disabled=move || rolls.with(|rolls| rolls.len() < 2)
+                        on:click=move |_| expanded.update(|expanded| *expanded = !*expanded)
+                    >
+                        <Icon name=Signal::derive(move || if expanded.get() { "chevron-up" } else { "chevron-down" }) size=14 />
+                    </button>
+                    <button class="btn-icon btn-icon--danger" title=move_tr!("roll-log-clear")
+                        disabled=move || rolls.with(RollLog::is_empty)
+                        on:click=move |_| rolls.update(RollLog::clear)
+                    ><Icon name="x" size=14 /></button>
+                </span>
+            </h3>
+            <Show
+                when=move || !rolls.with(RollLog::is_empty)
+                fallback=|| view! { <p class="session-empty">{move_tr!("roll-log-empty")}</p> }
+            >
+                <div class="roll-log">{entries}</div>
+            </Show>
+        </div>
+    }
+}
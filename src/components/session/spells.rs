@@ -16,10 +16,11 @@ use crate::{
     },
     effective::EffectiveCharacter,
     model::{
-        Ability, Attribute, Character, CharacterStoreFields, EffectRange, FeatureValue,
-        SpellSlotLevel, SpellSlotPool, format_bonus,
+        Ability, Attribute, Character, CharacterStoreFields, EffectDefinition, EffectRange,
+        EquipmentStoreFields, FeatureValue, Item, ItemSpell, SpellSlotLevel, SpellSlotPool,
+        format_bonus,
     },
-    rules::RulesRegistry,
+    rules::{RulesRegistry, SpellList},
 };
 
 #[component]
@@ -34,6 +35,35 @@ pub fn SpellsBlock() -> impl IntoView {
     let show_calc = RwSignal::new(false);
     let calc_info = StoredValue::new(None::<EffectsCalcInfo>);
 
+    // Apply diceless self effects right away, otherwise open the calculator
+    let show_effects = move |effects: Vec<EffectDefinition>,
+                             extra_vars: BTreeMap<Attribute, i32>,
+                             title: String,
+                             spell_name: &str,
+                             fname: &str| {
+        let all_caster = effects.iter().all(|e| e.range == EffectRange::Caster);
+        if all_caster && all_self_effects_diceless(&effects, &store.read_untracked(), &extra_vars) {
+            apply_self_effects_now(
+                &effects,
+                spell_name,
+                fname,
+                &extra_vars,
+                &store,
+                eff.effects(),
+            );
+            return;
+        }
+
+        calc_info.set_value(Some(EffectsCalcInfo {
+            title,
+            effects,
+            extra_vars,
+            spell_name: spell_name.to_string(),
+            feature_name: fname.to_string(),
+        }));
+        show_calc.set(true);
+    };
+
     let open_calc = move |spell_name: &str,
                           spell_level: u32,
                           fname: &str,
@@ -51,7 +81,7 @@ pub fn SpellsBlock() -> impl IntoView {
                     registry.with_spell_list(&spells_def.list, |spell_map| {
                         spell_map
                             .get(spell_name)
-                            .map(|sd| sd.effects.clone())
+                            .map(|sd| sd.cast_effects())
                             .unwrap_or_default()
                     })
                 })
@@ -79,21 +109,6 @@ pub fn SpellsBlock() -> impl IntoView {
                 extra_vars.insert(Attribute::Cost, *cost as i32);
             }
 
-            // All effects are Caster with no dice — apply immediately, skip modal
-            let all_caster = effects.iter().all(|e| e.range == EffectRange::Caster);
-            if all_caster && all_self_effects_diceless(&effects, &character, &extra_vars) {
-                drop(character);
-                apply_self_effects_now(
-                    &effects,
-                    spell_name,
-                    fname,
-                    &extra_vars,
-                    &store,
-                    eff.effects(),
-                );
-                return;
-            }
-
             let spell_label = character
                 .feature_data
                 .get(fname)
@@ -111,16 +126,46 @@ pub fn SpellsBlock() -> impl IntoView {
             } else {
                 spell_label
             };
+            drop(character);
+
+            show_effects(effects, extra_vars, title, spell_name, fname);
+        }
+    };
+
+    // Items cast with their own save DC and attack bonus. The bonus already
+    // includes proficiency, so the rest of it stands in for the modifier.
+    // Spells without effects have nothing to show.
+    let open_item_calc = move |item_name: &str, spell: &ItemSpell, spell_label: String| {
+        let effects = registry.with_spell_list(
+            &SpellList::Ref {
+                from: spell.list.clone(),
+            },
+            |spell_map| {
+                spell_map
+                    .get(spell.name.as_str())
+                    .map(|sd| sd.cast_effects())
+                    .unwrap_or_default()
+            },
+        );
+        if effects.is_empty() {
+            return;
+        }
 
-            calc_info.set_value(Some(EffectsCalcInfo {
-                title,
-                effects,
-                extra_vars,
-                spell_name: spell_name.to_string(),
-                feature_name: fname.to_string(),
-            }));
-            show_calc.set(true);
+        let mut extra_vars = BTreeMap::new();
+        {
+            let character = store.read_untracked();
+            extra_vars.insert(Attribute::SlotLevel, spell.level as i32);
+            extra_vars.insert(Attribute::CasterLevel(None), character.level() as i32);
+            extra_vars.insert(
+                Attribute::CasterModifier,
+                spell.attack_bonus - character.proficiency_bonus(),
+            );
         }
+        extra_vars.insert(Attribute::SpellDc, spell.save_dc as i32);
+        extra_vars.insert(Attribute::SpellAttack, spell.attack_bonus);
+
+        let title = format!("{spell_label} ({item_name})");
+        show_effects(effects, extra_vars, title, &spell.name, "");
     };
 
     let spells_view = move || {
@@ -397,8 +442,111 @@ pub fn SpellsBlock() -> impl IntoView {
             .collect_view()
     };
 
+    let items = store.equipment().items();
+    let item_spells_section = move |item_idx: usize, item: &Item| {
+        let item_name = StoredValue::new(item.name.clone());
+        let uses_badge = match &item.charges {
+            Some(charges) => view! {
+                <span class="entry-badge" title=move_tr!("item-charges")>
+                    {charges.available()} "/" {charges.max}
+                </span>
+            }
+            .into_any(),
+            None => view! {
+                <span class="entry-badge" title=move_tr!("item-single-use")>
+                    "\u{00d7}" {item.quantity}
+                </span>
+            }
+            .into_any(),
+        };
+
+        let list_items = item
+            .spells
+            .iter()
+            .enumerate()
+            .map(|(spell_idx, spell)| {
+                let (spell_label, description) = registry.with_spell_list(
+                    &SpellList::Ref {
+                        from: spell.list.clone(),
+                    },
+                    |spell_map| {
+                        spell_map.get(spell.name.as_str()).map_or_else(
+                            || (spell.name.clone(), String::new()),
+                            |sd| (sd.label().to_string(), sd.description.clone()),
+                        )
+                    },
+                );
+                let level_str = if spell.level == 0 {
+                    tr!("session-cantrips")
+                } else {
+                    tr!("slot-level", {"level" => spell.level})
+                };
+                let cost_badge = (item.charges.is_some() && spell.cost > 0).then(|| {
+                    view! {
+                        <span class="entry-badge">
+                            {tr!("item-charges-cost", {"cost" => spell.cost})}
+                        </span>
+                    }
+                });
+                let stats = tr!("item-spell-stats", {
+                    "dc" => spell.save_dc,
+                    "attack" => format_bonus(spell.attack_bonus),
+                });
+                let stored_spell = StoredValue::new(spell.clone());
+                let label = StoredValue::new(spell_label.clone());
+                let badge = view! {
+                    <span class="entry-badge">
+                        <span class="session-spell-level">{level_str}</span>
+                        <span class="session-spell-level">{stats}</span>
+                        {cost_badge}
+                        <CastButton
+                            disabled=!item.can_cast(spell_idx)
+                            on_cast=Callback::new(move |_: CastOption| {
+                                item_name.with_value(|name| {
+                                    stored_spell.with_value(|spell| {
+                                        open_item_calc(name, spell, label.get_value())
+                                    })
+                                });
+                                store.update(|ch| {
+                                    ch.equipment.cast_from_item(item_idx, spell_idx);
+                                });
+                            })
+                        />
+                    </span>
+                }
+                .into_any();
+
+                SessionListItem {
+                    name: spell_label,
+                    description,
+                    badge: Some(badge),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        view! {
+            <div class="session-subsection">
+                <h4 class="session-subsection-title">
+                    {item_name.get_value()} " " {uses_badge}
+                </h4>
+                <SessionList items=list_items />
+            </div>
+        }
+    };
+
+    let item_spells_view = move || {
+        registry.track_spell_cache();
+        items
+            .read()
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| !item.spells.is_empty())
+            .map(|(item_idx, item)| item_spells_section(item_idx, item))
+            .collect_view()
+    };
     view! {
         {spells_view}
+        {item_spells_view}
         <EffectsCalcModal show=show_calc info=calc_info />
     }
 }
@@ -1,14 +1,15 @@
 use leptos::{html::Input, prelude::*};
-use leptos_fluent::{I18n, move_tr};
+use leptos_fluent::{I18n, move_tr, tr};
 use reactive_stores::Store;
 use strum::IntoEnumIterator;
 
 use crate::{
-    components::icon::Icon,
+    components::{icon::Icon, modal::Modal},
     effective::{AdvantageState, EffectiveCharacter},
+    expr::Expr,
     model::{
-        Ability, Character, CharacterStoreFields, CombatStatsStoreFields, DamageType, Skill,
-        Translatable, format_bonus,
+        Ability, Attribute, Character, CharacterStoreFields, CombatStatsStoreFields, DamageType,
+        RollLog, RollRecord, Skill, Translatable, format_bonus,
     },
 };
 
@@ -45,6 +46,8 @@ pub fn StatsBlock() -> impl IntoView {
     };
 
     let show_damage_picker = RwSignal::new(false);
+    let show_concentration_save = RwSignal::new(false);
+    let concentration_dc = RwSignal::new(0u32);
 
     let apply_damage = move |damage_type: Option<DamageType>| {
         let damage = damage_value();
@@ -56,12 +59,28 @@ pub fn StatsBlock() -> impl IntoView {
                 }
                 None => damage,
             };
-            combat.update(|combat| combat.damage(modified));
+            let dc = combat
+                .try_update(|combat| combat.damage(modified))
+                .flatten();
+            let concentrating = eff
+                .effects()
+                .with_untracked(|effects| effects.concentration().is_some());
+            if let Some(dc) = dc
+                && concentrating
+            {
+                if combat.hp_current().get_untracked() == 0 {
+                    eff.effects()
+                        .update(|effects| effects.end_concentration(&store.read_untracked()));
+                } else {
+                    concentration_dc.set(dc);
+                    show_concentration_save.set(true);
+                }
+            }
         }
         show_damage_picker.set(false);
     };
 
-    move || {
+    let stats_view = move || {
         let modifiers = eff.damage_modifiers();
         let has_modifiers = !modifiers.is_empty();
 
@@ -278,5 +297,118 @@ pub fn StatsBlock() -> impl IntoView {
                 </div>
             </div>
         }
+    };
+
+    view! {
+        {stats_view}
+        <ConcentrationSaveModal show=show_concentration_save dc=concentration_dc />
+    }
+}
+
+/// Constitution save prompt shown after taking damage while concentrating.
+#[component]
+fn ConcentrationSaveModal(show: RwSignal<bool>, dc: RwSignal<u32>) -> impl IntoView {
+    let store = expect_context::<Store<Character>>();
+    let eff = expect_context::<EffectiveCharacter>();
+    let effects = eff.effects();
+    let rolls = expect_context::<RwSignal<RollLog>>();
+    let i18n = expect_context::<I18n>();
+    let result = RwSignal::new(Option::<i32>::None);
+
+    Effect::new(move || {
+        if show.get() {
+            result.set(None);
+        }
+    });
+
+    let end_concentration = move || {
+        effects.update(|effects| effects.end_concentration(&store.read_untracked()));
+    };
+
+    let roll_save = move |_| {
+        let dice = eff.save_advantage(Ability::Constitution).d20();
+        let text = format!("{dice} + CON.SAVE");
+        let expr = match text.parse::<Expr<Attribute>>() {
+            Ok(expr) => expr,
+            Err(error) => {
+                log::error!("Invalid concentration save expression: {error}");
+                return;
+            }
+        };
+        match eff.roll(&expr, false) {
+            Ok(roll) => {
+                let total = roll.total;
+                result.set(Some(total));
+                rolls.update(|rolls| {
+                    rolls.push(RollRecord::new(
+                        i18n.tr("concentration-save"),
+                        tr!("concentration-save-dc", {"dc" => dc.get_untracked()}),
+                        text,
+                        roll,
+                        false,
+                    ))
+                });
+                if total < dc.get_untracked() as i32 {
+                    end_concentration();
+                }
+            }
+            Err(error) => log::error!("Concentration save roll failed: {error}"),
+        }
+    };
+
+    let effect_label = move || {
+        effects.with(|effects| {
+            effects
+                .concentration()
+                .map(|effect| effect.label().to_owned())
+        })
+    };
+
+    view! {
+        <Modal show=show title=move_tr!("concentration-save")>
+            <div class="concentration-save">
+                <p>
+                    {move || effect_label().unwrap_or_default()}
+                    " \u{2014} "
+                    {move_tr!("concentration-save-dc", {"dc" => dc.get()})}
+                    " ("
+                    {move || format_bonus(eff.saving_throw_bonus(Ability::Constitution))}
+                    ")"
+                </p>
+                {move || result.get().map(|total| {
+                    let kept = total >= dc.get() as i32;
+                    view! {
+                        <p class="concentration-save-result" class:failed=!kept>
+                            <strong>{total}</strong>
+                            " \u{2014} "
+                            {if kept { move_tr!("concentration-kept") } else { move_tr!("concentration-lost") }}
+                        </p>
+                    }
+                })}
+                <div class="dice-pool-footer">
+                    <Show
+                        when=move || result.get().is_none()
+                        fallback=move || view! {
+                            <button type="button" class="btn-confirm" on:click=move |_| show.set(false)>
+                                <Icon name="check" size=16 />
+                                " " {move_tr!("btn-confirm")}
+                            </button>
+                        }
+                    >
+                        <button type="button" class="btn-confirm" on:click=roll_save>
+                            <Icon name="dices" size=16 />
+                            " " {move_tr!("concentration-roll")}
+                        </button>
+                        <button type="button" class="btn-icon btn-icon--danger"
+                            title=move_tr!("concentration-end")
+                            on:click=move |_| {
+                                end_concentration();
+                                show.set(false);
+                            }
+                        ><Icon name="x" size=16 /></button>
+                    </Show>
+                </div>
+            </div>
+        </Modal>
     }
 }
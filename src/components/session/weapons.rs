@@ -11,12 +11,26 @@ use crate::{
         icon::Icon,
         session_list::{SessionList, SessionListItem},
     },
-    effective::EffectiveCharacter,
+    effective::{AdvantageState, EffectiveCharacter},
+    expr::Expr,
     model::{
-        Character, CharacterStoreFields, EffectDefinition, EquipmentStoreFields, Translatable,
+        Attribute, Character, CharacterStoreFields, EffectDefinition, EquipmentStoreFields,
+        RollLog, RollRecord, Translatable, Weapon, WeaponProperty,
     },
 };
 
+/// Attack roll expression text for a weapon: d20 (with advantage applied),
+/// the global attack bonus and the weapon's own bonus formula.
+fn attack_roll_text(advantage: AdvantageState, weapon: &Weapon) -> String {
+    let d20 = advantage.d20();
+    let bonus = weapon.attack_bonus_expr_str();
+    match bonus.strip_prefix('-') {
+        _ if bonus == "0" => format!("{d20} + ATK"),
+        Some(abs) => format!("{d20} + ATK - {abs}"),
+        None => format!("{d20} + ATK + {bonus}"),
+    }
+}
+
 #[component]
 pub fn WeaponsBlock() -> impl IntoView {
     let store = expect_context::<Store<Character>>();
@@ -24,17 +38,85 @@ pub fn WeaponsBlock() -> impl IntoView {
     let i18n = expect_context::<I18n>();
     let weapons = store.equipment().weapons();
 
+    let rolls = expect_context::<RwSignal<RollLog>>();
+
     let show_calc = RwSignal::new(false);
     let calc_info = StoredValue::new(None::<EffectsCalcInfo>);
 
+    // Roll the attack, then every damage effect; a natural 20 doubles the
+    // damage dice. Ammunition weapons need a piece in the backpack and spend
+    // it once the attack is rolled.
+    let roll_attack = move |weapon: &Weapon, two_handed: bool| {
+        let uses_ammunition =
+            weapon.has(WeaponProperty::Ammunition) && !weapon.ammunition.is_empty();
+        if uses_ammunition
+            && store
+                .read_untracked()
+                .equipment
+                .ammunition_count(&weapon.ammunition)
+                .is_none_or(|count| count == 0)
+        {
+            return;
+        }
+
+        let text = attack_roll_text(eff.attack_advantage(), weapon);
+        let Some(attack) = text
+            .parse::<Expr<Attribute>>()
+            .ok()
+            .and_then(|expr| eff.roll(&expr, false).ok())
+        else {
+            return;
+        };
+        if uses_ammunition {
+            store.update(|character| {
+                if let Some(item) = character.equipment.ammunition_mut(&weapon.ammunition) {
+                    item.quantity = item.quantity.saturating_sub(1);
+                }
+            });
+        }
+        let crit = attack.natural(20) == Some(20);
+        let mut records = vec![RollRecord::new(
+            weapon.name.clone(),
+            i18n.tr("roll-attack"),
+            text,
+            attack,
+            crit,
+        )];
+
+        for effect in weapon
+            .attack_effects(two_handed)
+            .iter()
+            .filter(|e| !e.expr.is_empty())
+        {
+            let Ok(roll) = eff.roll(&effect.expr, crit) else {
+                continue;
+            };
+            let kind = if !effect.name.is_empty() {
+                effect.name.clone()
+            } else if let Some(dt) = effect.damage_type {
+                i18n.tr(dt.tr_key())
+            } else {
+                i18n.tr("roll-damage")
+            };
+            records.push(RollRecord::new(
+                weapon.name.clone(),
+                kind,
+                effect.expr.to_string(),
+                roll,
+                crit,
+            ));
+        }
+
+        rolls.update(|rolls| records.into_iter().for_each(|record| rolls.push(record)));
+    };
+
     let content = move || {
-        let global_atk = eff.attack_bonus();
         let items = weapons
             .read()
             .iter()
             .filter(|w| !w.name.is_empty())
             .map(|w| {
-                let total_atk = w.attack_bonus + global_atk;
+                let total_atk = eff.weapon_attack_bonus(w);
                 let name_atk = if total_atk != 0 {
                     format!("{} {:+}", w.name, total_atk)
                 } else {
@@ -61,8 +143,14 @@ pub fn WeaponsBlock() -> impl IntoView {
                 let more = (active_effects.len() > 1)
                     .then(|| view! { <span class="entry-badge">"\u{2026}"</span> });
 
-                // Full list for expandable description
-                let description = if active_effects.len() > 1 {
+                // Properties and full effect list for expandable description
+                let properties = w
+                    .properties
+                    .iter()
+                    .map(|property| i18n.tr(property.tr_key()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let effects_description = if active_effects.len() > 1 {
                     active_effects
                         .iter()
                         .map(|effect| {
@@ -83,6 +171,53 @@ pub fn WeaponsBlock() -> impl IntoView {
                 } else {
                     String::new()
                 };
+                let description = [properties, effects_description]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                let ammo =
+                    (w.has(WeaponProperty::Ammunition) && !w.ammunition.is_empty()).then(|| {
+                        store
+                            .equipment()
+                            .read()
+                            .ammunition_count(&w.ammunition)
+                            .unwrap_or(0)
+                    });
+                let ammo_badge = ammo.map(|count| {
+                    let title = w.ammunition.clone();
+                    view! {
+                        <span class="entry-badge" title=title>
+                            <Icon name="bow-arrow" size=14 /> {count}
+                        </span>
+                    }
+                });
+                let out_of_ammo = ammo == Some(0);
+
+                let attack_button = {
+                    let weapon = w.clone();
+                    view! {
+                        <button class="btn-icon" disabled=out_of_ammo
+                            title=move_tr!("roll-attack")
+                            on:click=move |_| roll_attack(&weapon, false)
+                        >
+                            <Icon name="dices" size=14 />
+                        </button>
+                    }
+                };
+                let two_handed_button = (w.has(WeaponProperty::Versatile) && w.versatile.is_some())
+                    .then(|| {
+                        let weapon = w.clone();
+                        view! {
+                            <button class="btn-icon" disabled=out_of_ammo
+                                title=move_tr!("roll-attack-two-handed")
+                                on:click=move |_| roll_attack(&weapon, true)
+                            >
+                                <Icon name="swords" size=14 />
+                            </button>
+                        }
+                    });
 
                 let cast_button = has_effects.then(|| {
                     let effects: Vec<EffectDefinition> =
@@ -105,11 +240,15 @@ pub fn WeaponsBlock() -> impl IntoView {
                 SessionListItem {
                     name: name_atk,
                     description,
-                    badge: if first_badge.is_none() && cast_button.is_none() {
-                        None
-                    } else {
-                        Some(view! { <>{first_badge}{more}{cast_button}</> }.into_any())
-                    },
+                    badge: Some(
+                        view! {
+                            <>
+                                {first_badge}{more}{ammo_badge}{attack_button}{two_handed_button}
+                                {cast_button}
+                            </>
+                        }
+                        .into_any(),
+                    ),
                 }
             })
             .collect::<Vec<_>>();
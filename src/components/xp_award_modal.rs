@@ -0,0 +1,112 @@
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use reactive_stores::Store;
+
+use crate::{
+    components::{icon::Icon, modal::Modal},
+    model::{Character, CharacterIdentityStoreFields, CharacterStoreFields, XpAward},
+};
+
+/// Awards experience points with an optional reason and lists past awards.
+/// Calls `on_level_up` when an award reaches the next level's threshold.
+#[component]
+pub fn XpAwardModal(
+    show: RwSignal<bool>,
+    #[prop(into)] on_level_up: Callback<()>,
+) -> impl IntoView {
+    let store = expect_context::<Store<Character>>();
+    let amount = RwSignal::new(String::new());
+    let reason = RwSignal::new(String::new());
+
+    let parsed_amount = move || {
+        amount
+            .get()
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|amount| *amount > 0)
+    };
+
+    let on_award = move |_| {
+        let Some(xp) = parsed_amount() else {
+            return;
+        };
+        let award = XpAward::new(xp, reason.get_untracked().trim().to_string());
+        let crossed = store.write().award_xp(award);
+        amount.set(String::new());
+        reason.set(String::new());
+        if crossed {
+            show.set(false);
+            on_level_up.run(());
+        }
+    };
+
+    let next_level = move || {
+        store.with(|character| {
+            character.next_level_xp().map(|xp| {
+                tr!("xp-next-level", {
+                    "xp" => xp,
+                    "level" => character.level() + 1,
+                })
+            })
+        })
+    };
+
+    let awards = move || {
+        store.identity().xp_awards().with(|awards| {
+            awards
+                .iter()
+                .rev()
+                .map(|award| {
+                    let date = award.short_date().to_string();
+                    let reason = award.reason.clone();
+                    view! {
+                        <div class="xp-award-entry">
+                            <span class="xp-award-amount">"+" {award.amount}</span>
+                            <span class="xp-award-reason">{reason}</span>
+                            <span class="entry-sublabel">{date}</span>
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <Modal show title=move_tr!("xp-award-title")>
+            <div class="xp-award">
+                <p class="entry-sublabel">{next_level}</p>
+                <div class="xp-award-form">
+                    <input
+                        type="number"
+                        min="1"
+                        placeholder=move_tr!("xp-award-amount")
+                        prop:value=amount
+                        on:input=move |e| amount.set(event_target_value(&e))
+                    />
+                    <input
+                        type="text"
+                        placeholder=move_tr!("xp-award-reason")
+                        prop:value=reason
+                        on:input=move |e| reason.set(event_target_value(&e))
+                    />
+                    <button
+                        type="button"
+                        class="btn-confirm"
+                        disabled=move || parsed_amount().is_none()
+                        on:click=on_award
+                    >
+                        <Icon name="plus" size=16 />
+                        " " {move_tr!("xp-award")}
+                    </button>
+                </div>
+                <Show
+                    when=move || !store.identity().xp_awards().read().is_empty()
+                    fallback=|| view! { <p class="session-empty">{move_tr!("xp-award-empty")}</p> }
+                >
+                    <div class="xp-award-log">{awards}</div>
+                </Show>
+            </div>
+        </Modal>
+    }
+}
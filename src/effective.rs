@@ -4,8 +4,11 @@ use leptos::prelude::*;
 use reactive_stores::Store;
 use strum::IntoEnumIterator;
 
-use crate::model::{
-    Ability, ActiveEffects, Attribute, Character, DamageModifiers, DamageType, Skill,
+use crate::{
+    expr::{self, Eval, Expr, Roll},
+    model::{
+        Ability, ActiveEffects, Attribute, Character, DamageModifiers, DamageType, Skill, Weapon,
+    },
 };
 
 /// Advantage/disadvantage state for a roll type.
@@ -26,6 +29,34 @@ impl From<i32> for AdvantageState {
     }
 }
 
+impl AdvantageState {
+    /// Dice expression for a d20 roll in this state.
+    pub fn d20(self) -> &'static str {
+        match self {
+            Self::Advantage => "2d20kh1",
+            Self::Disadvantage => "2d20kl1",
+            Self::Flat => "1d20",
+        }
+    }
+}
+
+/// Read-only expression context that resolves attributes with effects
+/// applied.
+struct EffectiveContext<'a> {
+    character: &'a Character,
+    effects: &'a ActiveEffects,
+}
+
+impl expr::Context<Attribute, i32> for EffectiveContext<'_> {
+    fn assign(&mut self, var: Attribute, _value: i32) -> Result<(), expr::Error> {
+        Err(expr::Error::read_only_var(var))
+    }
+
+    fn resolve(&self, var: Attribute) -> Result<i32, expr::Error> {
+        Ok(self.effects.resolve(self.character, var))
+    }
+}
+
 /// Reactive read-only view of a character with effects applied.
 /// Holds signals, so it's `Copy` and can be used directly in closures.
 #[derive(Clone, Copy)]
@@ -47,6 +78,21 @@ impl EffectiveCharacter {
         self.effects.read().resolve(&self.store.read(), attr)
     }
 
+    /// Evaluate an expression against the effective character, rolling any
+    /// dice it contains and keeping every die thrown. With `crit` set, dice
+    /// counts are doubled.
+    pub fn roll(&self, expr: &Expr<Attribute>, crit: bool) -> Result<Roll, expr::Error> {
+        let character = self.store.read_untracked();
+        let effects = self.effects.read_untracked();
+        expr.roll(
+            &EffectiveContext {
+                character: &character,
+                effects: &effects,
+            },
+            crit,
+        )
+    }
+
     pub fn ability_modifier(&self, ability: Ability) -> i32 {
         self.get(Attribute::Modifier(ability))
     }
@@ -75,6 +121,23 @@ impl EffectiveCharacter {
         self.get(Attribute::AttackBonus)
     }
 
+    /// Total attack bonus of a weapon: its derived bonus plus the global
+    /// `ATK` bonus, with effects applied.
+    pub fn weapon_attack_bonus(&self, weapon: &Weapon) -> i32 {
+        let bonus = {
+            let character = self.store.read();
+            let effects = self.effects.read();
+            weapon
+                .attack_bonus_expr()
+                .eval(&EffectiveContext {
+                    character: &character,
+                    effects: &effects,
+                })
+                .unwrap_or(weapon.attack_bonus)
+        };
+        bonus + self.attack_bonus()
+    }
+
     pub fn attack_count(&self) -> i32 {
         self.get(Attribute::Attacks)
     }
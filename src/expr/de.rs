@@ -1,8 +1,8 @@
-use std::{fmt, marker::PhantomData, ops::Neg, str::FromStr, sync::Arc};
+use std::{fmt, marker::PhantomData, ops::Neg, str::FromStr};
 
 use serde::{Deserialize, Deserializer, de};
 
-use crate::expr::{Block, Expr, Op};
+use crate::expr::{Expr, Op};
 
 impl<'de, Var, Val> Deserialize<'de> for Expr<Var, Val>
 where
@@ -41,8 +41,7 @@ where
                         BlocksOrOps::Blocks(blocks) => blocks,
                         BlocksOrOps::Ops(ops) => vec![ops],
                     };
-                let blocks: Arc<[Block<Var, Val>]> = blocks.into_iter().map(Block::from).collect();
-                Ok(Expr(blocks))
+                Ok(Expr::from_parts(blocks, Vec::new()))
             }
 
             fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Expr<Var, Val>, A::Error> {
@@ -51,12 +50,52 @@ where
                     ops: Vec<Vec<Op<Var, Val>>>,
                 }
                 let fields = ExprFields::deserialize(de::value::MapAccessDeserializer::new(map))?;
-                let blocks: Arc<[Block<Var, Val>]> =
-                    fields.ops.into_iter().map(Block::from).collect();
-                Ok(Expr(blocks))
+                Ok(Expr::from_parts(fields.ops, Vec::new()))
             }
         }
 
+        // Binary formats (postcard) aren't self-describing: read the blocks
+        // and, after an empty marker block, the `let` names written by
+        // `Serialize`.
+        struct BinaryVisitor<Var, Val>(PhantomData<(Var, Val)>);
+
+        impl<'de, Var, Val> de::Visitor<'de> for BinaryVisitor<Var, Val>
+        where
+            Var: Deserialize<'de>,
+            Val: Deserialize<'de>,
+        {
+            type Value = Expr<Var, Val>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence of blocks")
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Expr<Var, Val>, A::Error> {
+                let Some(first) = seq.next_element::<Vec<Op<Var, Val>>>()? else {
+                    return Ok(Expr::default());
+                };
+                let (mut blocks, locals) = if first.is_empty() {
+                    let locals = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                    (Vec::new(), locals)
+                } else {
+                    (vec![first], Vec::new())
+                };
+                while let Some(block) = seq.next_element()? {
+                    blocks.push(block);
+                }
+                Ok(Expr::from_parts(blocks, locals))
+            }
+        }
+
+        if !deserializer.is_human_readable() {
+            return deserializer.deserialize_seq(BinaryVisitor(PhantomData));
+        }
+
         deserializer.deserialize_any(ExprVisitor(PhantomData))
     }
 }
@@ -16,6 +16,7 @@ pub enum Error {
     InvalidBlock(u8),
     GuardFailed,
     RngFailed,
+    UnboundLocal(u8),
 }
 
 impl Error {
@@ -55,6 +56,7 @@ impl fmt::Display for Error {
             Error::InvalidBlock(idx) => write!(f, "invalid block index: {idx}"),
             Error::GuardFailed => write!(f, "guard condition failed"),
             Error::RngFailed => write!(f, "random number generation failed"),
+            Error::UnboundLocal(slot) => write!(f, "local binding #{slot} used before it was set"),
         }
     }
 }
@@ -5,8 +5,8 @@ use std::{
 
 use super::eval_block;
 use crate::expr::{
-    Context, Expr, Op, avg_hp,
-    ops::{BLOCK_MAIN, BlockIndex},
+    Context, Error, Expr, Op, avg_hp,
+    ops::{BLOCK_MAIN, BlockIndex, Slot},
     stack::Stack,
 };
 
@@ -20,6 +20,11 @@ pub struct ExprAnalysis {
     pub active_args: Vec<u8>,
     /// ARG indices constrained to 0/1 by `in(ARG.n, 0, 1)`.
     pub boolean_args: BTreeSet<u8>,
+    /// `let` slots whose value depends on ARGs, with the ARG index when the
+    /// slot is bound to a bare ARG (`let n = ARG.0`).
+    arg_slots: BTreeMap<Slot, Option<u8>>,
+    /// Visit every branch regardless of condition values.
+    exhaustive: bool,
 }
 
 impl ExprAnalysis {
@@ -46,6 +51,24 @@ impl ExprAnalysis {
         analysis
     }
 
+    /// Analyze an expression without a character: every `if`/`guard` branch
+    /// is visited, so `active_args` holds each ARG that gets an input field
+    /// for at least some character.
+    pub fn analyze_all<Var>(
+        expr: &Expr<Var, i32>,
+        is_arg: impl Fn(&Var) -> Option<u8> + Copy,
+    ) -> Self
+    where
+        Var: Copy + fmt::Display,
+    {
+        let mut analysis = Self {
+            exhaustive: true,
+            ..Self::default()
+        };
+        let _ = analysis.analyze_block(expr, &NoContext, is_arg, BLOCK_MAIN);
+        analysis
+    }
+
     fn analyze_block<Var, Ctx>(
         &mut self,
         expr: &Expr<Var, i32>,
@@ -64,19 +87,51 @@ impl ExprAnalysis {
         // State machine for detecting `in(ARG.n, 0, 1)` patterns inline.
         // Tracks (arg_index, steps_matched): 1 = saw Arg, 2 = saw 0, 3 = saw 1.
         let mut bool_detect: Option<(u8, u8)> = None;
+        // Start of the current statement and whether it has read ARGs, to
+        // carry ARG dependence through `let` bindings.
+        let mut stmt_start = 0;
+        let mut stmt_has_args = false;
 
-        for &op in ops.iter() {
+        for (i, &op) in ops.iter().enumerate() {
             match op {
                 Op::PushVar(var) => {
                     if let Some(idx) = is_arg(&var) {
                         has_args = true;
+                        stmt_has_args = true;
                         self.active_args.push(idx);
                         stack.push(0);
                     } else {
                         stack.push(ctx.resolve(var).unwrap_or(0));
                     }
                 }
-                Op::Assign(_) => {}
+                Op::Assign(_) => {
+                    stmt_start = i + 1;
+                    stmt_has_args = false;
+                }
+                Op::Store(slot) => {
+                    if stmt_has_args {
+                        let bare_arg = match &ops[stmt_start..i] {
+                            [Op::PushVar(var)] => is_arg(var),
+                            [Op::Load(from)] => self.arg_slots.get(from).copied().flatten(),
+                            _ => None,
+                        };
+                        self.arg_slots.insert(slot, bare_arg);
+                    } else {
+                        self.arg_slots.remove(&slot);
+                    }
+                    let _ = stack.store(slot);
+                    stmt_start = i + 1;
+                    stmt_has_args = false;
+                }
+                Op::Load(slot) => {
+                    if self.arg_slots.contains_key(&slot) {
+                        has_args = true;
+                        stmt_has_args = true;
+                    }
+                    if stack.load(slot).is_err() {
+                        stack.push(0);
+                    }
+                }
                 Op::Roll => {
                     let (count, sides) = stack.pop2().unwrap_or((0, 0));
                     if count > 0 && sides > 0 {
@@ -106,7 +161,7 @@ impl ExprAnalysis {
                     // Interactive condition (has ARGs) → visit both branches
                     // to discover all reachable ARGs. Non-interactive false
                     // condition → prune the then branch.
-                    if cond != 0 || last_eval_had_args {
+                    if cond != 0 || last_eval_had_args || self.exhaustive {
                         // Visit both branches for ARG discovery; push
                         // then-branch result for stack continuity.
                         let mut pushed = false;
@@ -141,6 +196,12 @@ impl ExprAnalysis {
             // Advance boolean-arg pattern: Arg(n) → PushConst(0) → PushConst(1) → In
             bool_detect = match op {
                 Op::PushVar(var) => is_arg(&var).map(|idx| (idx, 1)),
+                Op::Load(slot) => self
+                    .arg_slots
+                    .get(&slot)
+                    .copied()
+                    .flatten()
+                    .map(|idx| (idx, 1)),
                 Op::PushConst(0) if matches!(bool_detect, Some((_, 1))) => {
                     Some((bool_detect.unwrap().0, 2))
                 }
@@ -158,6 +219,19 @@ impl ExprAnalysis {
     }
 }
 
+/// Context for [`ExprAnalysis::analyze_all`]: every variable reads as 0.
+struct NoContext;
+
+impl<Var> Context<Var, i32> for NoContext {
+    fn assign(&mut self, _var: Var, _value: i32) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn resolve(&self, _var: Var) -> Result<i32, Error> {
+        Ok(0)
+    }
+}
+
 struct AnalyzedBlock {
     has_args: bool,
     result: i32,
@@ -1,24 +1,36 @@
 use std::fmt;
 
 use super::Interpreter;
-use crate::expr::{Error, Op, ops::BlockIndex, stack::Stack};
+use crate::expr::{
+    Error, Op,
+    ops::{BlockIndex, Slot},
+    stack::Stack,
+};
 
+#[derive(Clone)]
 struct Frag {
     text: String,
     prec: u8, // 0=assign, 1=or, 2=and, 3=cmp, 4=add/sub, 5=mul/div, 6=unary, 7=atom
 }
 
-pub struct Formatter {
+pub struct Formatter<'a> {
     stack: Stack<Frag>,
+    /// Names of `let` bindings, indexed by slot.
+    locals: &'a [Box<str>],
 }
 
-impl Formatter {
-    pub fn new() -> Self {
+impl<'a> Formatter<'a> {
+    pub fn new(locals: &'a [Box<str>]) -> Self {
         Self {
             stack: Stack::new(),
+            locals,
         }
     }
 
+    fn local(&self, slot: Slot) -> &str {
+        self.locals.get(slot as usize).map_or("_", |name| name)
+    }
+
     fn push(&mut self, text: String, prec: u8) {
         self.stack.push(Frag { text, prec });
     }
@@ -57,7 +69,7 @@ impl Formatter {
     }
 }
 
-impl<Var: Copy + fmt::Display, Val: Copy + fmt::Display> Interpreter<Var, Val> for Formatter {
+impl<Var: Copy + fmt::Display, Val: Copy + fmt::Display> Interpreter<Var, Val> for Formatter<'_> {
     type Output = String;
 
     fn exec(&mut self, op: Op<Var, Val>) -> Result<Option<BlockIndex>, Error> {
@@ -133,6 +145,13 @@ impl<Var: Copy + fmt::Display, Val: Copy + fmt::Display> Interpreter<Var, Val> f
                 let a = self.stack.pop()?;
                 self.push(format!("in({}, {}, {})", a.text, b.text, c.text), 3);
             }
+            Op::Store(slot) => {
+                let val = self.stack.pop()?;
+                self.push(format!("let {} = {}", self.local(slot), val.text), 0);
+            }
+            Op::Load(slot) => {
+                self.push(self.local(slot).to_string(), 7);
+            }
             Op::Eval(_) | Op::EvalIf(_, _) => {} // intercepted by format_block
         }
         Ok(None)
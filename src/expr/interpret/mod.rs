@@ -2,12 +2,14 @@ mod analyze;
 mod dice;
 mod evaluator;
 mod formatter;
+mod roller;
 
 pub use self::{
     analyze::ExprAnalysis,
     dice::{DicePool, DicePoolEvaluator},
     evaluator::{Evaluator, ReadOnlyEvaluator},
     formatter::Formatter,
+    roller::{DieRoll, Roll, RollEvaluator},
 };
 use crate::expr::{
     Error, Op, avg_hp,
@@ -174,6 +176,8 @@ fn eval_op<Var>(stack: &mut Stack<i32>, op: Op<Var, i32>) -> Result<Option<Block
             let (a, b, c) = stack.pop3()?;
             stack.push((b <= a && a <= c) as i32);
         }
+        Op::Store(slot) => stack.store(slot)?,
+        Op::Load(slot) => stack.load(slot)?,
         Op::Eval(idx) => return eval_block(idx),
         Op::EvalIf(then_idx, else_idx) => {
             let cond = stack.pop()?;
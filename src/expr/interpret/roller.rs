@@ -0,0 +1,190 @@
+use std::{collections::BTreeMap, fmt, marker::PhantomData};
+
+use serde::{Deserialize, Serialize};
+
+use super::{DicePool, Interpreter, eval_op, roll_die};
+use crate::expr::{Context, Error, Op, ops::BlockIndex, stack::Stack};
+
+// --- RollEvaluator (random dice, records every die thrown) ---
+
+/// A single die thrown while rolling an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DieRoll {
+    pub sides: u32,
+    pub value: u32,
+    /// `false` if the die was discarded by a keep/drop/explode modifier.
+    pub kept: bool,
+}
+
+/// Outcome of rolling an expression: the total and every die thrown.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Roll {
+    pub total: i32,
+    pub dice: Vec<DieRoll>,
+}
+
+impl Roll {
+    /// Face of the first kept die with the given number of sides, e.g. the
+    /// natural d20 of an attack roll (after advantage/disadvantage).
+    pub fn natural(&self, sides: u32) -> Option<u32> {
+        self.dice
+            .iter()
+            .find(|die| die.sides == sides && die.kept)
+            .map(|die| die.value)
+    }
+
+    /// Every die thrown (dropped ones included) as a preset pool, so the
+    /// same expression replays this roll under a `DicePoolEvaluator`.
+    pub fn pool(&self) -> DicePool {
+        let mut pool = BTreeMap::<u32, Vec<u32>>::new();
+        for die in &self.dice {
+            pool.entry(die.sides).or_default().push(die.value);
+        }
+        pool.into()
+    }
+}
+
+/// How a [`RollEvaluator`] treats `Assign` ops.
+enum Target<'a, Ctx> {
+    /// Assignments are an error (eval mode).
+    ReadOnly(&'a Ctx),
+    /// Assignments are silently skipped.
+    Lenient(&'a Ctx),
+    /// Assignments are written to the context (apply mode).
+    Apply(&'a mut Ctx),
+}
+
+impl<Ctx> Target<'_, Ctx> {
+    fn ctx(&self) -> &Ctx {
+        match self {
+            Self::ReadOnly(ctx) | Self::Lenient(ctx) => ctx,
+            Self::Apply(ctx) => ctx,
+        }
+    }
+}
+
+pub struct RollEvaluator<'a, Var, Ctx> {
+    stack: Stack<i32>,
+    target: Target<'a, Ctx>,
+    crit: bool,
+    dice: Vec<DieRoll>,
+    _var: PhantomData<Var>,
+}
+
+impl<'a, Var, Ctx> RollEvaluator<'a, Var, Ctx> {
+    /// With `crit` set, every dice group is rolled twice as many times
+    /// (critical hit damage).
+    pub fn new(ctx: &'a Ctx, crit: bool) -> Self {
+        Self::with_target(Target::ReadOnly(ctx), crit)
+    }
+
+    /// Like [`new`](Self::new), but `Assign` ops are skipped instead of
+    /// erroring.
+    pub fn lenient(ctx: &'a Ctx) -> Self {
+        Self::with_target(Target::Lenient(ctx), false)
+    }
+
+    /// Rolls while writing assignments to the context.
+    pub fn applying(ctx: &'a mut Ctx) -> Self {
+        Self::with_target(Target::Apply(ctx), false)
+    }
+
+    fn with_target(target: Target<'a, Ctx>, crit: bool) -> Self {
+        Self {
+            stack: Stack::new(),
+            target,
+            crit,
+            dice: Vec::new(),
+            _var: PhantomData,
+        }
+    }
+
+    /// Mark dropped dice in the group just rolled. The group is ordered by
+    /// face (`descending` or ascending) and `keep` maps its size to the number
+    /// of leading dice that survive.
+    fn mark_kept(&mut self, descending: bool, keep: impl FnOnce(usize) -> usize) {
+        let Some(&count) = self.stack.last() else {
+            return;
+        };
+        let count = count.max(0) as usize;
+        let start = self.dice.len().saturating_sub(count);
+        let group = &mut self.dice[start..];
+
+        let mut order: Vec<usize> = (0..group.len()).collect();
+        if descending {
+            order.sort_by_key(|&i| std::cmp::Reverse(group[i].value));
+        } else {
+            order.sort_by_key(|&i| group[i].value);
+        }
+        let kept = keep(group.len()).min(group.len());
+        for &i in &order[kept..] {
+            group[i].kept = false;
+        }
+    }
+
+    /// Exploding dice stop summing at the first non-max face.
+    fn mark_exploded(&mut self) {
+        let Some(&count) = self.stack.last() else {
+            return;
+        };
+        let start = self.dice.len().saturating_sub(count.max(0) as usize);
+        let mut stopped = false;
+        for die in &mut self.dice[start..] {
+            die.kept = !stopped;
+            stopped = stopped || die.value < die.sides;
+        }
+    }
+}
+
+impl<Var: Copy + fmt::Display, Ctx: Context<Var, i32>> Interpreter<Var, i32>
+    for RollEvaluator<'_, Var, Ctx>
+{
+    type Output = Roll;
+
+    fn exec(&mut self, op: Op<Var, i32>) -> Result<Option<BlockIndex>, Error> {
+        match op {
+            Op::PushVar(var) => {
+                self.stack.push(self.target.ctx().resolve(var)?);
+                return Ok(None);
+            }
+            Op::Assign(var) => {
+                match &mut self.target {
+                    Target::ReadOnly(_) => return Err(Error::assign_at_eval(var)),
+                    Target::Lenient(_) => {}
+                    Target::Apply(ctx) => ctx.assign(var, *self.stack.top()?)?,
+                }
+                return Ok(None);
+            }
+            Op::Roll => {
+                let (count, sides) = self.stack.pop2()?;
+                let count = if self.crit { count * 2 } else { count };
+                for _ in 0..count {
+                    let value = roll_die(sides)?;
+                    self.dice.push(DieRoll {
+                        sides: sides as u32,
+                        value: value as u32,
+                        kept: true,
+                    });
+                    self.stack.push(value);
+                }
+                self.stack.push(sides);
+                self.stack.push(count);
+                return Ok(None);
+            }
+            Op::KeepMax(n) => self.mark_kept(true, |_| n as usize),
+            Op::KeepMin(n) => self.mark_kept(false, |_| n as usize),
+            Op::DropMax(n) => self.mark_kept(false, |len| len.saturating_sub(n as usize)),
+            Op::DropMin(n) => self.mark_kept(true, |len| len.saturating_sub(n as usize)),
+            Op::Explode => self.mark_exploded(),
+            _ => {}
+        }
+        eval_op(&mut self.stack, op)
+    }
+
+    fn finish(self) -> Result<Roll, Error> {
+        Ok(Roll {
+            total: self.stack.result()?,
+            dice: self.dice,
+        })
+    }
+}
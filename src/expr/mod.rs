@@ -19,12 +19,12 @@ mod traits;
 
 pub use crate::expr::{
     error::Error,
-    interpret::{DicePool, ExprAnalysis, Interpreter},
-    ops::{BLOCK_ERROR, BLOCK_MAIN, BLOCK_NOOP, Block, BlockIndex, Op},
+    interpret::{DicePool, DieRoll, ExprAnalysis, Interpreter, Roll},
+    ops::{BLOCK_ERROR, BLOCK_MAIN, BLOCK_NOOP, Block, BlockIndex, Op, Slot},
     traits::{Context, Eval},
 };
 use crate::expr::{
-    interpret::{DicePoolEvaluator, Evaluator, Formatter, ReadOnlyEvaluator},
+    interpret::{DicePoolEvaluator, Evaluator, Formatter, ReadOnlyEvaluator, RollEvaluator},
     parser::Parser,
 };
 
@@ -34,17 +34,33 @@ pub const fn avg_hp(sides: i32) -> i32 {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Expr<Var, Val = i32>(Arc<[Block<Var, Val>]>);
+pub struct Expr<Var, Val = i32> {
+    blocks: Arc<[Block<Var, Val>]>,
+    /// Names of `let` bindings, indexed by slot.
+    locals: Arc<[Box<str>]>,
+}
 
 impl<Var, Val> Default for Expr<Var, Val> {
     fn default() -> Self {
-        Self(Arc::from([]))
+        Self::from_parts(Vec::new(), Vec::new())
     }
 }
 
 impl<Var, Val> Expr<Var, Val> {
+    fn from_parts(blocks: Vec<Vec<Op<Var, Val>>>, locals: Vec<Box<str>>) -> Self {
+        Self {
+            blocks: blocks.into_iter().map(Block::from).collect(),
+            locals: locals.into(),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.blocks.is_empty()
+    }
+
+    /// Name of the `let` binding in `slot`.
+    pub fn local_name(&self, slot: Slot) -> &str {
+        self.locals.get(slot as usize).map_or("_", |name| name)
     }
 }
 
@@ -55,18 +71,25 @@ where
 {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         if serializer.is_human_readable() {
-            if self.0.is_empty() {
+            if self.blocks.is_empty() {
                 return serializer.serialize_str("");
             }
             let s = self
                 .format_block(BLOCK_MAIN)
                 .map_err(serde::ser::Error::custom)?;
             serializer.serialize_str(&s)
-        } else {
+        } else if self.locals.is_empty() {
             // postcard (binary): serialize as ops for compact sharing URLs.
-            let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
-            for block in self.0.iter() {
-                seq.serialize_element(&block)?;
+            serializer.collect_seq(self.blocks.iter())
+        } else {
+            // `let` names follow an empty marker block, which a parsed main
+            // block never is, so expressions without bindings keep the
+            // encoding of older sharing URLs.
+            let mut seq = serializer.serialize_seq(Some(self.blocks.len() + 2))?;
+            seq.serialize_element::<[Op<Var, Val>]>(&[])?;
+            seq.serialize_element(&*self.locals)?;
+            for block in self.blocks.iter() {
+                seq.serialize_element(block)?;
             }
             seq.end()
         }
@@ -75,18 +98,38 @@ where
 
 impl<Var, Val> Expr<Var, Val> {
     pub fn block(&self, idx: BlockIndex) -> &Block<Var, Val> {
-        &self.0[idx as usize]
+        &self.blocks[idx as usize]
     }
 
     /// Returns true if any block in this expression contains a variable
     /// matching the predicate.
     pub fn has_var(&self, pred: impl Fn(&Var) -> bool) -> bool {
-        self.0.iter().any(|block| block.has_var(&pred))
+        self.blocks.iter().any(|block| block.has_var(&pred))
+    }
+
+    /// All variables the expression reads or writes, in op order.
+    pub fn vars(&self) -> impl Iterator<Item = &Var> {
+        self.blocks.iter().flat_map(|block| {
+            block.iter().filter_map(|op| match op {
+                Op::PushVar(var) | Op::Assign(var) => Some(var),
+                _ => None,
+            })
+        })
+    }
+
+    /// Variables the expression assigns to, in op order.
+    pub fn assigned_vars(&self) -> impl Iterator<Item = &Var> {
+        self.blocks.iter().flat_map(|block| {
+            block.iter().filter_map(|op| match op {
+                Op::Assign(var) => Some(var),
+                _ => None,
+            })
+        })
     }
 
     /// Returns true if any block assigns to a variable matching the predicate.
     pub fn assigns_to(&self, pred: impl Fn(&Var) -> bool) -> bool {
-        self.0.iter().any(|block| block.assigns_to(&pred))
+        self.blocks.iter().any(|block| block.assigns_to(&pred))
     }
 
     /// Returns true if a specific block or any of its sub-blocks contains a
@@ -94,14 +137,14 @@ impl<Var, Val> Expr<Var, Val> {
     /// Returns true if `idx` refers to a real sub-block (not a sentinel and
     /// within bounds).
     fn is_sub_block(&self, idx: BlockIndex) -> bool {
-        idx != BLOCK_NOOP && idx != BLOCK_ERROR && (idx as usize) < self.0.len()
+        idx != BLOCK_NOOP && idx != BLOCK_ERROR && (idx as usize) < self.blocks.len()
     }
 
     pub fn block_has_var(&self, block: BlockIndex, pred: &impl Fn(&Var) -> bool) -> bool {
         if !self.is_sub_block(block) {
             return false;
         }
-        let blk = &self.0[block as usize];
+        let blk = &self.blocks[block as usize];
         blk.has_var(pred)
             || blk.iter().any(|op| match op {
                 Op::Eval(idx) => self.block_has_var(*idx, pred),
@@ -114,8 +157,11 @@ impl<Var, Val> Expr<Var, Val> {
 impl<Var, Val> Expr<Var, Val> {
     /// Create a new Expr by mapping each op across all blocks.
     pub fn map(&self, mut f: impl FnMut(&Op<Var, Val>) -> Op<Var, Val>) -> Self {
-        let blocks: Vec<_> = self.0.iter().map(|block| block.map(&mut f)).collect();
-        Self(blocks.into())
+        let blocks: Vec<_> = self.blocks.iter().map(|block| block.map(&mut f)).collect();
+        Self {
+            blocks: blocks.into(),
+            locals: self.locals.clone(),
+        }
     }
 }
 
@@ -123,7 +169,7 @@ impl<Var, Val> Deref for Expr<Var, Val> {
     type Target = Block<Var, Val>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0[BLOCK_MAIN as usize]
+        &self.blocks[BLOCK_MAIN as usize]
     }
 }
 
@@ -138,7 +184,7 @@ impl<Var: Copy, Val: Copy> Expr<Var, Val> {
         interp: &mut I,
         block: BlockIndex,
     ) -> Result<(), Error> {
-        for &op in self.0[block as usize].iter() {
+        for &op in self.blocks[block as usize].iter() {
             if let Some(sub_block) = interp.exec(op)? {
                 self.run_block(interp, sub_block)?;
             }
@@ -187,6 +233,22 @@ impl<Var: Copy + fmt::Display> Eval<Var, i32> for Expr<Var, i32> {
 }
 
 impl<Var: Copy + fmt::Display> Expr<Var, i32> {
+    /// Like `eval`, but records every die thrown. With `crit` set, each dice
+    /// group is rolled twice as many times (critical hit damage).
+    pub fn roll(&self, ctx: &impl Context<Var, i32>, crit: bool) -> Result<Roll, Error> {
+        self.run(RollEvaluator::new(ctx, crit))
+    }
+
+    /// Like `roll`, but silently ignores `Assign` ops instead of erroring.
+    pub fn roll_lenient(&self, ctx: &impl Context<Var, i32>) -> Result<Roll, Error> {
+        self.run(RollEvaluator::lenient(ctx))
+    }
+
+    /// Like `apply`, but records every die thrown.
+    pub fn apply_roll(&self, ctx: &mut impl Context<Var, i32>) -> Result<Roll, Error> {
+        self.run(RollEvaluator::applying(ctx))
+    }
+
     /// Like `eval`, but silently ignores `Assign` ops instead of erroring.
     pub fn eval_lenient(&self, ctx: &impl Context<Var, i32>) -> Result<i32, Error> {
         self.run(ReadOnlyEvaluator::lenient(ctx))
@@ -215,6 +277,12 @@ impl<Var: Copy + fmt::Display> Expr<Var, i32> {
     ) -> ExprAnalysis {
         ExprAnalysis::analyze(self, ctx, is_arg)
     }
+
+    /// Analyze the expression independently of any character: no branch is
+    /// pruned, so `active_args` lists every ARG that can get an input.
+    pub fn analyze_all(&self, is_arg: impl Fn(&Var) -> Option<u8> + Copy) -> ExprAnalysis {
+        ExprAnalysis::analyze_all(self, is_arg)
+    }
 }
 
 impl<Var: FromStr + Copy, Val: FromStr + Copy + Neg<Output = Val>> FromStr for Expr<Var, Val> {
@@ -224,18 +292,14 @@ impl<Var: FromStr + Copy, Val: FromStr + Copy + Neg<Output = Val>> FromStr for E
         if s.trim().is_empty() {
             return Ok(Self::default());
         }
-        let blocks: Arc<[Block<Var, Val>]> = Parser::new(s)
-            .parse()?
-            .into_iter()
-            .map(Block::from)
-            .collect();
-        Ok(Self(blocks))
+        let (blocks, locals) = Parser::new(s).parse()?;
+        Ok(Self::from_parts(blocks, locals))
     }
 }
 
 impl<Var: Copy + PartialEq + fmt::Display, Val: Copy + fmt::Display> Expr<Var, Val> {
     fn format_block(&self, block: BlockIndex) -> Result<String, Error> {
-        let block = &self.0[block as usize];
+        let block = &self.blocks[block as usize];
         let mut results: Vec<String> = Vec::new();
         for stmt in block.statements() {
             if let Some(ca) = Block::detect_compound(stmt) {
@@ -254,7 +318,7 @@ impl<Var: Copy + PartialEq + fmt::Display, Val: Copy + fmt::Display> Expr<Var, V
     }
 
     fn format_ops(&self, ops: &[Op<Var, Val>]) -> Result<String, Error> {
-        let mut fmt = Formatter::new();
+        let mut fmt = Formatter::new(&self.locals);
         for &op in ops {
             match op {
                 Op::Eval(idx) => {
@@ -289,7 +353,7 @@ impl<Var: Copy + PartialEq + fmt::Display, Val: Copy + fmt::Display> fmt::Displa
     for Expr<Var, Val>
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.0.is_empty() {
+        if self.blocks.is_empty() {
             return Ok(());
         }
         let s = self.format_block(BLOCK_MAIN).map_err(|_| fmt::Error)?;
@@ -979,6 +1043,58 @@ mod tests {
         assert!(analysis.active_args.is_empty());
     }
 
+    #[wasm_bindgen_test]
+    fn roll_records_kept_dice() {
+        let character = test_character();
+        let expr: Expr = "4d6kh3 + DEX".parse().unwrap();
+        let roll = expr.roll(&character, false).unwrap();
+        assert_eq!(roll.dice.len(), 4);
+        assert_eq!(roll.dice.iter().filter(|die| die.kept).count(), 3);
+
+        let kept: i32 = roll
+            .dice
+            .iter()
+            .filter(|die| die.kept)
+            .map(|die| die.value as i32)
+            .sum();
+        assert_eq!(roll.total, kept + 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn roll_crit_doubles_dice() {
+        let character = test_character();
+        let expr: Expr = "2d6 + 1d8 + DEX".parse().unwrap();
+        let roll = expr.roll(&character, true).unwrap();
+        assert_eq!(roll.dice.iter().filter(|die| die.sides == 6).count(), 4);
+        assert_eq!(roll.dice.iter().filter(|die| die.sides == 8).count(), 2);
+
+        let sum: i32 = roll.dice.iter().map(|die| die.value as i32).sum();
+        assert_eq!(roll.total, sum + 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn apply_roll_assigns_and_replays() {
+        let mut character = test_character();
+        let expr: Expr = "AC += 2d4".parse().unwrap();
+        let roll = expr.apply_roll(&mut character).unwrap();
+        assert_eq!(roll.dice.len(), 2);
+        assert_eq!(character.ac, roll.total);
+
+        let sum: i32 = roll.dice.iter().map(|die| die.value as i32).sum();
+        assert_eq!(roll.total, 15 + sum);
+
+        let mut replay = test_character();
+        assert_eq!(
+            expr.apply_with_dice(&mut replay, &roll.pool()),
+            Ok(roll.total)
+        );
+        assert_eq!(replay.ac, roll.total);
+
+        let character = test_character();
+        assert!(expr.roll(&character, false).is_err());
+        assert_eq!(expr.roll_lenient(&character).unwrap().dice.len(), 2);
+    }
+
     #[wasm_bindgen_test]
     fn analyze_detects_boolean_args() {
         let character = test_character();
@@ -1007,4 +1123,66 @@ mod tests {
         let analysis = expr.analyze(&character, is_arg);
         assert!(!analysis.boolean_args.contains(&0));
     }
+
+    #[wasm_bindgen_test]
+    fn let_bindings() {
+        let mut ch = test_character();
+
+        let expr: Expr = "let a = DEX + 1; a * 2".parse().unwrap();
+        assert_eq!(expr.eval(&ch).unwrap(), 6);
+
+        // Display round-trip keeps the binding name
+        let expr: Expr = "let bonus = CHA + 1; AC += bonus".parse().unwrap();
+        assert_eq!(expr.to_string(), "let bonus = CHA + 1; AC += bonus");
+        assert_eq!(expr.to_string().parse::<Expr>().unwrap(), expr);
+        assert_eq!(expr.apply(&mut ch).unwrap(), 20);
+
+        // Bindings are visible inside function arguments
+        let expr: Expr = "let m = CHA; if(m > 3, m, 0)".parse().unwrap();
+        assert_eq!(expr.eval(&ch).unwrap(), 4);
+
+        // Rebinding reuses the slot and sees the previous value
+        let expr: Expr = "let x = 1; let x = x + 1; x".parse().unwrap();
+        assert_eq!(expr.eval(&ch).unwrap(), 2);
+
+        // Names must not shadow vars or functions, and need a trailing `;`
+        assert!("let STR = 1; 2".parse::<Expr>().is_err());
+        assert!("let min = 1; 2".parse::<Expr>().is_err());
+        assert!("let x = 1".parse::<Expr>().is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn let_bindings_postcard_roundtrip() {
+        let expr: Expr = "let bonus = CHA + 1; AC += bonus".parse().unwrap();
+        let bytes = postcard::to_allocvec(&expr).unwrap();
+        let decoded: Expr = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, expr);
+        assert_eq!(decoded.to_string(), "let bonus = CHA + 1; AC += bonus");
+    }
+
+    #[wasm_bindgen_test]
+    fn postcard_without_let_keeps_block_encoding() {
+        let expr: Expr = "if(STR > 1, AC += 1, AC += 2)".parse().unwrap();
+        let blocks: Vec<&[Op<Var, i32>]> = expr.blocks.iter().map(|block| &**block).collect();
+        let legacy = postcard::to_allocvec(&blocks).unwrap();
+        assert_eq!(postcard::to_allocvec(&expr).unwrap(), legacy);
+        assert_eq!(postcard::from_bytes::<Expr>(&legacy).unwrap(), expr);
+
+        let empty = postcard::to_allocvec(&Expr::default()).unwrap();
+        assert_eq!(
+            postcard::from_bytes::<Expr>(&empty).unwrap(),
+            Expr::default()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn analyze_let_bound_args() {
+        let character = test_character();
+        let expr: Expr = "let a = ARG.0; guard(in(a, 0, 1), AC += a)"
+            .parse()
+            .unwrap();
+        let analysis = expr.analyze(&character, is_arg);
+        assert!(analysis.boolean_args.contains(&0));
+        assert!(analysis.active_args.contains(&0));
+    }
 }
@@ -60,6 +60,9 @@ pub const BLOCK_NOOP: BlockIndex = 0;
 /// else-branch of `EvalIf` to signal a failed guard condition.
 pub const BLOCK_ERROR: BlockIndex = BlockIndex::MAX;
 
+/// Type alias for `let` binding slots in expressions.
+pub type Slot = u8;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Op<Var, Val> {
     PushVar(Var),
@@ -88,13 +91,15 @@ pub enum Op<Var, Val> {
     In,                             // in(a, b, c) → b <= a && a <= c
     EvalIf(BlockIndex, BlockIndex), // if: pop cond, branch to then/else block
     Eval(BlockIndex),               // evaluate sub-block
+    Store(Slot),                    // let name = …: pop into a local slot
+    Load(Slot),                     // push the value of a local slot
 }
 
 impl<Var: PartialEq, Val> Op<Var, Val> {
     /// Net stack-depth change of this op (+1 for push, -1 for binary, etc).
     fn stack_delta(&self) -> i32 {
         match self {
-            Op::PushVar(_) | Op::PushConst(_) => 1,
+            Op::PushVar(_) | Op::PushConst(_) | Op::Load(_) => 1,
             // Binary ops: pop 2, push 1 → -1
             Op::Add
             | Op::Sub
@@ -121,7 +126,7 @@ impl<Var: PartialEq, Val> Op<Var, Val> {
             | Op::KeepMin(_)
             | Op::DropMax(_)
             | Op::DropMin(_) => 0,
-            Op::Assign(_) => -1,
+            Op::Assign(_) | Op::Store(_) => -1,
             Op::In => -2, // pop 3, push 1
             Op::Eval(_) => 0,
             Op::EvalIf(_, _) => -1, // pops condition
@@ -190,9 +195,10 @@ impl<Var, Val> Block<Var, Val> {
 }
 
 impl<Var: PartialEq, Val> Block<Var, Val> {
-    /// Split this block into statements at `Assign` boundaries.
+    /// Split this block into statements at `Assign` and `let` boundaries.
     pub fn statements(&self) -> impl Iterator<Item = &[Op<Var, Val>]> {
-        self.0.split_inclusive(|op| matches!(op, Op::Assign(_)))
+        self.0
+            .split_inclusive(|op| matches!(op, Op::Assign(_) | Op::Store(_)))
     }
 
     /// Detect compound assignment pattern in an ops slice (a single statement).
@@ -3,15 +3,24 @@ use std::{iter::Peekable, marker::PhantomData, ops::Neg, str::FromStr};
 use crate::expr::{
     Op,
     error::Error,
-    ops::{BLOCK_ERROR, BLOCK_NOOP, BlockIndex, Cmp},
+    ops::{BLOCK_ERROR, BLOCK_NOOP, BlockIndex, Cmp, Slot},
     tokenizer::{Token, Tokenizer},
 };
 
+/// Function names, which can't be shadowed by `let` bindings.
+const FUNCTIONS: [&str; 6] = ["min", "max", "avg_hp", "in", "if", "guard"];
+
+/// Parsed blocks (main block first) and the names of `let` slots.
+pub(super) type Parsed<Var, Val> = (Vec<Vec<Op<Var, Val>>>, Vec<Box<str>>);
+
 pub(super) struct Parser<'a, Var, Val> {
     tokens: Peekable<Tokenizer<'a>>,
     /// Extra blocks for sub-expressions (if branches, etc.).
     /// Block indices are 1-based (0 = main block / "no block").
     blocks: Vec<Vec<Op<Var, Val>>>,
+    /// Names of `let` bindings; the index is the slot. Bindings are scoped
+    /// to the whole expression, sub-blocks included.
+    locals: Vec<Box<str>>,
     _var: PhantomData<(Var, Val)>,
 }
 
@@ -20,6 +29,7 @@ impl<'a, Var, Val> From<Tokenizer<'a>> for Parser<'a, Var, Val> {
         Self {
             tokens: tokens.peekable(),
             blocks: Vec::new(),
+            locals: Vec::new(),
             _var: PhantomData,
         }
     }
@@ -30,13 +40,13 @@ impl<'a, Var: FromStr + Copy, Val: FromStr + Copy + Neg<Output = Val>> Parser<'a
         Self::from(Tokenizer::new(expr))
     }
 
-    pub fn parse(&mut self) -> Result<Vec<Vec<Op<Var, Val>>>, Error> {
+    pub fn parse(&mut self) -> Result<Parsed<Var, Val>, Error> {
         let mut ops = Vec::new();
         self.parse_into(&mut ops)?;
         let mut blocks = Vec::with_capacity(1 + self.blocks.len());
         blocks.push(ops);
         blocks.append(&mut self.blocks);
-        Ok(blocks)
+        Ok((blocks, std::mem::take(&mut self.locals)))
     }
 
     pub fn parse_into(&mut self, ops: &mut Vec<Op<Var, Val>>) -> Result<(), Error> {
@@ -299,6 +309,10 @@ impl<'a, Var: FromStr + Copy, Val: FromStr + Copy + Neg<Output = Val>> Parser<'a
                     ops.push(Op::PushVar(var));
                     return Ok(());
                 }
+                if let Some(slot) = self.local(name) {
+                    ops.push(Op::Load(slot));
+                    return Ok(());
+                }
                 self.parse_function_call(name, ops)?;
                 Ok(())
             }
@@ -424,9 +438,54 @@ impl<'a, Var: FromStr + Copy, Val: FromStr + Copy + Neg<Output = Val>> Parser<'a
         }
     }
 
-    // assignment = IDENT '=' expr | IDENT op= expr | expr
+    fn local(&self, name: &str) -> Option<Slot> {
+        self.locals
+            .iter()
+            .position(|local| **local == *name)
+            .map(|idx| idx as Slot)
+    }
+
+    // let = 'let' IDENT '=' or_expr ';'
+    //
+    // The name is bound after its value is parsed, so `let x = x + 1`
+    // refers to the previous `x`. Rebinding a name reuses its slot.
+    fn parse_let(&mut self, ops: &mut Vec<Op<Var, Val>>) -> Result<(), Error> {
+        let name = match self.next()? {
+            Some(Token::Ident(name))
+                if name.parse::<Var>().is_err()
+                    && !FUNCTIONS.contains(&name)
+                    && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') =>
+            {
+                name
+            }
+            Some(token) => return Err(Error::unexpected_token(token)),
+            None => return Err(Error::UnexpectedEnd),
+        };
+        self.expect(|token| matches!(token, Token::Eq))?;
+        self.parse_or(ops)?;
+        let slot = match self.local(name) {
+            Some(slot) => slot,
+            None => {
+                let slot =
+                    Slot::try_from(self.locals.len()).map_err(|_| Error::unexpected_token(name))?;
+                self.locals.push(name.into());
+                slot
+            }
+        };
+        ops.push(Op::Store(slot));
+        // A binding leaves nothing on the stack, so something must follow
+        self.expect(|token| matches!(token, Token::Semicolon))
+    }
+
+    // assignment = let* (IDENT '=' expr | IDENT op= expr | expr)
     fn parse_assignment(&mut self, ops: &mut Vec<Op<Var, Val>>) -> Result<(), Error> {
         loop {
+            if let Some(Token::Let) = self.peek() {
+                self.next()?;
+                self.parse_let(ops)?;
+                continue;
+            }
+
             if let Some(&Token::Ident(name)) = self.peek()
                 && let Ok(var) = name.parse::<Var>()
             {
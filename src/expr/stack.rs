@@ -1,14 +1,18 @@
 use std::ops::Deref;
 
-use crate::expr::Error;
+use crate::expr::{Error, ops::Slot};
 
-pub(super) struct Stack<T>(Vec<T>);
+/// Evaluation stack, plus the slots of `let` bindings.
+pub(super) struct Stack<T> {
+    items: Vec<T>,
+    slots: Vec<Option<T>>,
+}
 
 impl<T> Deref for Stack<T> {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.items
     }
 }
 
@@ -16,15 +20,18 @@ impl<T> Stack<T> {
     const DEFAULT_CAPACITY: usize = 16;
 
     pub fn new() -> Self {
-        Self(Vec::with_capacity(Self::DEFAULT_CAPACITY))
+        Self {
+            items: Vec::with_capacity(Self::DEFAULT_CAPACITY),
+            slots: Vec::new(),
+        }
     }
 
     pub fn push(&mut self, val: T) {
-        self.0.push(val);
+        self.items.push(val);
     }
 
     pub fn pop(&mut self) -> Result<T, Error> {
-        self.0.pop().ok_or(Error::StackUnderflow)
+        self.items.pop().ok_or(Error::StackUnderflow)
     }
 
     pub fn pop2(&mut self) -> Result<(T, T), Error> {
@@ -51,18 +58,43 @@ impl<T> Stack<T> {
         f: impl FnOnce(&mut [T]) -> T,
     ) -> Result<(), Error> {
         let start = self
-            .0
+            .items
             .len()
             .checked_sub(count)
             .ok_or(Error::StackUnderflow)?;
-        let result = f(&mut self.0[start..]);
-        self.0.truncate(start);
-        self.0.push(result);
+        let result = f(&mut self.items[start..]);
+        self.items.truncate(start);
+        self.items.push(result);
         Ok(())
     }
 
     pub fn top(&self) -> Result<&T, Error> {
-        self.0.last().ok_or(Error::StackUnderflow)
+        self.items.last().ok_or(Error::StackUnderflow)
+    }
+
+    /// Pop the top value into a `let` slot.
+    pub fn store(&mut self, slot: Slot) -> Result<(), Error> {
+        let value = self.pop()?;
+        let idx = slot as usize;
+        if self.slots.len() <= idx {
+            self.slots.resize_with(idx + 1, || None);
+        }
+        self.slots[idx] = Some(value);
+        Ok(())
+    }
+}
+
+impl<T: Clone> Stack<T> {
+    /// Push a copy of the value bound to a `let` slot.
+    pub fn load(&mut self, slot: Slot) -> Result<(), Error> {
+        let value = self
+            .slots
+            .get(slot as usize)
+            .cloned()
+            .flatten()
+            .ok_or(Error::UnboundLocal(slot))?;
+        self.push(value);
+        Ok(())
     }
 }
 
@@ -71,6 +103,6 @@ impl<T> IntoIterator for Stack<T> {
     type Item = T;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.items.into_iter()
     }
 }
@@ -27,6 +27,7 @@ pub(super) enum Token<'a> {
     Comma,
     Semicolon,
     Bang,
+    Let,
     // Boolean / comparison
     And,
     Or,
@@ -110,6 +111,7 @@ impl<'a> Iterator for Tokenizer<'a> {
                     "and" => Token::And,
                     "or" => Token::Or,
                     "not" => Token::Not,
+                    "let" => Token::Let,
                     _ => Token::Ident(ident),
                 }))
             }
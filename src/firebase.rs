@@ -356,6 +356,32 @@ pub fn subscribe_collection(
     })
 }
 
+/// Subscribe to a single Firestore document. `on_change` gets `None` when the
+/// document is deleted or can no longer be read.
+pub fn subscribe_doc(
+    path: &[&str],
+    on_change: impl Fn(Option<serde_json::Value>) + 'static,
+) -> Result<Subscription, FirebaseError> {
+    let callback = Closure::wrap(Box::new(move |data: JsValue| {
+        if data.is_null() || data.is_undefined() {
+            on_change(None);
+        } else if let Ok(parsed) = from_js(data) {
+            on_change(Some(parsed));
+        }
+    }) as Box<dyn Fn(JsValue)>);
+
+    let mut args: Vec<JsValue> = vec![callback.as_ref().clone()];
+    args.extend(path.iter().map(|segment| JsValue::from_str(segment)));
+    let unsubscribe: js_sys::Function = call("onDocSnapshot", &args)?
+        .dyn_into()
+        .map_err(FirebaseError::from)?;
+
+    Ok(Subscription {
+        _callback: callback,
+        unsubscribe,
+    })
+}
+
 /// Extract a human-readable message from a JsValue error.
 fn friendly_js_error(js_err: &JsValue) -> String {
     if let Some(error_obj) = js_err.dyn_ref::<js_sys::Error>() {
@@ -43,16 +43,20 @@ use components::{
 };
 use hooks::use_theme;
 use pages::{
+    campaign::{CampaignDashboard, CampaignList},
     character::{
-        editor::CharacterEditor, layout::CharacterLayout, list::CharacterList,
-        quick_start::QuickStart, session::CharacterSession, story::CharacterStory,
+        editor::CharacterEditor, history::CharacterHistory, layout::CharacterLayout,
+        list::CharacterList, quick_start::QuickStart, session::CharacterSession,
+        story::CharacterStory,
     },
-    import_character::{ImportCharacter, ImportCloudCharacter},
+    import_character::{ImportCharacter, ImportCloudCharacter, SyncConflict},
     not_found::NotFound,
     reference::{
-        background::BackgroundReference, class::ClassReference, feature::FeatureReference,
-        species::SpeciesReference, spell::SpellReference,
+        background::BackgroundReference, class::ClassReference, equipment::EquipmentReference,
+        feature::FeatureReference, packs::PacksReference, species::SpeciesReference,
+        spell::SpellReference,
     },
+    spectate::SpectateCharacter,
 };
 use rules::RulesRegistry;
 
@@ -63,12 +67,19 @@ pub fn App() -> impl IntoView {
 
     let theme = use_theme();
     let i18n = expect_context::<leptos_fluent::I18n>();
-    provide_context(RulesRegistry::new(i18n));
+    let registry = RulesRegistry::new(i18n);
+    provide_context(registry);
     provide_context(ActiveCharacterId::default());
     let is_routing = IsRouting::default();
     provide_context(is_routing);
     storage::init_sync();
 
+    let packs_version = storage::sync_packs_version();
+    Effect::new(move || {
+        packs_version.track();
+        registry.set_packs(storage::load_packs());
+    });
+
     view! {
         <Html attr:lang="en" attr:dir="ltr" attr:data-theme=move || theme.get() />
 
@@ -83,9 +94,14 @@ pub fn App() -> impl IntoView {
                         <Route path=path!("/quick-start") view=QuickStart />
                         <Route path=path!("/story") view=CharacterStory />
                         <Route path=path!("/story/:story_id") view=CharacterStory />
+                        <Route path=path!("/history") view=CharacterHistory />
                     </ParentRoute>
                     <Route path=path!("/s/:user_id/:char_id") view=ImportCloudCharacter />
+                    <Route path=path!("/s/:user_id/:char_id/live") view=SpectateCharacter />
                     <Route path=path!("/s/:data") view=ImportCharacter />
+                    <Route path=path!("/sync/:id") view=SyncConflict />
+                    <Route path=path!("/campaigns") view=CampaignList />
+                    <Route path=path!("/campaigns/:code") view=CampaignDashboard />
                     <Route path=path!("/r/class") view=ClassReference />
                     <Route path=path!("/r/class/:name") view=ClassReference />
                     <Route path=path!("/r/class/:name/:subname") view=ClassReference />
@@ -97,6 +113,9 @@ pub fn App() -> impl IntoView {
                     <Route path=path!("/r/feature/:category") view=FeatureReference />
                     <Route path=path!("/r/spell") view=SpellReference />
                     <Route path=path!("/r/spell/:list") view=SpellReference />
+                    <Route path=path!("/r/equipment") view=EquipmentReference />
+                    <Route path=path!("/r/equipment/:kind") view=EquipmentReference />
+                    <Route path=path!("/r/packs") view=PacksReference />
                 </Routes>
             </main>
         </Router>
@@ -91,6 +91,36 @@ impl Attribute {
             Self::SpellDc | Self::SpellAttack | Self::SpellAttackAdvantage
         )
     }
+
+    /// Returns true if feature assignments can write this attribute. Derived
+    /// values (modifiers, skill bonuses, level, ...) are read-only; effects
+    /// override them instead.
+    pub fn is_writable(&self) -> bool {
+        matches!(
+            self,
+            Self::Ability(_)
+                | Self::SkillProficiency(_)
+                | Self::SaveProficiency(_)
+                | Self::SaveAdvantage(_)
+                | Self::EquipmentProficiency(_)
+                | Self::MaxHp
+                | Self::Hp
+                | Self::TempHp
+                | Self::Ac
+                | Self::Speed
+                | Self::AttackBonus
+                | Self::Attacks
+                | Self::InitiativeBonus
+                | Self::Inspiration
+                | Self::Points(_)
+                | Self::PointsMax(_)
+                | Self::Resistance(_)
+                | Self::Vulnerability(_)
+                | Self::Immunity(_)
+                | Self::DamageReduction(_)
+                | Self::Language(_)
+        )
+    }
 }
 
 fn parse_ability(s: &str) -> Option<Ability> {
@@ -8,15 +8,25 @@ use uuid::Uuid;
 use crate::{
     constvec::ConstVec,
     demap::{self, Keyed},
-    expr::{self, Eval as _},
+    expr::{self, DicePool, DieRoll, Eval as _, Expr, Roll},
     model::{
-        AbilityScores, Attribute, CharacterIdentity, CombatStats, DamageModifiers, Equipment,
-        Feature, FeatureData, FeatureSource, FeatureValue, Features, Personality, SpellSlotLevel,
-        enums::*,
+        AbilityScores, Attribute, CharacterIdentity, CombatStats, DamageModifiers, Encumbrance,
+        Equipment, Feature, FeatureData, FeatureSource, FeatureValue, Features, Personality, Spell,
+        SpellCopy, SpellSlotLevel, Weapon, XpAward, enums::*,
     },
     vecset::VecSet,
 };
 
+/// A class's caster level in sixths. 6 is LCM(1,2,3) — the valid
+/// caster_coef values. coef is the reciprocal multiplier: full=6, half=3,
+/// third=2. The bitwise `& coef & 1` term rounds up for half casters (divide
+/// by 2, round up) and rounds down for third casters (divide by 3, round
+/// down).
+fn caster_level_sixths_for(class_level: u32, caster_coef: u32) -> u32 {
+    let coef = 6 / caster_coef;
+    coef * (class_level + (class_level & coef & 1))
+}
+
 /// Default walking speed in feet (most species).
 const DEFAULT_SPEED: u32 = 30;
 
@@ -85,6 +95,10 @@ impl Keyed for CharacterSummary {
 
 // --- Main Character ---
 
+/// Version of the stored character format. Bump it together with a new
+/// entry in the `storage::migrate` registry.
+pub const SCHEMA_VERSION: u32 = 9;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Store)]
 pub struct Character {
     pub id: Uuid,
@@ -112,6 +126,9 @@ pub struct Character {
     pub languages: VecSet<String>,
     #[serde(default)]
     pub damage_modifiers: BTreeMap<DamageType, DamageModifiers>,
+    /// Advantage (1) or disadvantage (-1) features grant on saving throws.
+    #[serde(default)]
+    pub save_advantages: BTreeMap<Ability, i32>,
     #[serde(default)]
     pub spell_slots: BTreeMap<SpellSlotPool, ConstVec<SpellSlotLevel, 9>>,
     #[serde(default)]
@@ -120,9 +137,21 @@ pub struct Character {
     pub updated_at: u64,
     #[serde(default)]
     pub shared: bool,
+    /// Ids of the content packs this character's classes, species, background
+    /// or features come from.
+    #[serde(default)]
+    pub packs: VecSet<String>,
+    /// Invite code of the campaign the character plays in. The campaign's GM
+    /// can read the cloud copy.
+    #[serde(default)]
+    pub campaign: Option<String>,
+    /// Format version the character was written with; missing in documents
+    /// that predate versioning.
+    #[serde(default)]
+    pub schema_version: u32,
 }
 
-fn now_epoch_secs() -> u64 {
+pub(crate) fn now_epoch_secs() -> u64 {
     (js_sys::Date::now() / 1000.0) as u64
 }
 
@@ -177,6 +206,32 @@ impl Character {
                         fu.used = 0;
                     }
                 }
+                spell_data.preparing = spell_data.prepares();
+            }
+        }
+
+        self.recharge_items();
+    }
+
+    /// Restore item charges at dawn, rolling each item's recharge
+    /// expression.
+    pub fn recharge_items(&mut self) {
+        for index in 0..self.equipment.items.len() {
+            let Some(charges) = &self.equipment.items[index].charges else {
+                continue;
+            };
+            let amount = match &charges.recharge {
+                Some(recharge) => match recharge.roll(self, false) {
+                    Ok(roll) => roll.total.max(0) as u32,
+                    Err(error) => {
+                        log::error!("Item recharge error: {error}");
+                        continue;
+                    }
+                },
+                None => charges.max,
+            };
+            if let Some(charges) = &mut self.equipment.items[index].charges {
+                charges.regain(amount);
             }
         }
     }
@@ -304,10 +359,51 @@ impl Character {
         self.combat.armor_class
     }
 
+    /// Carrying capacity in pounds: STR × 15, scaled by size.
+    pub fn carrying_capacity(&self) -> f32 {
+        self.abilities.get(Ability::Strength) as f32 * 15.0 * self.identity.size.carry_multiplier()
+    }
+
+    /// Encumbrance from carried weight. The 5×/10× STR thresholds only apply
+    /// with the variant rule enabled.
+    pub fn encumbrance(&self) -> Encumbrance {
+        let weight = self.equipment.total_weight();
+        let capacity = self.carrying_capacity();
+        if weight > capacity {
+            return Encumbrance::OverCapacity;
+        }
+        if !self.equipment.variant_encumbrance {
+            return Encumbrance::None;
+        }
+        // Variant thresholds are a third and two thirds of capacity.
+        if weight > capacity * 2.0 / 3.0 {
+            Encumbrance::HeavilyEncumbered
+        } else if weight > capacity / 3.0 {
+            Encumbrance::Encumbered
+        } else {
+            Encumbrance::None
+        }
+    }
+
+    /// Apply the assignment expressions of equipped (and attuned) items.
+    /// Runs after armor so bonuses like `AC += 1` stack on top of it.
+    pub fn apply_item_assignments(&mut self) {
+        let exprs: Vec<(String, Expr<Attribute>)> = self
+            .equipment
+            .active_item_exprs()
+            .map(|(name, expr)| (name.to_string(), expr.clone()))
+            .collect();
+        for (name, expr) in exprs {
+            if let Err(error) = expr.apply(self) {
+                log::warn!("Item expr apply failed for '{name}': {error}");
+            }
+        }
+    }
+
     /// Compute base max HP from class levels and CON modifier.
     ///
-    /// Formula: for each class, `hit_die_sides` at level 1 +
-    /// `avg_hp(hit_die_sides)` for each subsequent level, plus
+    /// Formula: for each class, `hit_die_sides` at level 1 + the recorded
+    /// roll (or `avg_hp(hit_die_sides)`) for each subsequent level, plus
     /// `total_level * CON modifier`.
     pub fn compute_hp_max(&mut self) -> u32 {
         let con_mod = self.ability_modifier(Ability::Constitution);
@@ -316,10 +412,12 @@ impl Character {
             .identity
             .classes
             .iter()
-            .map(|cl| {
+            .enumerate()
+            .map(|(idx, cl)| {
                 total_level += cl.level as i32;
-                let sides = cl.hit_die_sides as i32;
-                sides + (cl.level as i32 - 1) * expr::avg_hp(sides)
+                (1..=cl.level)
+                    .map(|level| cl.level_hp(level, idx == 0) as i32)
+                    .sum::<i32>()
             })
             .sum();
         let total = (base + total_level * con_mod).max(0) as u32;
@@ -327,6 +425,41 @@ impl Character {
         total
     }
 
+    /// Roll the hit die of a class for `level` and record the result as the
+    /// hit points gained at that level. Dice come from `pool` when the
+    /// player rolled physical dice, otherwise they are rolled randomly.
+    pub fn roll_hit_points(
+        &mut self,
+        class_index: usize,
+        level: u32,
+        pool: Option<&DicePool>,
+    ) -> Result<Roll, expr::Error> {
+        let sides = self
+            .identity
+            .classes
+            .get(class_index)
+            .map_or(0, |cl| cl.hit_die_sides);
+        let hit_die: Expr<Attribute> = format!("1d{sides}").parse()?;
+        let roll = match pool {
+            Some(pool) => {
+                let total = hit_die.apply_with_dice(self, pool)?;
+                Roll {
+                    total,
+                    dice: vec![DieRoll {
+                        sides,
+                        value: total as u32,
+                        kept: true,
+                    }],
+                }
+            }
+            None => hit_die.roll(self, false)?,
+        };
+        if let Some(cl) = self.identity.classes.get_mut(class_index) {
+            cl.hp_rolls.insert(level, roll.total.max(1) as u32);
+        }
+        Ok(roll)
+    }
+
     /// Reset speed to the default walking speed (30 ft).
     /// Race/feature `OnCompute` assignments override this.
     pub fn compute_speed(&mut self) -> u32 {
@@ -347,6 +480,7 @@ impl Character {
         self.compute_speed();
         self.combat.initiative_misc_bonus = 0;
         self.combat.attack_count = 1;
+        self.save_advantages.clear();
     }
 
     /// Returns (caster_level, caster_class_count) for the given pool in a
@@ -369,18 +503,38 @@ impl Character {
                 .max();
             if let Some(max_coef) = max_coef {
                 caster_classes += 1;
-                // 6 is LCM(1,2,3) — the valid caster_coef values.
-                // coef is the reciprocal multiplier: full=6, half=3, third=2.
-                // The bitwise `& coef & 1` term rounds up for half casters
-                // (divide by 2, round up) and rounds down for third casters
-                // (divide by 3, round down).
-                let coef = 6 / max_coef;
-                caster_level_sixths += coef * (cl.level + (cl.level & coef & 1));
+                caster_level_sixths += caster_level_sixths_for(cl.level, max_coef);
             }
         }
         (caster_level_sixths / 6, caster_classes)
     }
 
+    /// Highest spell level the class behind spellcasting feature
+    /// `feature_name` can cast on its own levels, ignoring slots shared
+    /// with other classes. Zero if the feature isn't a class caster.
+    pub fn class_spell_level(&self, feature_name: &str) -> u32 {
+        let Some(coef) = self
+            .feature_data
+            .get(feature_name)
+            .and_then(|entry| entry.spells.as_ref())
+            .map(|spell_data| spell_data.caster_coef)
+            .filter(|&coef| coef != 0)
+        else {
+            return 0;
+        };
+        let class_level = self
+            .features
+            .iter()
+            .find(|feature| feature.name == feature_name)
+            .and_then(|feature| feature.source.as_class())
+            .and_then(|class| self.identity.classes.iter().find(|cl| cl.class == class))
+            .map_or(0, |cl| cl.level);
+        (caster_level_sixths_for(class_level, coef) / 6)
+            .checked_sub(1)
+            .and_then(|level| SPELL_SLOT_TABLE.get(level as usize))
+            .map_or(0, |slots| slots.len() as u32)
+    }
+
     pub fn caster_level(&self, pool: SpellSlotPool) -> u32 {
         self.caster_info(pool).0
     }
@@ -412,6 +566,49 @@ impl Character {
         }
     }
 
+    /// Copy a spell found in the world into the spellbook of the
+    /// spellcasting feature `feature_name`, paying for it from the
+    /// character's purse. `on_class_list` tells whether the spell is on the
+    /// class's spell list at its level. Returns `false` without changes for
+    /// cantrips, spells off the list or above the class's spell level,
+    /// spells already in the book, or when the gold isn't there.
+    pub fn copy_into_spellbook(
+        &mut self,
+        feature_name: &str,
+        mut spell: Spell,
+        on_class_list: impl FnOnce(&Spell) -> bool,
+    ) -> bool {
+        let Some(book) = self
+            .feature_data
+            .get(feature_name)
+            .and_then(|entry| entry.spells.as_ref())
+            .and_then(|spell_data| spell_data.known.as_ref())
+        else {
+            return false;
+        };
+        if spell.level == 0
+            || spell.level > self.class_spell_level(feature_name)
+            || book.iter().any(|s| s.name == spell.name)
+            || !on_class_list(&spell)
+        {
+            return false;
+        }
+        let copy = SpellCopy::for_level(spell.level);
+        if !self.equipment.currency.spend(copy.cost) {
+            return false;
+        }
+        spell.copied = Some(copy);
+        if let Some(book) = self
+            .feature_data
+            .get_mut(feature_name)
+            .and_then(|entry| entry.spells.as_mut())
+            .and_then(|spell_data| spell_data.known.as_mut())
+        {
+            book.push(spell);
+        }
+        true
+    }
+
     pub fn can_level_up(&self) -> bool {
         !self.identity.classes.is_empty()
             && self.identity.classes.iter().all(|cl| !cl.class.is_empty())
@@ -449,6 +646,36 @@ impl Character {
             .unwrap_or(0)
     }
 
+    /// XP needed for the next character level, or `None` at level 20.
+    pub fn next_level_xp(&self) -> Option<u32> {
+        XP_THRESHOLDS.get(self.level() as usize).copied()
+    }
+
+    /// Whether XP progression is used and the next level's threshold has
+    /// been reached.
+    pub fn xp_level_up_ready(&self) -> bool {
+        !self.identity.milestone
+            && self
+                .next_level_xp()
+                .is_some_and(|xp| self.identity.experience_points >= xp)
+    }
+
+    /// Set the XP total. Returns `true` when the change crosses the
+    /// threshold for the next level.
+    pub fn set_experience_points(&mut self, xp: u32) -> bool {
+        let was_ready = self.xp_level_up_ready();
+        self.identity.experience_points = xp;
+        !was_ready && self.xp_level_up_ready()
+    }
+
+    /// Log an XP award and add it to the total. Returns `true` when the
+    /// award crosses the threshold for the next level.
+    pub fn award_xp(&mut self, award: XpAward) -> bool {
+        let xp = self.identity.experience_points.saturating_add(award.amount);
+        self.identity.xp_awards.push(award);
+        self.set_experience_points(xp)
+    }
+
     pub fn proficiency_bonus(&self) -> i32 {
         proficiency_bonus_for_level(self.level())
     }
@@ -457,6 +684,16 @@ impl Character {
         self.abilities.modifier(ability)
     }
 
+    /// Attack bonus of a weapon (without the global `ATK` bonus): ability
+    /// modifier plus proficiency for categorized weapons, the flat bonus
+    /// otherwise.
+    pub fn weapon_attack_bonus(&self, weapon: &Weapon) -> i32 {
+        weapon
+            .attack_bonus_expr()
+            .eval(self)
+            .unwrap_or(weapon.attack_bonus)
+    }
+
     pub fn proficient_with(&self, ability: Ability) -> bool {
         self.saving_throws.contains(&ability)
     }
@@ -546,6 +783,7 @@ impl Character {
         self.proficiencies.clear();
         self.languages.clear();
         self.damage_modifiers.clear();
+        self.save_advantages.clear();
         self.spell_slots.clear();
         self.combat = CombatStats::default();
     }
@@ -621,9 +859,13 @@ impl Default for Character {
             proficiencies: VecSet::new(),
             languages: VecSet::new(),
             damage_modifiers: BTreeMap::new(),
+            save_advantages: BTreeMap::new(),
             notes: String::new(),
             updated_at: now_epoch_secs(),
             shared: false,
+            packs: VecSet::new(),
+            campaign: None,
+            schema_version: SCHEMA_VERSION,
         }
     }
 }
@@ -723,6 +965,14 @@ impl expr::Context<Attribute, i32> for Character {
                     self.damage_modifiers.remove(&dt);
                 }
             }
+            Attribute::SaveAdvantage(ability) => match value.clamp(-1, 1) {
+                0 => {
+                    self.save_advantages.remove(&ability);
+                }
+                value => {
+                    self.save_advantages.insert(ability, value);
+                }
+            },
             other => return Err(expr::Error::read_only_var(other)),
         }
 
@@ -771,6 +1021,9 @@ impl expr::Context<Attribute, i32> for Character {
             Attribute::Feature(name) => Ok(self.features.has(name) as i32),
             Attribute::Language(name) => Ok(self.languages.contains(name) as i32),
             Attribute::FeatCategory(cat) => Ok(self.features.has_category(cat) as i32),
+            Attribute::SaveAdvantage(ability) => {
+                Ok(self.save_advantages.get(&ability).copied().unwrap_or(0))
+            }
             a if a.is_advantage() => Ok(0),
             other => Err(expr::Error::unsupported_var(other)),
         }
@@ -914,13 +1167,17 @@ impl Character {
                     hit_die_sides: 8,
                     hit_dice_used: 0,
                     applied_levels: VecSet::new(),
+                    hp_rolls: BTreeMap::new(),
                 }],
                 species: "Elf".to_string(),
                 background: "Entertainer".to_string(),
                 alignment: Alignment::ChaoticGood,
                 experience_points: 900,
+                milestone: false,
+                xp_awards: Vec::new(),
                 species_applied: true,
                 background_applied: true,
+                size: Size::Medium,
             },
             abilities: AbilityScores {
                 strength: 8,
@@ -975,18 +1232,25 @@ impl Character {
                             sticky: false,
                             cost: 0,
                             free_uses: None,
+                            copied: None,
                         }],
                         known: None,
+                        prepare_limit: None,
+                        preparing: false,
                     }),
                 },
             )]),
             proficiencies: VecSet::new(),
             languages: VecSet::new(),
             damage_modifiers: BTreeMap::new(),
+            save_advantages: BTreeMap::new(),
             spell_slots: BTreeMap::new(),
             notes: String::new(),
             updated_at: 0,
             shared: false,
+            packs: VecSet::new(),
+            campaign: None,
+            schema_version: SCHEMA_VERSION,
         };
         ch.update_spell_slots(SpellSlotPool::Arcane, None);
         ch
@@ -1002,7 +1266,10 @@ pub mod tests {
     use super::*;
     use crate::{
         expr::Expr,
-        model::{Armor, ClassLevel, Currency, FeatureSource, Money, SpellData},
+        model::{
+            Armor, ClassLevel, Currency, FeatureSource, Item, ItemCharges, ItemSpell, Money, Spell,
+            SpellData,
+        },
         vecset::VecSet,
     };
 
@@ -1022,13 +1289,17 @@ pub mod tests {
                     hit_die_sides: 10,
                     hit_dice_used: 0,
                     applied_levels: VecSet::new(),
+                    hp_rolls: BTreeMap::new(),
                 }],
                 species: "Human".to_string(),
                 background: "Soldier".to_string(),
                 alignment: Alignment::TrueNeutral,
                 experience_points: 0,
+                milestone: false,
+                xp_awards: Vec::new(),
                 species_applied: false,
                 background_applied: false,
+                size: Size::Medium,
             },
             abilities: AbilityScores {
                 strength: 16,
@@ -1072,10 +1343,14 @@ pub mod tests {
             .collect(),
             languages: VecSet::new(),
             damage_modifiers: BTreeMap::new(),
+            save_advantages: BTreeMap::new(),
             spell_slots: BTreeMap::new(),
             notes: String::new(),
             updated_at: 0,
             shared: false,
+            packs: VecSet::new(),
+            campaign: None,
+            schema_version: SCHEMA_VERSION,
         }
     }
 
@@ -1102,12 +1377,274 @@ pub mod tests {
                     pool,
                     spells: Vec::new(),
                     known: None,
+                    prepare_limit: None,
+                    preparing: false,
                 }),
                 ..Default::default()
             },
         );
     }
 
+    // --- weapon_attack_bonus() ---
+
+    #[wasm_bindgen_test]
+    fn weapon_attack_bonus_uncategorized_is_flat() {
+        let ch = test_character();
+        let weapon = Weapon {
+            attack_bonus: 4,
+            ..Weapon::default()
+        };
+        assert_eq!(ch.weapon_attack_bonus(&weapon), 4);
+    }
+
+    #[wasm_bindgen_test]
+    fn weapon_attack_bonus_derived_from_category_and_properties() {
+        let mut ch = test_character();
+        ch.proficiencies.insert(Proficiency::SimpleWeapons);
+
+        // Finesse: best of STR (+3) / DEX (+2), proficient (+3), magic +1
+        let dagger = Weapon {
+            attack_bonus: 1,
+            category: Some(WeaponCategory::Simple),
+            properties: [WeaponProperty::Finesse, WeaponProperty::Thrown]
+                .into_iter()
+                .collect(),
+            ..Weapon::default()
+        };
+        assert_eq!(ch.weapon_attack_bonus(&dagger), 7);
+
+        // Ranged martial without proficiency: DEX only
+        let longbow = Weapon {
+            category: Some(WeaponCategory::Martial),
+            properties: [WeaponProperty::Ranged, WeaponProperty::Ammunition]
+                .into_iter()
+                .collect(),
+            ..Weapon::default()
+        };
+        assert_eq!(ch.weapon_attack_bonus(&longbow), 2);
+    }
+
+    // --- items ---
+
+    fn ring_of_protection() -> Item {
+        Item {
+            name: "Ring of Protection".to_string(),
+            quantity: 1,
+            equipped: true,
+            requires_attunement: true,
+            expr: Some("AC += 1".parse().unwrap()),
+            ..Item::default()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn item_assignments_require_attunement() {
+        let mut ch = test_character();
+        ch.equipment.items.push(ring_of_protection());
+        ch.combat.armor_class = 12;
+
+        ch.apply_item_assignments();
+        assert_eq!(ch.combat.armor_class, 12);
+
+        assert!(ch.equipment.set_attuned(0, true));
+        ch.apply_item_assignments();
+        assert_eq!(ch.combat.armor_class, 13);
+    }
+
+    #[wasm_bindgen_test]
+    fn attunement_limited_to_three_items() {
+        let mut ch = test_character();
+        ch.equipment.items = vec![ring_of_protection(); 4];
+        for i in 0..Equipment::MAX_ATTUNED {
+            assert!(ch.equipment.set_attuned(i, true));
+        }
+        assert!(!ch.equipment.set_attuned(3, true));
+        assert_eq!(ch.equipment.attuned_count(), 3);
+        // Re-attuning an attuned item takes no new slot
+        assert!(ch.equipment.set_attuned(2, true));
+
+        assert!(ch.equipment.set_attuned(0, false));
+        assert!(ch.equipment.set_attuned(3, true));
+    }
+
+    // --- item spells ---
+
+    fn wand(recharge: Option<&str>) -> Item {
+        Item {
+            name: "Wand of Magic Missiles".to_string(),
+            quantity: 1,
+            spells: vec![ItemSpell {
+                list: "spells/wizard.json".to_string(),
+                name: "Magic Missile".to_string(),
+                level: 1,
+                cost: 1,
+                ..Default::default()
+            }],
+            charges: Some(ItemCharges {
+                used: 0,
+                max: 7,
+                recharge: recharge.map(|r| r.parse().unwrap()),
+            }),
+            ..Item::default()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn scroll_consumed_on_cast() {
+        let mut ch = test_character();
+        ch.equipment.items.push(Item {
+            name: "Spell Scroll".to_string(),
+            quantity: 2,
+            spells: vec![ItemSpell {
+                name: "Fireball".to_string(),
+                level: 3,
+                save_dc: 15,
+                attack_bonus: 7,
+                ..Default::default()
+            }],
+            ..Item::default()
+        });
+        assert!(ch.equipment.cast_from_item(0, 0));
+        assert_eq!(ch.equipment.items[0].quantity, 1);
+        assert!(ch.equipment.cast_from_item(0, 0));
+        assert!(ch.equipment.items.is_empty());
+        assert!(!ch.equipment.cast_from_item(0, 0));
+    }
+
+    #[wasm_bindgen_test]
+    fn effectless_spell_spends_item_on_cast() {
+        // Comprehend Languages has no cast effects; the item is still spent
+        let spell = ItemSpell {
+            list: "spells/wizard.json".to_string(),
+            name: "Comprehend Languages".to_string(),
+            level: 1,
+            cost: 1,
+            ..Default::default()
+        };
+        let mut ch = test_character();
+        ch.equipment.items.push(Item {
+            name: "Spell Scroll".to_string(),
+            quantity: 1,
+            spells: vec![spell.clone()],
+            ..Item::default()
+        });
+        let mut staff = wand(None);
+        staff.spells = vec![spell];
+        ch.equipment.items.push(staff);
+
+        assert!(ch.equipment.cast_from_item(0, 0));
+        assert_eq!(ch.equipment.items.len(), 1);
+        assert!(ch.equipment.cast_from_item(0, 0));
+        let charges = ch.equipment.items[0].charges.as_ref().unwrap();
+        assert_eq!(charges.used, 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn wand_spends_charges_and_recharges_at_dawn() {
+        let mut ch = test_character();
+        ch.equipment.items.push(wand(Some("3")));
+        for _ in 0..7 {
+            assert!(ch.equipment.cast_from_item(0, 0));
+        }
+        assert!(!ch.equipment.items[0].can_cast(0));
+        assert!(!ch.equipment.cast_from_item(0, 0));
+        assert_eq!(ch.equipment.items.len(), 1);
+
+        ch.long_rest();
+        let charges = ch.equipment.items[0].charges.as_ref().unwrap();
+        assert_eq!(charges.available(), 3);
+    }
+
+    #[wasm_bindgen_test]
+    fn wand_recharge_rolls_dice() {
+        let mut ch = test_character();
+        ch.equipment.items.push(wand(Some("1d6 + 1")));
+        ch.equipment.items[0].charges.as_mut().unwrap().used = 7;
+        ch.recharge_items();
+        let available = ch.equipment.items[0].charges.as_ref().unwrap().available();
+        assert!((2..=7).contains(&available));
+
+        // Without a recharge rule every charge comes back
+        ch.equipment.items[0] = wand(None);
+        ch.equipment.items[0].charges.as_mut().unwrap().used = 5;
+        ch.recharge_items();
+        assert_eq!(
+            ch.equipment.items[0].charges.as_ref().unwrap().available(),
+            7
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn item_spells_require_attunement() {
+        let mut ch = test_character();
+        let mut staff = wand(None);
+        staff.requires_attunement = true;
+        ch.equipment.items.push(staff);
+        assert!(!ch.equipment.cast_from_item(0, 0));
+        ch.equipment.items[0].attuned = true;
+        assert!(ch.equipment.cast_from_item(0, 0));
+    }
+
+    // --- encumbrance ---
+
+    #[wasm_bindgen_test]
+    fn carrying_capacity_scales_with_size() {
+        let mut ch = test_character();
+        assert_eq!(ch.carrying_capacity(), 240.0);
+        ch.identity.size = Size::Large;
+        assert_eq!(ch.carrying_capacity(), 480.0);
+        ch.identity.size = Size::Tiny;
+        assert_eq!(ch.carrying_capacity(), 120.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn total_weight_includes_items_and_coins() {
+        let mut ch = test_character();
+        ch.equipment.items = vec![Item {
+            name: "Torch".to_string(),
+            quantity: 10,
+            weight: 1.0,
+            ..Item::default()
+        }];
+        ch.equipment.currency.gp = 100;
+        assert_eq!(ch.equipment.total_weight(), 12.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn encumbrance_thresholds() {
+        let mut ch = test_character();
+        ch.equipment.items = vec![Item {
+            name: "Rock".to_string(),
+            quantity: 1,
+            weight: 100.0,
+            ..Item::default()
+        }];
+        assert_eq!(ch.encumbrance(), Encumbrance::None);
+
+        ch.equipment.variant_encumbrance = true;
+        assert_eq!(ch.encumbrance(), Encumbrance::Encumbered);
+
+        ch.equipment.items[0].weight = 200.0;
+        assert_eq!(ch.encumbrance(), Encumbrance::HeavilyEncumbered);
+
+        ch.equipment.items[0].weight = 250.0;
+        assert_eq!(ch.encumbrance(), Encumbrance::OverCapacity);
+        ch.equipment.variant_encumbrance = false;
+        assert_eq!(ch.encumbrance(), Encumbrance::OverCapacity);
+    }
+
+    #[wasm_bindgen_test]
+    fn encumbrance_penalties_parse() {
+        assert!(Encumbrance::None.penalty_expr().is_none());
+        for level in [
+            Encumbrance::Encumbered,
+            Encumbrance::HeavilyEncumbered,
+            Encumbrance::OverCapacity,
+        ] {
+            assert!(level.penalty_expr().is_some(), "{level:?}");
+        }
+    }
+
     // --- level() ---
 
     #[wasm_bindgen_test]
@@ -1398,6 +1935,189 @@ pub mod tests {
         assert_eq!(slots[4].total, 2); // new 5th-level slots
     }
 
+    // --- spell preparation ---
+
+    fn preparing_caster(limit: u32) -> Character {
+        let mut ch = test_character();
+        make_caster(&mut ch, "Fighter", "Spellcasting", 1, SpellSlotPool::Arcane);
+        let spell_data = ch
+            .feature_data
+            .get_mut("Spellcasting")
+            .and_then(|entry| entry.spells.as_mut())
+            .unwrap();
+        spell_data.prepare_limit = Some(limit);
+        spell_data.spells = (0..3)
+            .map(|i| Spell {
+                name: format!("Spell {i}"),
+                level: 1,
+                ..Default::default()
+            })
+            .collect();
+        ch
+    }
+
+    fn spell_data(ch: &mut Character) -> &mut SpellData {
+        ch.feature_data
+            .get_mut("Spellcasting")
+            .and_then(|entry| entry.spells.as_mut())
+            .unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn prepared_count_ignores_cantrips_and_always_prepared() {
+        let mut ch = preparing_caster(2);
+        let sd = spell_data(&mut ch);
+        sd.spells.push(Spell {
+            name: "Light".into(),
+            level: 0,
+            ..Default::default()
+        });
+        sd.spells.push(Spell {
+            name: "Bless".into(),
+            level: 1,
+            sticky: true,
+            ..Default::default()
+        });
+        sd.spells.push(Spell {
+            level: 1,
+            ..Default::default()
+        });
+        assert_eq!(sd.prepared_count(), 3);
+    }
+
+    #[wasm_bindgen_test]
+    fn preparations_locked_until_long_rest() {
+        let mut ch = preparing_caster(2);
+        let sd = spell_data(&mut ch);
+        assert!(!sd.can_change(&sd.spells[0]));
+        let cantrip = Spell::default();
+        assert!(sd.can_change(&cantrip));
+
+        ch.long_rest();
+        let sd = spell_data(&mut ch);
+        assert!(sd.preparing);
+        assert!(sd.can_change(&sd.spells[0]));
+        sd.preparing = false;
+        assert!(!sd.can_change(&sd.spells[0]));
+    }
+
+    #[wasm_bindgen_test]
+    fn preparing_stops_at_limit() {
+        let mut ch = preparing_caster(3);
+        let sd = spell_data(&mut ch);
+        sd.spells.push(Spell {
+            name: "Light".into(),
+            ..Default::default()
+        });
+        // Outside preparation a cantrip row can't become a leveled spell
+        assert!(!sd.update_spell(3, |spell| spell.level = 1));
+        assert!(sd.update_spell(3, |spell| spell.description = "Glow".into()));
+
+        sd.preparing = true;
+        // Three of three prepared already
+        assert!(!sd.update_spell(3, |spell| spell.level = 1));
+        assert!(sd.update_spell(0, |spell| spell.level = 0));
+        assert!(sd.update_spell(3, |spell| spell.level = 1));
+        assert_eq!(sd.prepared_count(), 3);
+        assert!(!sd.can_prepare_more());
+    }
+
+    #[wasm_bindgen_test]
+    fn first_prepare_limit_opens_preparation() {
+        let mut ch = preparing_caster(2);
+        let sd = spell_data(&mut ch);
+        sd.prepare_limit = None;
+        sd.set_prepare_limit(Some(2));
+        assert!(sd.preparing);
+
+        sd.preparing = false;
+        sd.set_prepare_limit(Some(3));
+        assert!(!sd.preparing);
+    }
+
+    // --- spellbook copying ---
+
+    fn wizard_with_book(level: u32, gp: u32) -> Character {
+        let mut ch = test_character();
+        ch.identity.classes[0].class = "Wizard".to_string();
+        ch.identity.classes[0].level = level;
+        make_caster(&mut ch, "Wizard", "Spellcasting", 1, SpellSlotPool::Arcane);
+        spell_data(&mut ch).known = Some(Vec::new());
+        ch.equipment.currency = Currency {
+            gp,
+            ..Default::default()
+        };
+        ch
+    }
+
+    fn listed(_: &Spell) -> bool {
+        true
+    }
+
+    fn found_spell(name: &str, level: u32) -> Spell {
+        Spell {
+            name: name.to_string(),
+            level,
+            ..Default::default()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn copy_into_spellbook_spends_gold_and_time() {
+        let mut ch = wizard_with_book(5, 200);
+        assert!(ch.copy_into_spellbook("Spellcasting", found_spell("Fireball", 3), listed));
+        assert_eq!(ch.equipment.currency.as_money(), Money::from_gp(50));
+        let book = spell_data(&mut ch).known.clone().unwrap();
+        assert_eq!(book.len(), 1);
+        assert_eq!(
+            book[0].copied,
+            Some(SpellCopy {
+                cost: Money::from_gp(150),
+                hours: 6,
+            })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn copy_into_spellbook_rejects_invalid_copies() {
+        let mut ch = wizard_with_book(3, 60);
+        // Cantrips aren't written into the book
+        assert!(!ch.copy_into_spellbook("Spellcasting", found_spell("Light", 0), listed));
+        // Not enough gold for a 2nd-level spell
+        assert!(!ch.copy_into_spellbook("Spellcasting", found_spell("Web", 2), listed));
+        // Not on the class's spell list
+        assert!(!ch.copy_into_spellbook("Spellcasting", found_spell("Cure Wounds", 1), |_| false));
+        assert!(ch.copy_into_spellbook("Spellcasting", found_spell("Sleep", 1), listed));
+        // Already in the book
+        assert!(!ch.copy_into_spellbook("Spellcasting", found_spell("Sleep", 1), listed));
+        // No spellbook on this feature
+        assert!(!ch.copy_into_spellbook("Pact Magic", found_spell("Shield", 1), listed));
+        assert_eq!(ch.equipment.currency.as_money(), Money::from_gp(10));
+        assert_eq!(spell_data(&mut ch).known.as_ref().unwrap().len(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn copy_into_spellbook_limited_by_wizard_levels() {
+        let mut ch = wizard_with_book(3, 1000);
+        // Cleric slots in the same pool don't let the wizard copy higher
+        ch.identity.classes.push(ClassLevel {
+            class: "Cleric".to_string(),
+            level: 5,
+            ..ch.identity.classes[0].clone()
+        });
+        make_caster(
+            &mut ch,
+            "Cleric",
+            "Divine Spellcasting",
+            1,
+            SpellSlotPool::Arcane,
+        );
+        assert_eq!(ch.caster_level(SpellSlotPool::Arcane), 8);
+        assert_eq!(ch.class_spell_level("Spellcasting"), 2);
+        assert!(!ch.copy_into_spellbook("Spellcasting", found_spell("Fireball", 3), listed));
+        assert!(ch.copy_into_spellbook("Spellcasting", found_spell("Web", 2), listed));
+    }
+
     // --- class_summary() ---
 
     #[wasm_bindgen_test]
@@ -1672,6 +2392,8 @@ pub mod tests {
             } else {
                 Some(expr_str.parse::<Expr<Attribute>>().unwrap())
             },
+            weight: 0.0,
+            cost: Money::default(),
         }
     }
 
@@ -1781,9 +2503,9 @@ pub mod tests {
     fn compute_hp_max_multiclass() {
         // Fighter 5 (d10) + Wizard 2 (d6), CON 12 (mod +1), total level 7
         // Fighter: 10 + 4 * 6 = 34
-        // Wizard: 6 + 1 * 4 = 10
+        // Wizard: only the starting class gets the full die, 2 * 4 = 8
         // con = 7 * 1 = 7
-        // total = 51
+        // total = 49
         let mut ch = test_character();
         ch.identity.classes.push(ClassLevel {
             class: "Wizard".to_string(),
@@ -1794,9 +2516,14 @@ pub mod tests {
             hit_die_sides: 6,
             hit_dice_used: 0,
             applied_levels: VecSet::new(),
+            hp_rolls: BTreeMap::new(),
         });
         let hp = ch.compute_hp_max();
-        assert_eq!(hp, 51);
+        assert_eq!(hp, 49);
+
+        // Rolled 5 for the first Wizard level
+        ch.identity.classes[1].hp_rolls.insert(1, 5);
+        assert_eq!(ch.compute_hp_max(), 50);
     }
 
     #[wasm_bindgen_test]
@@ -1811,6 +2538,61 @@ pub mod tests {
         assert_eq!(hp, 24);
     }
 
+    #[wasm_bindgen_test]
+    fn compute_hp_max_recorded_rolls() {
+        // Fighter level 5, d10, CON 12 (mod +1)
+        // rolled 2, 10 and 1 at levels 2, 4 and 5; average (6) at level 3
+        // base = 10 + 2 + 6 + 10 + 1 = 29
+        // con = 5 * 1 = 5
+        // total = 34
+        let mut ch = test_character();
+        ch.identity.classes[0].hp_rolls = BTreeMap::from([(2, 2), (4, 10), (5, 1)]);
+        let hp = ch.compute_hp_max();
+        assert_eq!(hp, 34);
+    }
+
+    #[wasm_bindgen_test]
+    fn roll_hit_points_from_pool() {
+        let mut ch = test_character();
+        let pool: DicePool = BTreeMap::from([(10, vec![7])]).into();
+        let roll = ch.roll_hit_points(0, 5, Some(&pool)).unwrap();
+        assert_eq!(roll.total, 7);
+        assert_eq!(ch.identity.classes[0].hp_rolls.get(&5), Some(&7));
+        // base = 10 + 6 + 6 + 6 + 7 = 35, con = 5
+        assert_eq!(ch.compute_hp_max(), 40);
+    }
+
+    // --- XP progression ---
+
+    fn award(amount: u32) -> XpAward {
+        XpAward {
+            amount,
+            reason: "Goblin ambush".to_string(),
+            date: String::new(),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn award_xp_crosses_next_level() {
+        // Level 5 with 0 XP, level 6 needs 14 000
+        let mut ch = test_character();
+        assert_eq!(ch.next_level_xp(), Some(14_000));
+        assert!(!ch.award_xp(award(13_900)));
+        assert!(ch.award_xp(award(100)));
+        // Already past the threshold: further awards don't prompt again
+        assert!(!ch.award_xp(award(500)));
+        assert_eq!(ch.identity.experience_points, 14_500);
+        assert_eq!(ch.identity.xp_awards.len(), 3);
+    }
+
+    #[wasm_bindgen_test]
+    fn award_xp_milestone_never_prompts() {
+        let mut ch = test_character();
+        ch.identity.milestone = true;
+        assert!(!ch.award_xp(award(20_000)));
+        assert_eq!(ch.identity.experience_points, 20_000);
+    }
+
     #[wasm_bindgen_test]
     fn compute_speed_resets_to_default() {
         let mut ch = test_character();
@@ -1819,4 +2601,76 @@ pub mod tests {
         assert_eq!(speed, 30);
         assert_eq!(ch.combat.speed, 30);
     }
+
+    #[wasm_bindgen_test]
+    fn compute_clears_save_advantages() {
+        use crate::expr::Context as _;
+
+        let mut ch = test_character();
+        ch.assign(Attribute::SaveAdvantage(Ability::Constitution), 1)
+            .unwrap();
+        assert_eq!(
+            ch.resolve(Attribute::SaveAdvantage(Ability::Constitution)),
+            Ok(1)
+        );
+        ch.compute();
+        assert_eq!(
+            ch.resolve(Attribute::SaveAdvantage(Ability::Constitution)),
+            Ok(0)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn writable_attributes_match_assign() {
+        use crate::expr::Context as _;
+
+        let mut ch = test_character();
+        let mut ctx = Context::from(&mut ch);
+        ctx.points = vec![(0, 2, 3)];
+        for name in [
+            "STR",
+            "STR.MOD",
+            "STR.SAVE",
+            "STR.SAVE.PROF",
+            "STR.ADV",
+            "STR.SAVE.ADV",
+            "SKILL.ACRO",
+            "SKILL.ACRO.PROF",
+            "PROF.SHIELDS",
+            "MAX_HP",
+            "HP",
+            "TEMP_HP",
+            "LEVEL",
+            "AC",
+            "SPEED",
+            "CLASS_LEVEL",
+            "CASTER_LEVEL",
+            "PROF_BONUS",
+            "ATK",
+            "ATK.ADV",
+            "ATTACKS",
+            "INITIATIVE",
+            "INITIATIVE.BONUS",
+            "INSPIRATION",
+            "SPELL.DC",
+            "SLOT_LEVEL",
+            "POINTS",
+            "POINTS_MAX",
+            "COST",
+            "RESIST.FIRE",
+            "VULN.FIRE",
+            "IMMUNE.FIRE",
+            "DR.FIRE",
+            "ARG.0",
+            "FEAT.Alert",
+            "LANG.Elvish",
+        ] {
+            let attr: Attribute = name.parse().unwrap();
+            assert_eq!(
+                ctx.assign(attr, 1).is_ok(),
+                attr.is_writable(),
+                "{name} writability disagrees with assign"
+            );
+        }
+    }
 }
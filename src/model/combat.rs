@@ -86,12 +86,21 @@ impl DamageModifiers {
 }
 
 impl CombatStats {
-    pub fn damage(&mut self, amount: u32) {
+    /// DC of the Constitution save to keep concentration after taking
+    /// `damage`.
+    pub fn concentration_dc(damage: u32) -> u32 {
+        (damage / 2).max(10)
+    }
+
+    /// Apply damage, temp HP first. Returns the concentration save DC for
+    /// the hit, based on the full damage even if temp HP absorbed some of
+    /// it, or `None` if no damage was dealt.
+    pub fn damage(&mut self, amount: u32) -> Option<u32> {
         if amount == 0 {
-            return;
+            return None;
         }
 
-        let amount = if self.hp_temp > 0 {
+        let remaining = if self.hp_temp > 0 {
             let temp_absorb = self.hp_temp.min(amount);
             self.hp_temp -= temp_absorb;
             amount - temp_absorb
@@ -99,7 +108,8 @@ impl CombatStats {
             amount
         };
 
-        self.hp_current = self.hp_current.saturating_sub(amount);
+        self.hp_current = self.hp_current.saturating_sub(remaining);
+        Some(Self::concentration_dc(amount))
     }
 
     pub fn heal(&mut self, amount: u32) {
@@ -112,3 +122,34 @@ impl CombatStats {
         self.death_save_failures = 0;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn damage_returns_concentration_dc() {
+        let mut combat = CombatStats {
+            hp_max: 40,
+            hp_current: 40,
+            hp_temp: 5,
+            ..Default::default()
+        };
+
+        assert_eq!(combat.damage(0), None);
+        assert_eq!(combat.damage(8), Some(10));
+        assert_eq!(combat.hp_temp, 0);
+        assert_eq!(combat.hp_current, 37);
+        assert_eq!(combat.damage(27), Some(13));
+        assert_eq!(combat.hp_current, 10);
+
+        // Damage soaked up by temp HP still counts toward the DC
+        combat.hp_current = 40;
+        combat.hp_temp = 10;
+        assert_eq!(combat.damage(30), Some(15));
+        assert_eq!(combat.hp_temp, 0);
+        assert_eq!(combat.hp_current, 20);
+    }
+}
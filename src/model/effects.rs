@@ -24,6 +24,16 @@ pub enum EffectDuration {
     Forever,
 }
 
+impl EffectDuration {
+    /// Number of combat rounds the effect lasts, if it is round-limited.
+    pub fn rounds(self) -> Option<u32> {
+        match self {
+            Self::Rounds(rounds) => Some(rounds),
+            Self::Instant | Self::Forever => None,
+        }
+    }
+}
+
 /// A lightweight effect definition carrying a name and expression.
 /// Used on `SpellDefinition` for damage/healing formulas; designed to be
 /// reusable for feature effects, weapon effects, etc.
@@ -40,6 +50,8 @@ pub struct EffectDefinition {
     #[serde(default)]
     pub stackable: bool,
     #[serde(default)]
+    pub concentration: bool,
+    #[serde(default)]
     pub scope: Option<String>,
 }
 
@@ -58,6 +70,7 @@ impl From<&WeaponEffect> for EffectDefinition {
             range: EffectRange::default(),
             duration: EffectDuration::default(),
             stackable: false,
+            concentration: false,
             scope: None,
         }
     }
@@ -78,6 +91,13 @@ pub struct ActiveEffect {
     pub enabled: bool,
     #[serde(default)]
     pub scope: Option<Box<str>>,
+    /// Combat rounds left before the effect expires. `None` means the effect
+    /// lasts until turned off by hand.
+    #[serde(default)]
+    pub rounds: Option<u32>,
+    /// Requires concentration: only one such effect can be enabled at a time.
+    #[serde(default)]
+    pub concentration: bool,
 }
 
 impl ActiveEffect {
@@ -96,6 +116,15 @@ impl demap::Named for ActiveEffect {
     }
 }
 
+/// A condition currently affecting the character. The expression is copied
+/// from the conditions catalog when toggled on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveCondition {
+    pub level: u32,
+    #[serde(default)]
+    pub expr: Option<Expr<Attribute>>,
+}
+
 #[derive(Clone)]
 pub struct EffectsIndex(pub BTreeMap<Box<str>, ActiveEffect>);
 
@@ -125,6 +154,12 @@ pub struct ActiveEffects {
     /// Persisted so additive effects (HP += X) don't re-apply on reload.
     #[serde(default)]
     memoized: BTreeMap<Attribute, i32>,
+    /// Current combat round, `None` when out of combat.
+    #[serde(default)]
+    combat_round: Option<u32>,
+    /// Active conditions keyed by name.
+    #[serde(default)]
+    conditions: BTreeMap<Box<str>, ActiveCondition>,
 }
 
 impl ActiveEffects {
@@ -157,7 +192,10 @@ impl ActiveEffects {
     }
 
     pub fn add(&mut self, effect: ActiveEffect, character: &Character) {
-        let needs_recompute = effect.enabled && effect.expr.is_some();
+        let mut needs_recompute = effect.enabled && effect.expr.is_some();
+        if effect.enabled && effect.concentration {
+            needs_recompute |= self.break_concentration();
+        }
         self.effects.push(effect);
         if needs_recompute {
             self.recompute(character);
@@ -179,12 +217,117 @@ impl ActiveEffects {
     }
 
     pub fn toggle(&mut self, index: usize, character: &Character) {
+        let Some(effect) = self.effects.get(index) else {
+            return;
+        };
+        if !effect.enabled && effect.concentration {
+            self.break_concentration();
+        }
         if let Some(effect) = self.effects.get_mut(index) {
             effect.enabled = !effect.enabled;
+            // Re-enabling an expired effect makes it last until turned off again
+            if effect.enabled && effect.rounds == Some(0) {
+                effect.rounds = None;
+            }
+        }
+        self.recompute(character);
+    }
+
+    /// The enabled effect the character is concentrating on, if any.
+    pub fn concentration(&self) -> Option<&ActiveEffect> {
+        self.effects
+            .iter()
+            .find(|effect| effect.enabled && effect.concentration)
+    }
+
+    /// End concentration (e.g. after a failed Constitution save).
+    pub fn end_concentration(&mut self, character: &Character) {
+        if self.break_concentration() {
+            self.recompute(character);
+        }
+    }
+
+    /// Disable every enabled concentration effect without recomputing.
+    /// Returns true if anything was disabled.
+    fn break_concentration(&mut self) -> bool {
+        let mut changed = false;
+        for effect in self
+            .effects
+            .iter_mut()
+            .filter(|effect| effect.enabled && effect.concentration)
+        {
+            effect.enabled = false;
+            changed = true;
+        }
+        changed
+    }
+
+    /// Active conditions with their levels, by name.
+    pub fn conditions(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.conditions
+            .iter()
+            .map(|(name, condition)| (&**name, condition.level))
+    }
+
+    /// Current level of a condition, 0 if not active.
+    pub fn condition_level(&self, name: &str) -> u32 {
+        self.conditions
+            .get(name)
+            .map_or(0, |condition| condition.level)
+    }
+
+    /// Set a condition to the given level (0 removes it) and recompute.
+    pub fn set_condition(
+        &mut self,
+        name: &str,
+        level: u32,
+        expr: Option<Expr<Attribute>>,
+        character: &Character,
+    ) {
+        if level == 0 {
+            self.conditions.remove(name);
+        } else {
+            self.conditions
+                .insert(name.into(), ActiveCondition { level, expr });
         }
         self.recompute(character);
     }
 
+    pub fn combat_round(&self) -> Option<u32> {
+        self.combat_round
+    }
+
+    pub fn start_combat(&mut self) {
+        self.combat_round = Some(1);
+    }
+
+    pub fn end_combat(&mut self) {
+        self.combat_round = None;
+    }
+
+    /// Advance the combat clock by one round: count down round-limited
+    /// effects and disable the ones that run out. Does nothing out of combat.
+    pub fn next_turn(&mut self, character: &Character) {
+        let Some(round) = self.combat_round.as_mut() else {
+            return;
+        };
+        *round += 1;
+
+        let mut expired = false;
+        for effect in self.effects.iter_mut().filter(|effect| effect.enabled) {
+            if let Some(rounds) = effect.rounds.as_mut() {
+                *rounds = rounds.saturating_sub(1);
+                if *rounds == 0 {
+                    effect.enabled = false;
+                    expired = true;
+                }
+            }
+        }
+        if expired {
+            self.recompute(character);
+        }
+    }
+
     /// Evaluate all enabled expressions. Must be called after
     /// deserialization and after any mutation.
     pub fn recompute(&mut self, character: &Character) -> bool {
@@ -250,6 +393,7 @@ impl ActiveEffects {
             effects,
             overrides,
             scoped_overrides,
+            conditions,
             ..
         } = self;
 
@@ -284,6 +428,38 @@ impl ActiveEffects {
                 log::error!("Effect '{}' expression error: {error}", effect.name);
             }
         }
+
+        // Leveled conditions apply their expression once per level
+        for (name, condition) in conditions.iter() {
+            let Some(ref expr) = condition.expr else {
+                continue;
+            };
+            let mut ctx = Ctx {
+                character,
+                global: overrides,
+                scoped: None,
+                casting_ability: None,
+            };
+            for _ in 0..condition.level {
+                if let Err(error) = expr.apply(&mut ctx) {
+                    log::error!("Condition '{name}' expression error: {error}");
+                    break;
+                }
+            }
+        }
+
+        // Carried weight penalties
+        if let Some(expr) = character.encumbrance().penalty_expr() {
+            let mut ctx = Ctx {
+                character,
+                global: overrides,
+                scoped: None,
+                casting_ability: None,
+            };
+            if let Err(error) = expr.apply(&mut ctx) {
+                log::error!("Encumbrance expression error: {error}");
+            }
+        }
         CONSUMABLE_ATTRS.iter().any(|attr| {
             if self.overrides.contains_key(attr) {
                 !self.memoized.contains_key(attr)
@@ -333,6 +509,8 @@ mod tests {
             pool: None,
             enabled: true,
             scope: None,
+            rounds: None,
+            concentration: false,
         }
     }
 
@@ -385,6 +563,8 @@ mod tests {
             pool: None,
             enabled: true,
             scope: Some(scope.into()),
+            rounds: None,
+            concentration: false,
         }
     }
 
@@ -399,6 +579,8 @@ mod tests {
                     pool: SpellSlotPool::default(),
                     spells: Vec::new(),
                     known: None,
+                    prepare_limit: None,
+                    preparing: false,
                 }),
                 ..Default::default()
             },
@@ -473,4 +655,142 @@ mod tests {
         effects.add(effect_with_expr("AC = 18"), &character);
         assert_eq!(effects.resolve(&character, Attribute::Ac), 18);
     }
+
+    #[wasm_bindgen_test]
+    fn next_turn_expires_round_limited_effects() {
+        let character = Character::new();
+        let base_ac = character.resolve(Attribute::Ac).unwrap_or(0);
+        let mut effects = ActiveEffects::default();
+
+        effects.add(
+            ActiveEffect {
+                rounds: Some(2),
+                ..effect_with_expr("AC += 2")
+            },
+            &character,
+        );
+        effects.add(effect_with_expr("SPEED += 10"), &character);
+
+        // Out of combat the clock doesn't tick
+        effects.next_turn(&character);
+        assert_eq!(effects.effects()[0].rounds, Some(2));
+
+        effects.start_combat();
+        effects.next_turn(&character);
+        assert_eq!(effects.combat_round(), Some(2));
+        assert_eq!(effects.effects()[0].rounds, Some(1));
+        assert_eq!(effects.resolve(&character, Attribute::Ac), base_ac + 2);
+
+        effects.next_turn(&character);
+        assert!(!effects.effects()[0].enabled);
+        assert_eq!(effects.resolve(&character, Attribute::Ac), base_ac);
+        // Unlimited effects keep running
+        assert!(effects.effects()[1].enabled);
+
+        effects.end_combat();
+        assert_eq!(effects.combat_round(), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn reenabling_expired_effect_clears_duration() {
+        let character = Character::new();
+        let mut effects = ActiveEffects::default();
+
+        effects.add(
+            ActiveEffect {
+                rounds: Some(1),
+                ..effect_with_expr("AC += 2")
+            },
+            &character,
+        );
+        effects.start_combat();
+        effects.next_turn(&character);
+        assert!(!effects.effects()[0].enabled);
+
+        effects.toggle(0, &character);
+        assert!(effects.effects()[0].enabled);
+        assert_eq!(effects.effects()[0].rounds, None);
+    }
+
+    #[wasm_bindgen_test]
+    fn concentration_replaces_previous_concentration_effect() {
+        let character = Character::new();
+        let base_ac = character.resolve(Attribute::Ac).unwrap_or(0);
+        let mut effects = ActiveEffects::default();
+
+        effects.add(
+            ActiveEffect {
+                concentration: true,
+                ..effect_with_expr("AC += 2")
+            },
+            &character,
+        );
+        effects.add(effect_with_expr("SPEED += 10"), &character);
+        effects.add(
+            ActiveEffect {
+                concentration: true,
+                ..effect_with_expr("AC += 1")
+            },
+            &character,
+        );
+
+        assert!(!effects.effects()[0].enabled);
+        assert!(effects.effects()[1].enabled);
+        assert!(effects.effects()[2].enabled);
+        assert_eq!(effects.resolve(&character, Attribute::Ac), base_ac + 1);
+
+        // Toggling the first one back on ends the second
+        effects.toggle(0, &character);
+        assert!(effects.effects()[0].enabled);
+        assert!(!effects.effects()[2].enabled);
+        assert_eq!(effects.resolve(&character, Attribute::Ac), base_ac + 2);
+
+        effects.end_concentration(&character);
+        assert!(effects.concentration().is_none());
+        assert!(effects.effects()[1].enabled);
+        assert_eq!(effects.resolve(&character, Attribute::Ac), base_ac);
+    }
+
+    #[wasm_bindgen_test]
+    fn leveled_condition_stacks_penalties() {
+        let character = Character::new();
+        let base_speed = character.resolve(Attribute::Speed).unwrap_or(0);
+        let mut effects = ActiveEffects::default();
+        let exhaustion: Expr<Attribute> = "SPEED -= 5; ATK -= 2".parse().unwrap();
+
+        effects.set_condition("Exhaustion", 2, Some(exhaustion.clone()), &character);
+        assert_eq!(effects.condition_level("Exhaustion"), 2);
+        assert_eq!(
+            effects.resolve(&character, Attribute::Speed),
+            base_speed - 10
+        );
+
+        effects.set_condition("Exhaustion", 3, Some(exhaustion), &character);
+        assert_eq!(
+            effects.resolve(&character, Attribute::Speed),
+            base_speed - 15
+        );
+
+        effects.set_condition("Exhaustion", 0, None, &character);
+        assert_eq!(effects.condition_level("Exhaustion"), 0);
+        assert_eq!(effects.resolve(&character, Attribute::Speed), base_speed);
+    }
+
+    #[wasm_bindgen_test]
+    fn condition_sets_disadvantage() {
+        let character = Character::new();
+        let mut effects = ActiveEffects::default();
+
+        effects.set_condition(
+            "Poisoned",
+            1,
+            Some("ATK.ADV = -1; STR.ADV = -1".parse().unwrap()),
+            &character,
+        );
+        assert_eq!(effects.resolve(&character, Attribute::AttackAdvantage), -1);
+
+        // An advantage effect cancels it out
+        effects.add(effect_with_expr("ATK.ADV = 1"), &character);
+        assert_eq!(effects.resolve(&character, Attribute::AttackAdvantage), 0);
+    }
 }
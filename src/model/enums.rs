@@ -62,7 +62,13 @@ macro_rules! enum_serde_u8 {
                             .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))
                     }
                 }
-                d.deserialize_u8(Vis)
+                // Self-describing formats (JSON data files) may spell the
+                // variant name; compact ones (postcard) only carry the u8.
+                if d.is_human_readable() {
+                    d.deserialize_any(Vis)
+                } else {
+                    d.deserialize_u8(Vis)
+                }
             }
         }
     };
@@ -445,6 +451,145 @@ impl ArmorType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Display, Default)]
+#[repr(u8)]
+pub enum Size {
+    Tiny,
+    Small,
+    #[default]
+    Medium,
+    Large,
+    Huge,
+    Gargantuan,
+}
+enum_serde_u8!(Size {
+    Tiny,
+    Small,
+    Medium,
+    Large,
+    Huge,
+    Gargantuan,
+});
+
+impl Size {
+    /// Carrying capacity multiplier relative to a Medium creature.
+    pub fn carry_multiplier(self) -> f32 {
+        match self {
+            Self::Tiny => 0.5,
+            Self::Small | Self::Medium => 1.0,
+            Self::Large => 2.0,
+            Self::Huge => 4.0,
+            Self::Gargantuan => 8.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Display, Default)]
+#[repr(u8)]
+pub enum WeaponCategory {
+    #[default]
+    Simple,
+    Martial,
+}
+enum_serde_u8!(WeaponCategory { Simple, Martial });
+
+impl WeaponCategory {
+    /// The `Proficiency` that adds the proficiency bonus to attacks with
+    /// weapons of this category.
+    pub fn proficiency(self) -> Proficiency {
+        match self {
+            Self::Simple => Proficiency::SimpleWeapons,
+            Self::Martial => Proficiency::MartialWeapons,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Display)]
+#[repr(u8)]
+pub enum WeaponProperty {
+    Finesse,
+    Ranged,
+    Thrown,
+    Versatile,
+    Reach,
+    Ammunition,
+}
+enum_serde_u8!(WeaponProperty {
+    Finesse,
+    Ranged,
+    Thrown,
+    Versatile,
+    Reach,
+    Ammunition,
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Display, EnumString)]
+#[repr(u8)]
+pub enum SpellSchool {
+    Abjuration,
+    Conjuration,
+    Divination,
+    Enchantment,
+    Evocation,
+    Illusion,
+    Necromancy,
+    Transmutation,
+}
+enum_serde_u8!(SpellSchool {
+    Abjuration,
+    Conjuration,
+    Divination,
+    Enchantment,
+    Evocation,
+    Illusion,
+    Necromancy,
+    Transmutation,
+});
+
+/// How long a spell takes to cast. Anything beyond a reaction (1 minute,
+/// 10 minutes, 1 hour...) is `Longer`; the exact wording lives in the spell's
+/// `casting_time_text`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Display, EnumString)]
+#[repr(u8)]
+pub enum CastingTime {
+    Action,
+    BonusAction,
+    Reaction,
+    Longer,
+}
+enum_serde_u8!(CastingTime {
+    Action,
+    BonusAction,
+    Reaction,
+    Longer,
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Display, EnumString)]
+#[repr(u8)]
+pub enum SpellRange {
+    Personal,
+    Touch,
+    Ranged,
+}
+enum_serde_u8!(SpellRange {
+    Personal,
+    Touch,
+    Ranged,
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Display, EnumString)]
+#[repr(u8)]
+pub enum SpellComponent {
+    Verbal,
+    Somatic,
+    Material,
+}
+enum_serde_u8!(SpellComponent {
+    Verbal,
+    Somatic,
+    Material,
+});
+
 impl Translatable for DamageType {
     fn tr_key(&self) -> &'static str {
         match self {
@@ -486,6 +631,95 @@ impl Translatable for ArmorType {
     }
 }
 
+impl Translatable for Size {
+    fn tr_key(&self) -> &'static str {
+        match self {
+            Self::Tiny => "size-tiny",
+            Self::Small => "size-small",
+            Self::Medium => "size-medium",
+            Self::Large => "size-large",
+            Self::Huge => "size-huge",
+            Self::Gargantuan => "size-gargantuan",
+        }
+    }
+}
+
+impl Translatable for WeaponCategory {
+    fn tr_key(&self) -> &'static str {
+        match self {
+            Self::Simple => "weapon-category-simple",
+            Self::Martial => "weapon-category-martial",
+        }
+    }
+}
+
+impl Translatable for WeaponProperty {
+    fn tr_key(&self) -> &'static str {
+        match self {
+            Self::Finesse => "weapon-property-finesse",
+            Self::Ranged => "weapon-property-ranged",
+            Self::Thrown => "weapon-property-thrown",
+            Self::Versatile => "weapon-property-versatile",
+            Self::Reach => "weapon-property-reach",
+            Self::Ammunition => "weapon-property-ammunition",
+        }
+    }
+}
+
+impl Translatable for SpellSchool {
+    fn tr_key(&self) -> &'static str {
+        match self {
+            Self::Abjuration => "spell-school-abjuration",
+            Self::Conjuration => "spell-school-conjuration",
+            Self::Divination => "spell-school-divination",
+            Self::Enchantment => "spell-school-enchantment",
+            Self::Evocation => "spell-school-evocation",
+            Self::Illusion => "spell-school-illusion",
+            Self::Necromancy => "spell-school-necromancy",
+            Self::Transmutation => "spell-school-transmutation",
+        }
+    }
+}
+
+impl Translatable for CastingTime {
+    fn tr_key(&self) -> &'static str {
+        match self {
+            Self::Action => "casting-time-action",
+            Self::BonusAction => "casting-time-bonus-action",
+            Self::Reaction => "casting-time-reaction",
+            Self::Longer => "casting-time-longer",
+        }
+    }
+}
+
+impl Translatable for SpellRange {
+    fn tr_key(&self) -> &'static str {
+        match self {
+            Self::Personal => "spell-range-personal",
+            Self::Touch => "spell-range-touch",
+            Self::Ranged => "spell-range-ranged",
+        }
+    }
+}
+
+impl Translatable for SpellComponent {
+    fn tr_key(&self) -> &'static str {
+        match self {
+            Self::Verbal => "spell-component-verbal",
+            Self::Somatic => "spell-component-somatic",
+            Self::Material => "spell-component-material",
+        }
+    }
+
+    fn tr_abbr_key(&self) -> &'static str {
+        match self {
+            Self::Verbal => "spell-component-verbal-abbr",
+            Self::Somatic => "spell-component-somatic-abbr",
+            Self::Material => "spell-component-material-abbr",
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use wasm_bindgen_test::*;
@@ -578,4 +812,13 @@ pub mod tests {
         let deserialized: ProficiencyLevel = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, pl);
     }
+
+    #[wasm_bindgen_test]
+    fn enum_serde_u8_accepts_variant_names_in_json() {
+        let category: WeaponCategory = serde_json::from_str("\"Martial\"").unwrap();
+        assert_eq!(category, WeaponCategory::Martial);
+        let category: WeaponCategory = serde_json::from_str("1").unwrap();
+        assert_eq!(category, WeaponCategory::Martial);
+        assert!(serde_json::from_str::<WeaponCategory>("\"Exotic\"").is_err());
+    }
 }
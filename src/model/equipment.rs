@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     expr::Expr,
-    model::{ArmorType, Attribute, DamageType, Money},
+    model::{ArmorType, Attribute, DamageType, Money, WeaponCategory, WeaponProperty},
+    vecset::VecSet,
 };
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, Store)]
@@ -16,6 +17,50 @@ pub struct Equipment {
     pub items: Vec<Item>,
     #[serde(default)]
     pub currency: Currency,
+    /// Apply the variant encumbrance rules (speed penalties at 5× and 10×
+    /// STR) instead of only the carrying capacity limit.
+    #[serde(default)]
+    pub variant_encumbrance: bool,
+}
+
+/// Encumbrance level derived from carried weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encumbrance {
+    #[default]
+    None,
+    /// Variant rule: over 5× STR, speed −10 ft.
+    Encumbered,
+    /// Variant rule: over 10× STR, speed −20 ft and disadvantage on
+    /// STR/DEX/CON checks, saves and attacks.
+    HeavilyEncumbered,
+    /// Over carrying capacity: speed drops to 5 ft.
+    OverCapacity,
+}
+
+impl Encumbrance {
+    /// Override expression applied on top of active effects.
+    pub fn penalty_expr(self) -> Option<Expr<Attribute>> {
+        let s = match self {
+            Self::None => return None,
+            Self::Encumbered => "SPEED -= 10",
+            Self::HeavilyEncumbered => {
+                "SPEED -= 20; ATK.ADV = -1; \
+                 STR.ADV = -1; DEX.ADV = -1; CON.ADV = -1; \
+                 STR.SAVE.ADV = -1; DEX.SAVE.ADV = -1; CON.SAVE.ADV = -1"
+            }
+            Self::OverCapacity => "SPEED = min(SPEED, 5)",
+        };
+        s.parse().ok()
+    }
+
+    pub fn tr_key(self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Encumbered => Some("encumbrance-encumbered"),
+            Self::HeavilyEncumbered => Some("encumbrance-heavily-encumbered"),
+            Self::OverCapacity => Some("encumbrance-over-capacity"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, Store)]
@@ -28,6 +73,11 @@ pub struct Armor {
     pub armor_type: ArmorType,
     #[serde(default)]
     pub ac_expr: Option<Expr<Attribute>>,
+    /// Weight in pounds.
+    #[serde(default)]
+    pub weight: f32,
+    #[serde(default)]
+    pub cost: Money,
 }
 
 impl Armor {
@@ -68,10 +118,82 @@ pub struct WeaponEffect {
 pub struct Weapon {
     #[serde(default)]
     pub name: String,
+    /// Flat attack bonus. For weapons with a category this is an extra
+    /// (e.g. magic) bonus on top of the derived ability and proficiency.
     #[serde(default)]
     pub attack_bonus: i32,
     #[serde(default)]
     pub effects: Vec<WeaponEffect>,
+    #[serde(default)]
+    pub category: Option<WeaponCategory>,
+    #[serde(default)]
+    pub properties: VecSet<WeaponProperty>,
+    /// Two-handed damage of a versatile weapon; replaces the first effect's
+    /// expression when wielded with both hands.
+    #[serde(default)]
+    pub versatile: Option<Expr<Attribute>>,
+    /// Backpack item spent on every attack with an ammunition weapon.
+    #[serde(default)]
+    pub ammunition: String,
+    /// Weight in pounds.
+    #[serde(default)]
+    pub weight: f32,
+    #[serde(default)]
+    pub cost: Money,
+}
+
+impl Weapon {
+    pub fn has(&self, property: WeaponProperty) -> bool {
+        self.properties.contains(&property)
+    }
+
+    /// Ability modifier used for attacks: DEX for ranged weapons, the better
+    /// of STR and DEX for finesse weapons, STR otherwise.
+    fn attack_modifier_str(&self) -> &'static str {
+        if self.has(WeaponProperty::Finesse) {
+            "max(STR.MOD, DEX.MOD)"
+        } else if self.has(WeaponProperty::Ranged) {
+            "DEX.MOD"
+        } else {
+            "STR.MOD"
+        }
+    }
+
+    /// Attack bonus formula without the d20 and the global `ATK` bonus.
+    /// Weapons without a category keep the hand-entered flat bonus.
+    pub fn attack_bonus_expr_str(&self) -> String {
+        let Some(category) = self.category else {
+            return self.attack_bonus.to_string();
+        };
+        let proficiency = Attribute::EquipmentProficiency(category.proficiency());
+        let mut s = format!(
+            "{} + if({proficiency}, PROF_BONUS, 0)",
+            self.attack_modifier_str()
+        );
+        match self.attack_bonus {
+            0 => {}
+            bonus if bonus < 0 => s.push_str(&format!(" - {}", -bonus)),
+            bonus => s.push_str(&format!(" + {bonus}")),
+        }
+        s
+    }
+
+    pub fn attack_bonus_expr(&self) -> Expr<Attribute> {
+        self.attack_bonus_expr_str().parse().unwrap_or_default()
+    }
+
+    /// Damage effects for an attack, with the first effect's expression
+    /// swapped for the versatile damage when attacking two-handed.
+    pub fn attack_effects(&self, two_handed: bool) -> Vec<WeaponEffect> {
+        let mut effects = self.effects.clone();
+        if two_handed
+            && self.has(WeaponProperty::Versatile)
+            && let (Some(versatile), Some(first)) = (&self.versatile, effects.first_mut())
+        {
+            first.expr = versatile.clone();
+        }
+        effects
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, Store)]
@@ -82,6 +204,184 @@ pub struct Item {
     pub quantity: u32,
     #[serde(default)]
     pub description: String,
+    #[serde(default)]
+    pub equipped: bool,
+    #[serde(default)]
+    pub requires_attunement: bool,
+    #[serde(default)]
+    pub attuned: bool,
+    /// Assignments applied on every compute while the item is active, e.g.
+    /// `AC += 1` for a Ring of Protection.
+    #[serde(default)]
+    pub expr: Option<Expr<Attribute>>,
+    /// Weight of a single unit in pounds.
+    #[serde(default)]
+    pub weight: f32,
+    #[serde(default)]
+    pub cost: Money,
+    /// Spells the item can cast, e.g. the spell of a scroll or the spells
+    /// of a staff.
+    #[serde(default)]
+    pub spells: Vec<ItemSpell>,
+    /// Charges spent by casting. Items with spells but without charges are
+    /// single-use (scrolls): one unit is consumed per cast.
+    #[serde(default)]
+    pub charges: Option<ItemCharges>,
+}
+
+/// A spell cast from an item with the item's own save DC and attack bonus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ItemSpell {
+    /// Spell list the spell is defined in, e.g. `spells/wizard.json`.
+    #[serde(default)]
+    pub list: String,
+    #[serde(default)]
+    pub name: String,
+    /// Level the spell is cast at.
+    #[serde(default)]
+    pub level: u32,
+    #[serde(default)]
+    pub save_dc: u32,
+    #[serde(default)]
+    pub attack_bonus: i32,
+    /// Charges spent per cast.
+    #[serde(default)]
+    pub cost: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ItemCharges {
+    #[serde(default)]
+    pub used: u32,
+    #[serde(default)]
+    pub max: u32,
+    /// Charges regained at dawn, e.g. `1d6 + 1`. All of them when unset.
+    #[serde(default)]
+    pub recharge: Option<Expr<Attribute>>,
+}
+
+impl ItemCharges {
+    pub fn available(&self) -> u32 {
+        self.max.saturating_sub(self.used)
+    }
+
+    /// Regain `amount` charges, up to the maximum.
+    pub fn regain(&mut self, amount: u32) {
+        self.used = self.used.saturating_sub(amount);
+    }
+}
+
+impl Item {
+    /// Equipped and, if it requires attunement, attuned.
+    pub fn is_active(&self) -> bool {
+        self.equipped && (!self.requires_attunement || self.attuned)
+    }
+
+    /// Whether the item holds enough charges (or units, for single-use
+    /// items) to cast its spell at `index`.
+    pub fn can_cast(&self, index: usize) -> bool {
+        let Some(spell) = self.spells.get(index) else {
+            return false;
+        };
+        if self.requires_attunement && !self.attuned {
+            return false;
+        }
+        match &self.charges {
+            Some(charges) => charges.available() >= spell.cost,
+            None => self.quantity > 0,
+        }
+    }
+}
+
+impl Equipment {
+    /// Total carried weight in pounds, coins included.
+    pub fn total_weight(&self) -> f32 {
+        let weapons: f32 = self.weapons.iter().map(|weapon| weapon.weight).sum();
+        let armors: f32 = self.armors.iter().map(|armor| armor.weight).sum();
+        let items: f32 = self
+            .items
+            .iter()
+            .map(|item| item.weight * item.quantity as f32)
+            .sum();
+        weapons + armors + items + self.currency.weight()
+    }
+
+    /// Maximum number of items a character can be attuned to at once.
+    pub const MAX_ATTUNED: usize = 3;
+
+    pub fn attuned_count(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.requires_attunement && item.attuned)
+            .count()
+    }
+
+    /// Attune to or end attunement with an item. Fails (returns `false`)
+    /// when all attunement slots are taken.
+    pub fn set_attuned(&mut self, index: usize, attuned: bool) -> bool {
+        if attuned
+            && self
+                .items
+                .get(index)
+                .is_some_and(|item| item.requires_attunement && item.attuned)
+        {
+            return true;
+        }
+        if attuned && self.attuned_count() >= Self::MAX_ATTUNED {
+            return false;
+        }
+        match self.items.get_mut(index) {
+            Some(item) if item.requires_attunement => {
+                item.attuned = attuned;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Assignment expressions of all active items.
+    pub fn active_item_exprs(&self) -> impl Iterator<Item = (&str, &Expr<Attribute>)> {
+        self.items
+            .iter()
+            .filter(|item| item.is_active())
+            .filter_map(|item| Some((item.name.as_str(), item.expr.as_ref()?)))
+    }
+
+    /// Cast the spell at `spell_index` of the item at `item_index`: spends
+    /// its charges, or consumes one unit of a single-use item and removes
+    /// it once none are left. Returns `false` if the item can't cast it.
+    pub fn cast_from_item(&mut self, item_index: usize, spell_index: usize) -> bool {
+        let Some(item) = self.items.get_mut(item_index) else {
+            return false;
+        };
+        if !item.can_cast(spell_index) {
+            return false;
+        }
+        match &mut item.charges {
+            Some(charges) => charges.used += item.spells[spell_index].cost,
+            None => {
+                item.quantity -= 1;
+                if item.quantity == 0 {
+                    self.items.remove(item_index);
+                }
+            }
+        }
+        true
+    }
+
+    /// Backpack item used as ammunition by the given name (case-insensitive).
+    pub fn ammunition_mut(&mut self, name: &str) -> Option<&mut Item> {
+        self.items
+            .iter_mut()
+            .find(|item| !name.is_empty() && item.name.to_lowercase() == name.to_lowercase())
+    }
+
+    pub fn ammunition_count(&self, name: &str) -> Option<u32> {
+        self.items
+            .iter()
+            .find(|item| !name.is_empty() && item.name.to_lowercase() == name.to_lowercase())
+            .map(|item| item.quantity)
+    }
 }
 
 impl std::fmt::Display for Item {
@@ -109,6 +409,14 @@ pub struct Currency {
 }
 
 impl Currency {
+    /// Coins weigh a pound per fifty, regardless of denomination.
+    pub const COINS_PER_LB: u32 = 50;
+
+    pub fn weight(&self) -> f32 {
+        let coins = self.cp + self.sp + self.ep + self.gp + self.pp;
+        coins as f32 / Self::COINS_PER_LB as f32
+    }
+
     pub fn as_money(&self) -> Money {
         Money::from_cp(
             self.cp
@@ -1,7 +1,13 @@
+use std::collections::BTreeMap;
+
 use reactive_stores::Store;
 use serde::{Deserialize, Serialize};
 
-use crate::{model::Alignment, vecset::VecSet};
+use crate::{
+    expr,
+    model::{Alignment, Size},
+    vecset::VecSet,
+};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Store)]
 pub struct CharacterIdentity {
@@ -16,10 +22,18 @@ pub struct CharacterIdentity {
     pub alignment: Alignment,
     #[serde(default)]
     pub experience_points: u32,
+    /// Levels are granted at story milestones rather than by XP thresholds.
+    #[serde(default)]
+    pub milestone: bool,
+    /// Log of XP awards, oldest first.
+    #[serde(default)]
+    pub xp_awards: Vec<XpAward>,
     #[serde(default, alias = "race_applied")]
     pub species_applied: bool,
     #[serde(default)]
     pub background_applied: bool,
+    #[serde(default)]
+    pub size: Size,
 }
 
 impl Default for CharacterIdentity {
@@ -31,10 +45,42 @@ impl Default for CharacterIdentity {
             background: String::new(),
             alignment: Alignment::TrueNeutral,
             experience_points: 0,
+            milestone: false,
+            xp_awards: Vec::new(),
             species_applied: false,
             background_applied: false,
+            size: Size::Medium,
+        }
+    }
+}
+
+/// A single grant of experience points.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XpAward {
+    pub amount: u32,
+    #[serde(default)]
+    pub reason: String,
+    /// ISO 8601 timestamp of when the award was made.
+    #[serde(default)]
+    pub date: String,
+}
+
+impl XpAward {
+    pub fn new(amount: u32, reason: String) -> Self {
+        Self {
+            amount,
+            reason,
+            date: js_sys::Date::new_0()
+                .to_iso_string()
+                .as_string()
+                .unwrap_or_default(),
         }
     }
+
+    /// Date part of the timestamp, e.g. "2026-04-04".
+    pub fn short_date(&self) -> &str {
+        self.date.get(..10).unwrap_or(&self.date)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Store)]
@@ -55,6 +101,11 @@ pub struct ClassLevel {
     pub hit_dice_used: u32,
     #[serde(default)]
     pub applied_levels: VecSet<u32>,
+    /// Hit points gained at each level after the character's first, before
+    /// the CON modifier. Levels without an entry count as the hit die
+    /// average.
+    #[serde(default)]
+    pub hp_rolls: BTreeMap<u32, u32>,
 }
 
 impl ClassLevel {
@@ -65,6 +116,25 @@ impl ClassLevel {
     pub fn subclass_label(&self) -> Option<&str> {
         self.subclass_label.as_deref().or(self.subclass.as_deref())
     }
+
+    /// Average hit points gained per level after the character's first.
+    pub fn average_hp(&self) -> u32 {
+        expr::avg_hp(self.hit_die_sides as i32) as u32
+    }
+
+    /// Hit points gained at `level` before the CON modifier: the full hit
+    /// die at level 1 of the `starting` class (the character's first
+    /// level), otherwise the recorded roll or the average.
+    pub fn level_hp(&self, level: u32, starting: bool) -> u32 {
+        if starting && level <= 1 {
+            self.hit_die_sides
+        } else {
+            self.hp_rolls
+                .get(&level)
+                .copied()
+                .unwrap_or_else(|| self.average_hp())
+        }
+    }
 }
 
 impl std::fmt::Display for ClassLevel {
@@ -88,6 +158,7 @@ impl Default for ClassLevel {
             hit_die_sides: 8,
             hit_dice_used: 0,
             applied_levels: VecSet::new(),
+            hp_rolls: BTreeMap::new(),
         }
     }
 }
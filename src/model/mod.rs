@@ -9,6 +9,7 @@ mod equipment;
 mod feature;
 mod identity;
 mod money;
+mod roll_log;
 mod spell;
 
 pub use ability::*;
@@ -22,6 +23,7 @@ pub use equipment::*;
 pub use feature::*;
 pub use identity::*;
 pub use money::*;
+pub use roll_log::*;
 pub use spell::*;
 
 /// Format an integer as a signed bonus string (e.g. `+3`, `-1`).
@@ -32,3 +34,8 @@ pub fn format_bonus(value: i32) -> String {
         value.to_string()
     }
 }
+
+/// Format a weight in pounds, rounded to two decimals (e.g. `12.5`).
+pub fn format_weight(pounds: f32) -> String {
+    ((pounds * 100.0).round() / 100.0).to_string()
+}
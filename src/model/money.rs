@@ -3,7 +3,21 @@ use std::{
     ops::{Add, AddAssign, Sub, SubAssign},
 };
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize
+)]
+#[serde(transparent)]
 pub struct Money {
     cp: u32,
 }
@@ -45,6 +59,18 @@ impl Money {
         Some(Self::from_gp_cp(whole_gp, fraction_gp))
     }
 
+    /// Inverse of `from_gp_str`: whole gold with a two-digit copper fraction
+    /// (`"15"`, `"0.05"`). Empty for zero.
+    pub fn to_gp_str(self) -> String {
+        let gp = self.cp / Self::CP_PER_GP;
+        let cp = self.cp % Self::CP_PER_GP;
+        match (gp, cp) {
+            (0, 0) => String::new(),
+            (gp, 0) => gp.to_string(),
+            (gp, cp) => format!("{gp}.{cp:02}"),
+        }
+    }
+
     pub fn from_cp(cp: u32) -> Self {
         Self { cp }
     }
@@ -215,6 +241,16 @@ mod tests {
         assert_eq!(Money::from_gp_str("-5"), None);
     }
 
+    #[test]
+    fn to_gp_str_roundtrip() {
+        for cp in [0, 5, 10, 99, 100, 1505] {
+            let money = Money::from_cp(cp);
+            assert_eq!(Money::from_gp_str(&money.to_gp_str()), Some(money));
+        }
+        assert_eq!(Money::from_cp(1505).to_gp_str(), "15.05");
+        assert_eq!(Money::from_gp(15).to_gp_str(), "15");
+    }
+
     #[test]
     fn display_mixed() {
         assert_eq!(Money::from_cp(1234).to_string(), "1pp 2gp 3sp 4cp");
@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    expr::{DieRoll, Roll},
+    model::character::now_epoch_secs,
+};
+
+/// A roll kept for auditing after the fact: what was rolled, every die face
+/// (including dropped ones) and the total.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollRecord {
+    #[serde(default)]
+    pub timestamp: u64,
+    /// What was rolled for, e.g. the weapon name.
+    #[serde(default)]
+    pub title: String,
+    /// Kind of roll, e.g. "Attack roll" or the damage type.
+    #[serde(default)]
+    pub kind: String,
+    /// Expression text the roll was evaluated from.
+    #[serde(default)]
+    pub expr: String,
+    #[serde(default)]
+    pub dice: Vec<DieRoll>,
+    pub total: i32,
+    #[serde(default)]
+    pub crit: bool,
+}
+
+impl RollRecord {
+    pub fn new(title: String, kind: String, expr: String, roll: Roll, crit: bool) -> Self {
+        Self {
+            timestamp: now_epoch_secs(),
+            title,
+            kind,
+            expr,
+            dice: roll.dice,
+            total: roll.total,
+            crit,
+        }
+    }
+}
+
+/// Per-character roll history, newest first, capped at [`RollLog::LIMIT`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RollLog(VecDeque<RollRecord>);
+
+impl RollLog {
+    pub const LIMIT: usize = 100;
+
+    pub fn push(&mut self, record: RollRecord) {
+        self.0.push_front(record);
+        self.0.truncate(Self::LIMIT);
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RollRecord> {
+        self.0.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    fn record(total: i32) -> RollRecord {
+        RollRecord {
+            timestamp: 0,
+            title: String::new(),
+            kind: String::new(),
+            expr: "1d20".into(),
+            dice: Vec::new(),
+            total,
+            crit: false,
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn push_keeps_newest_first_and_caps() {
+        let mut log = RollLog::default();
+        for total in 0..(RollLog::LIMIT as i32 + 5) {
+            log.push(record(total));
+        }
+        assert_eq!(log.len(), RollLog::LIMIT);
+        assert_eq!(
+            log.iter().next().map(|r| r.total),
+            Some(RollLog::LIMIT as i32 + 4)
+        );
+        assert_eq!(log.iter().last().map(|r| r.total), Some(5));
+    }
+}
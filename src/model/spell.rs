@@ -1,7 +1,7 @@
 use reactive_stores::Store;
 use serde::{Deserialize, Serialize};
 
-use crate::model::{Ability, SpellSlotPool};
+use crate::model::{Ability, Money, SpellSlotPool};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Store)]
 pub struct SpellData {
@@ -14,6 +14,15 @@ pub struct SpellData {
     pub spells: Vec<Spell>,
     #[serde(default)]
     pub known: Option<Vec<Spell>>,
+    /// How many leveled spells can be prepared at once, from the class level
+    /// table; `None` for casters who don't prepare spells. The leveled spells
+    /// in `spells` are the prepared ones.
+    #[serde(default)]
+    pub prepare_limit: Option<u32>,
+    /// Whether preparations can currently be changed. A long rest opens
+    /// them, finishing preparation closes them again.
+    #[serde(default)]
+    pub preparing: bool,
 }
 
 impl SpellData {
@@ -28,6 +37,55 @@ impl SpellData {
     pub fn is_two_tier(&self) -> bool {
         self.known.is_some()
     }
+
+    pub fn prepares(&self) -> bool {
+        self.prepare_limit.is_some()
+    }
+
+    /// Leveled spells chosen in `spells`, not counting always-prepared ones.
+    pub fn prepared_count(&self) -> u32 {
+        self.spells.iter().filter(|s| s.is_prepared()).count() as u32
+    }
+
+    /// Whether another leveled spell can be prepared now: only while
+    /// preparing, and not past the preparation limit.
+    pub fn can_prepare_more(&self) -> bool {
+        match self.prepare_limit {
+            Some(limit) => self.preparing && self.prepared_count() < limit,
+            None => true,
+        }
+    }
+
+    /// Apply `f` to the spell at `index`, unless that would prepare another
+    /// spell when [`can_prepare_more`](Self::can_prepare_more) says no.
+    /// Returns whether the change was made.
+    pub fn update_spell(&mut self, index: usize, f: impl FnOnce(&mut Spell)) -> bool {
+        let Some(spell) = self.spells.get(index) else {
+            return false;
+        };
+        let mut updated = spell.clone();
+        f(&mut updated);
+        if updated.is_prepared() && !spell.is_prepared() && !self.can_prepare_more() {
+            return false;
+        }
+        self.spells[index] = updated;
+        true
+    }
+
+    /// Whether `spell` in `spells` can be swapped or removed now: a preparing
+    /// caster changes leveled spells only after a long rest.
+    pub fn can_change(&self, spell: &Spell) -> bool {
+        !spell.sticky && (!self.prepares() || self.preparing || spell.level == 0)
+    }
+
+    /// Set the preparation limit. A caster who gains one for the first time
+    /// may adjust their preparations right away.
+    pub fn set_prepare_limit(&mut self, limit: Option<u32>) {
+        if self.prepare_limit.is_none() && limit.is_some() {
+            self.preparing = true;
+        }
+        self.prepare_limit = limit;
+    }
 }
 
 impl Default for SpellData {
@@ -38,6 +96,8 @@ impl Default for SpellData {
             pool: SpellSlotPool::default(),
             spells: Vec::new(),
             known: None,
+            prepare_limit: None,
+            preparing: false,
         }
     }
 }
@@ -98,6 +158,9 @@ pub struct Spell {
     pub cost: u32,
     #[serde(default)]
     pub free_uses: Option<FreeUses>,
+    /// Set on spellbook entries copied from a scroll or another book.
+    #[serde(default)]
+    pub copied: Option<SpellCopy>,
 }
 
 impl Spell {
@@ -108,4 +171,29 @@ impl Spell {
     pub fn set_label(&mut self, value: String) {
         self.label = Some(value);
     }
+
+    /// Counts against the preparation limit: a named leveled spell that
+    /// isn't always prepared.
+    fn is_prepared(&self) -> bool {
+        self.level > 0 && !self.sticky && !self.name.is_empty()
+    }
+}
+
+/// Gold and time spent copying a spell into a spellbook.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpellCopy {
+    pub cost: Money,
+    pub hours: u32,
+}
+
+impl SpellCopy {
+    pub const GP_PER_LEVEL: u32 = 50;
+    pub const HOURS_PER_LEVEL: u32 = 2;
+
+    pub fn for_level(level: u32) -> Self {
+        Self {
+            cost: Money::from_gp(Self::GP_PER_LEVEL * level),
+            hours: Self::HOURS_PER_LEVEL * level,
+        }
+    }
 }
@@ -0,0 +1,259 @@
+use leptos::{either::Either, prelude::*};
+use leptos_fluent::move_tr;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use wasm_bindgen_futures::spawn_local;
+
+use crate::{
+    BASE_URL,
+    components::icon::Icon,
+    firebase,
+    model::Translatable,
+    rules::RulesRegistry,
+    storage::campaign::{self, PartyStatus, PartySubscription},
+};
+
+/// Campaigns created on this device, with a form to start a new one.
+#[component]
+pub fn CampaignList() -> impl IntoView {
+    let campaigns = RwSignal::new(campaign::load_campaigns());
+    let name = RwSignal::new(String::new());
+    let creating = RwSignal::new(false);
+    let error = RwSignal::new(Option::<String>::None);
+
+    let on_create = move |event: web_sys::SubmitEvent| {
+        event.prevent_default();
+        let campaign_name = name.get_untracked().trim().to_string();
+        if campaign_name.is_empty() || creating.get_untracked() {
+            return;
+        }
+        creating.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match campaign::create_campaign(campaign_name).await {
+                Ok(_) => {
+                    name.set(String::new());
+                    campaigns.set(campaign::load_campaigns());
+                }
+                Err(create_error) => {
+                    log::warn!("Failed to create campaign: {create_error}");
+                    error.set(Some(create_error.to_string()));
+                }
+            }
+            creating.set(false);
+        });
+    };
+
+    let on_forget = move |code: &str| {
+        campaign::forget_campaign(code);
+        campaigns.set(campaign::load_campaigns());
+    };
+
+    view! {
+        <div class="panel campaign-list">
+            <h2>{move_tr!("campaigns-title")}</h2>
+            <p class="campaign-hint">{move_tr!("campaigns-hint")}</p>
+
+            <form class="campaign-create" on:submit=on_create>
+                <input
+                    type="text"
+                    placeholder=move_tr!("campaign-name")
+                    prop:value=move || name.get()
+                    on:input=move |event| name.set(event_target_value(&event))
+                />
+                <button type="submit" class="btn-primary" disabled=move || creating.get()>
+                    {move_tr!("campaign-create")}
+                </button>
+            </form>
+            {move || error.get().map(|message| view! { <p class="campaign-error">{message}</p> })}
+
+            <ul class="campaign-entries">
+                <For
+                    each=move || campaigns.get()
+                    key=|campaign| campaign.code.clone()
+                    let:campaign
+                >
+                    {
+                        let code = campaign.code.clone();
+                        view! {
+                            <li class="campaign-entry">
+                                <A href=format!("{BASE_URL}/campaigns/{}", campaign.code)>
+                                    {campaign.name.clone()}
+                                </A>
+                                <span class="campaign-code" title=move_tr!("campaign-invite-code")>
+                                    {campaign.code.clone()}
+                                </span>
+                                <button
+                                    class="btn-primary btn-danger"
+                                    title=move_tr!("campaign-forget")
+                                    on:click=move |_| on_forget(&code)
+                                >
+                                    <Icon name="trash-2" size=14 />
+                                </button>
+                            </li>
+                        }
+                    }
+                </For>
+            </ul>
+        </div>
+    }
+}
+
+#[derive(Params, Clone, Debug, PartialEq, Eq)]
+struct CampaignParams {
+    code: String,
+}
+
+/// Live view of every character in a campaign for its GM.
+#[component]
+pub fn CampaignDashboard() -> impl IntoView {
+    let code = use_params::<CampaignParams>()
+        .get_untracked()
+        .map(|params| campaign::normalize_code(&params.code))
+        .unwrap_or_default();
+
+    let party = RwSignal::new(Vec::<(uuid::Uuid, PartyStatus)>::new());
+    let subscription = StoredValue::new_local(Option::<PartySubscription>::None);
+    let error = RwSignal::new(Option::<String>::None);
+
+    let campaign = LocalResource::new({
+        let code = code.clone();
+        move || {
+            let code = code.clone();
+            async move {
+                if !firebase::wait_ready().await || firebase::wait_for_auth().await.is_none() {
+                    return None;
+                }
+                let found = campaign::load_campaign(&code)
+                    .await
+                    .inspect_err(|load_error| log::warn!("Failed to load campaign: {load_error}"))
+                    .ok()
+                    .flatten()?;
+                match campaign::subscribe_party(&code, party) {
+                    Ok(party_subscription) => {
+                        subscription.try_set_value(Some(party_subscription));
+                    }
+                    Err(subscribe_error) => error.set(Some(subscribe_error.to_string())),
+                }
+                Some(found)
+            }
+        }
+    });
+
+    let title = move || {
+        campaign
+            .get()
+            .flatten()
+            .map(|campaign| campaign.name)
+            .unwrap_or_default()
+    };
+
+    view! {
+        <div class="campaign-dashboard">
+            <div class="campaign-dashboard-header">
+                <A href=format!("{BASE_URL}/campaigns") attr:class="btn-cancel">
+                    <Icon name="arrow-left" size=14 />
+                </A>
+                <h2>{title}</h2>
+                <span class="campaign-code" title=move_tr!("campaign-invite-code")>
+                    {code}
+                </span>
+            </div>
+            {move || error.get().map(|message| view! { <p class="campaign-error">{message}</p> })}
+            <Suspense fallback=move || view! { <p>{move_tr!("share-loading")}</p> }>
+                {move || campaign.get().map(|found| match found {
+                    None => Either::Left(view! {
+                        <p class="campaign-error">{move_tr!("campaign-not-found")}</p>
+                    }),
+                    Some(_) => Either::Right(view! {
+                        <Show
+                            when=move || party.with(|party| !party.is_empty())
+                            fallback=|| view! {
+                                <p class="campaign-hint">{move_tr!("campaign-no-members")}</p>
+                            }
+                        >
+                            <div class="party-grid">
+                                {move || party
+                                    .get()
+                                    .into_iter()
+                                    .map(|(_, status)| view! { <PartyCard status /> })
+                                    .collect_view()}
+                            </div>
+                        </Show>
+                    }),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn PartyCard(status: PartyStatus) -> impl IntoView {
+    let i18n = expect_context::<leptos_fluent::I18n>();
+    let registry = expect_context::<RulesRegistry>();
+
+    let hp = if status.hp_temp > 0 {
+        format!("{}/{} (+{})", status.hp, status.hp_max, status.hp_temp)
+    } else {
+        format!("{}/{}", status.hp, status.hp_max)
+    };
+    let slots = status
+        .spell_slots
+        .iter()
+        .map(|slots| {
+            let pool = untrack(|| i18n.tr(slots.pool.tr_key()));
+            let available = slots.total.saturating_sub(slots.used);
+            view! {
+                <span class="party-slot" title=pool>
+                    {format!("{}: {available}/{}", slots.level, slots.total)}
+                </span>
+            }
+        })
+        .collect_view();
+    let conditions = status.conditions.clone();
+    let conditions = move || {
+        registry.with_conditions_index(|index| {
+            conditions
+                .iter()
+                .map(|condition| {
+                    let label = index
+                        .get(condition.name.as_str())
+                        .map_or(condition.name.as_str(), |definition| definition.label());
+                    let text = if condition.level > 1 {
+                        format!("{label} {}", condition.level)
+                    } else {
+                        label.to_string()
+                    };
+                    view! { <span class="party-tag party-condition">{text}</span> }
+                })
+                .collect_view()
+        })
+    };
+    let effects = status
+        .effects
+        .into_iter()
+        .map(|effect| view! { <span class="party-tag">{effect}</span> })
+        .collect_view();
+
+    view! {
+        <div class="panel party-card">
+            <h3>{status.name}</h3>
+            <div class="party-class">{status.class_summary}</div>
+            <div class="party-stats">
+                <span class="party-stat">
+                    <span class="party-stat-label">{move_tr!("armor-class")}</span>
+                    <span class="party-stat-value">{status.ac}</span>
+                </span>
+                <span class="party-stat">
+                    <span class="party-stat-label">{move_tr!("current-hp")}</span>
+                    <span class="party-stat-value">{hp}</span>
+                </span>
+                <span class="party-stat">
+                    <span class="party-stat-label">{move_tr!("passive-perception")}</span>
+                    <span class="party-stat-value">{status.passive_perception}</span>
+                </span>
+            </div>
+            <div class="party-slots">{slots}</div>
+            <div class="party-tags">{conditions} {effects}</div>
+        </div>
+    }
+}
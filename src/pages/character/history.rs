@@ -0,0 +1,170 @@
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_router::{components::A, hooks::use_navigate};
+use reactive_stores::Store;
+use uuid::Uuid;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    BASE_URL,
+    components::{
+        diff_table::{DiffTable, group_diff_rows},
+        icon::Icon,
+    },
+    model::Character,
+    storage::history::{self, Snapshot, SnapshotReason},
+};
+
+fn format_taken_at(taken_at: u64) -> String {
+    js_sys::Date::new(&JsValue::from_f64(taken_at as f64 * 1000.0))
+        .to_locale_string("default", &JsValue::UNDEFINED)
+        .into()
+}
+
+/// Snapshots of the open character: save named checkpoints, compare any
+/// snapshot with the current state and restore it.
+#[component]
+pub fn CharacterHistory() -> impl IntoView {
+    let store = expect_context::<Store<Character>>();
+    let char_id = store.read_untracked().id;
+
+    let snapshots = RwSignal::new(history::load_history(&char_id));
+    let reload = move || snapshots.set(history::load_history(&char_id));
+    let compared = RwSignal::new(Option::<Uuid>::None);
+    let checkpoint_name = RwSignal::new(String::new());
+
+    let on_checkpoint = move |event: web_sys::SubmitEvent| {
+        event.prevent_default();
+        let name = checkpoint_name.get_untracked().trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        history::save_checkpoint(&store.read_untracked(), name);
+        checkpoint_name.set(String::new());
+        reload();
+    };
+
+    let restore = move |snapshot: &Snapshot| {
+        let Some(mut restored) = snapshot.character() else {
+            return;
+        };
+        let msg = tr!("history-confirm-restore");
+        if !window().confirm_with_message(&msg).unwrap_or(false) {
+            return;
+        }
+        history::snapshot_character(&store.read_untracked(), SnapshotReason::Restore);
+        restored.id = char_id;
+        store.set(restored);
+        let navigate = use_navigate();
+        navigate(&format!("/c/{char_id}"), Default::default());
+    };
+
+    let delete = move |snapshot_id: Uuid| {
+        history::delete_snapshot(&char_id, &snapshot_id);
+        if compared.get_untracked() == Some(snapshot_id) {
+            compared.set(None);
+        }
+        reload();
+    };
+
+    let i18n = expect_context::<leptos_fluent::I18n>();
+    let diff_view = move || {
+        let snapshot_id = compared.get()?;
+        let snapshot = snapshots.with(|list| {
+            list.iter()
+                .find(|snapshot| snapshot.id == snapshot_id)
+                .and_then(Snapshot::character)
+        })?;
+        let sections = untrack(|| group_diff_rows(store.read().diff(&snapshot, i18n)));
+        Some(view! { <DiffTable sections imported_label=move_tr!("diff-snapshot") /> })
+    };
+
+    view! {
+        <div class="panel character-history">
+            <div class="character-history-header">
+                <A href=format!("{BASE_URL}/c/{char_id}") attr:class="btn-cancel">
+                    <Icon name="arrow-left" size=14 />
+                </A>
+                <h2>{move_tr!("history-title")}</h2>
+            </div>
+
+            <form class="history-checkpoint" on:submit=on_checkpoint>
+                <input
+                    type="text"
+                    placeholder=move_tr!("history-checkpoint-name")
+                    prop:value=move || checkpoint_name.get()
+                    on:input=move |event| checkpoint_name.set(event_target_value(&event))
+                />
+                <button type="submit" class="btn-primary">
+                    {move_tr!("history-save-checkpoint")}
+                </button>
+            </form>
+
+            <Show
+                when=move || snapshots.with(|list| !list.is_empty())
+                fallback=|| view! { <p class="history-empty">{move_tr!("history-empty")}</p> }
+            >
+                <ul class="history-list">
+                    <For
+                        each=move || snapshots.get()
+                        key=|snapshot| snapshot.id
+                        let:snapshot
+                    >
+                        {
+                            let id = snapshot.id;
+                            let label = if snapshot.is_checkpoint() {
+                                snapshot.name.clone()
+                            } else {
+                                untrack(|| i18n.tr(snapshot.reason.tr_key()))
+                            };
+                            let readable = snapshot.character().is_some();
+                            let snapshot = StoredValue::new(snapshot);
+                            view! {
+                                <li
+                                    class="history-entry"
+                                    class:checkpoint=move || snapshot.read_value().is_checkpoint()
+                                    class:selected=move || compared.get() == Some(id)
+                                >
+                                    <span class="history-date">
+                                        {format_taken_at(snapshot.read_value().taken_at)}
+                                    </span>
+                                    <span class="history-label">{label}</span>
+                                    <span class="history-actions">
+                                        <button
+                                            class="btn-primary"
+                                            disabled=!readable
+                                            title=move_tr!("history-compare")
+                                            on:click=move |_| {
+                                                compared.update(|current| {
+                                                    *current = (*current != Some(id)).then_some(id);
+                                                });
+                                            }
+                                        >
+                                            {move_tr!("history-compare")}
+                                        </button>
+                                        <button
+                                            class="btn-primary"
+                                            disabled=!readable
+                                            on:click=move |_| restore(&snapshot.read_value())
+                                        >
+                                            {move_tr!("history-restore-action")}
+                                        </button>
+                                        <button
+                                            class="btn-primary btn-danger"
+                                            title=move_tr!("history-delete")
+                                            on:click=move |_| delete(id)
+                                        >
+                                            <Icon name="trash-2" size=14 />
+                                        </button>
+                                    </span>
+                                </li>
+                            }
+                        }
+                    </For>
+                </ul>
+            </Show>
+
+            {diff_view}
+        </div>
+    }
+}
@@ -87,6 +87,14 @@ fn CharacterInner(char_data: Character) -> impl IntoView {
     Effect::new(move || {
         let eff = effects.read();
         storage::save_effects(&char_id, &eff);
+        storage::sync_effects(&store.read_untracked());
+    });
+
+    // Per-character roll history, persisted locally next to the effects.
+    let rolls = RwSignal::new(storage::load_rolls(&char_id));
+    provide_context(rolls);
+    Effect::new(move || {
+        storage::save_rolls(&char_id, &rolls.read());
     });
 
     // Auto-save + cloud sync pull (touch gated on initial sync).
@@ -113,6 +121,12 @@ fn CharacterInner(char_data: Character) -> impl IntoView {
     // overwrites labels/descriptions, so when caches update with new locale
     // data, fill re-runs and applies the new labels directly.
 
+    let missing_packs = Memo::new(move |_| store.with(|c| registry.missing_packs(c)));
+    let sync_conflict = Memo::new(move |_| {
+        storage::sync_index_version().track();
+        storage::has_sync_conflict(&char_id)
+    });
+
     let name = Memo::new(move |_| store.identity().name().get());
     let class_summary = Memo::new(move |_| store.read().class_summary());
     let title = move || {
@@ -129,6 +143,27 @@ fn CharacterInner(char_data: Character) -> impl IntoView {
     view! {
         <Title text=title />
         <div class="character-editor">
+            {move || {
+                let missing = missing_packs.get();
+                (!missing.is_empty()).then(|| view! {
+                    <div class="missing-packs-warning">
+                        {move_tr!("missing-packs", { "packs" => missing.join(", ") })}
+                        " "
+                        <A href=format!("{BASE_URL}/r/packs")>{move_tr!("ref-packs")}</A>
+                    </div>
+                })
+            }}
+            {move || {
+                sync_conflict.get().then(|| view! {
+                    <div class="sync-conflict-warning">
+                        {move_tr!("sync-conflict-banner")}
+                        " "
+                        <A href=format!("{BASE_URL}/sync/{char_id}")>
+                            {move_tr!("sync-conflict-resolve")}
+                        </A>
+                    </div>
+                })
+            }}
             <Outlet />
             <ArgsModal />
         </div>
@@ -1,4 +1,5 @@
 pub mod editor;
+pub mod history;
 pub mod layout;
 pub mod list;
 pub mod quick_start;
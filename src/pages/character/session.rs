@@ -6,14 +6,15 @@ use crate::{
     components::{
         icon::Icon,
         session::{
-            BackpackBlock, ChoicesBlock, DamageModifiersBlock, EffectsBlock, LanguagesBlock,
-            ResourcesBlock, SpellsBlock, StatsBlock, WeaponsBlock,
+            BackpackBlock, ChoicesBlock, ConditionsBlock, DamageModifiersBlock, EffectsBlock,
+            LanguagesBlock, ResourcesBlock, RollLogBlock, SpellsBlock, StatsBlock, WeaponsBlock,
         },
         session_header::SessionHeader,
         session_nav::SessionNav,
     },
     model::Character,
     rules::RulesRegistry,
+    storage::history::{self, SnapshotReason},
 };
 
 #[component]
@@ -36,7 +37,10 @@ pub fn CharacterSession() -> impl IntoView {
                         <Icon name="coffee" size=14 />
                     </button>
                     <button class="session-rest-btn" title=move_tr!("long-rest")
-                        on:click=move |_| store.update(|ch| registry.long_rest(ch))
+                        on:click=move |_| store.update(|ch| {
+                            history::snapshot_character(ch, SnapshotReason::LongRest);
+                            registry.long_rest(ch);
+                        })
                     >
                         <Icon name="moon" size=14 />
                     </button>
@@ -48,11 +52,13 @@ pub fn CharacterSession() -> impl IntoView {
                 <DamageModifiersBlock />
             </div>
 
-            // === Right column: Effects + Stats + Resources ===
+            // === Right column: Effects + Conditions + Stats + Resources ===
             <div class="session-right-column">
                 <EffectsBlock />
+                <ConditionsBlock />
                 <ResourcesBlock />
                 <StatsBlock />
+                <RollLogBlock />
             </div>
             </div>
 
@@ -349,14 +349,7 @@ fn ViewStoryView(char_id: Uuid, story_id: Uuid, stories: RwSignal<Vec<Story>>) -
 
             let on_delete = move |_| {
                 stories.update(|list| list.retain(|story| story.id != story_id));
-                storage::save_stories(&char_id, &stories.get_untracked());
-                if let Some(uid) = crate::firebase::current_uid() {
-                    storage::queue::push(storage::queue::CloudOp::DeleteStory {
-                        uid,
-                        char_id,
-                        story_id,
-                    });
-                }
+                storage::delete_story(&char_id, &story_id);
                 navigate(&format!("{BASE_URL}/c/{char_id}/story"), Default::default());
             };
 
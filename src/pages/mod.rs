@@ -1,4 +1,6 @@
+pub mod campaign;
 pub mod character;
 pub mod import_character;
 pub mod not_found;
 pub mod reference;
+pub mod spectate;
@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use leptos::{leptos_dom::helpers::debounce, prelude::*};
+use leptos_fluent::move_tr;
+use leptos_meta::Title;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use regex::RegexBuilder;
+use strum::IntoEnumIterator as _;
+
+use super::ReferenceSidebar;
+use crate::{
+    BASE_URL,
+    hooks::use_query_signal,
+    model::{Armor, Translatable, format_weight},
+    rules::{EquipmentDefinition, EquipmentKind, RulesRegistry},
+};
+
+#[derive(Params, Clone, Debug, PartialEq, Eq)]
+struct EquipmentRefParams {
+    kind: Option<String>,
+}
+
+/// One-line summary of an entry's mechanics: category and properties for
+/// weapons, armor type and formula for armor.
+fn equipment_summary(entry: &EquipmentDefinition, i18n: leptos_fluent::I18n) -> String {
+    let mut parts = Vec::new();
+    match entry.kind() {
+        EquipmentKind::Weapon => {
+            if let Some(category) = entry.category {
+                parts.push(i18n.tr(category.tr_key()));
+            }
+            if let Some(damage) = &entry.damage {
+                let damage_type = entry
+                    .damage_type
+                    .map(|dt| format!(" {}", i18n.tr(dt.tr_key())))
+                    .unwrap_or_default();
+                parts.push(format!("{damage}{damage_type}"));
+            }
+            if let Some(versatile) = &entry.versatile {
+                parts.push(format!(
+                    "{}: {versatile}",
+                    i18n.tr("weapon-versatile-damage")
+                ));
+            }
+            parts.extend(
+                entry
+                    .properties
+                    .iter()
+                    .map(|property| i18n.tr(property.tr_key())),
+            );
+        }
+        EquipmentKind::Armor => {
+            if let Some(armor_type) = entry.armor_type {
+                parts.push(i18n.tr(armor_type.tr_key()));
+                parts.push(format!(
+                    "{}: {}",
+                    i18n.tr("armor-class"),
+                    Armor::default_ac_expr_str(armor_type, entry.base_ac)
+                ));
+            }
+        }
+        EquipmentKind::Gear => {}
+    }
+    if entry.weight > 0.0 {
+        parts.push(format!("{} lb", format_weight(entry.weight)));
+    }
+    let cost = entry.cost();
+    if cost.whole_cp() > 0 {
+        parts.push(cost.to_string());
+    }
+    parts.join(" · ")
+}
+
+#[component]
+pub fn EquipmentReference() -> impl IntoView {
+    let registry = expect_context::<RulesRegistry>();
+    let i18n = expect_context::<leptos_fluent::I18n>();
+    let params = use_params::<EquipmentRefParams>();
+    let (search, set_search) = use_query_signal::<String>("q");
+    let mut on_search = debounce(Duration::from_millis(300), move |value: String| {
+        set_search.set(if value.is_empty() { None } else { Some(value) });
+    });
+
+    let kind = move || {
+        params
+            .get()
+            .ok()
+            .and_then(|p| p.kind)
+            .and_then(|name| name.parse::<EquipmentKind>().ok())
+    };
+
+    let current_label = Signal::derive(move || {
+        kind()
+            .map(|kind| i18n.tr(kind.tr_key()))
+            .unwrap_or_else(|| i18n.tr("ref-equipment-all"))
+    });
+
+    let equipment_view = move || {
+        let kind = kind();
+        let query = search.read().clone().unwrap_or_default();
+        let regex = if query.is_empty() {
+            None
+        } else {
+            RegexBuilder::new(&query)
+                .case_insensitive(true)
+                .build()
+                .or_else(|_| {
+                    RegexBuilder::new(&regex::escape(&query))
+                        .case_insensitive(true)
+                        .build()
+                })
+                .ok()
+        };
+
+        registry.with_equipment_index(|idx| {
+            let entries: Vec<_> = idx
+                .values()
+                .filter(|entry| kind.is_none_or(|kind| entry.kind() == kind))
+                .filter(|entry| match &regex {
+                    Some(regex) => {
+                        regex.is_match(entry.label()) || regex.is_match(&entry.description)
+                    }
+                    None => true,
+                })
+                .map(|entry| {
+                    (
+                        entry.label().to_string(),
+                        equipment_summary(entry, i18n),
+                        entry.description.clone(),
+                    )
+                })
+                .collect();
+            if entries.is_empty() {
+                return None;
+            }
+            Some(view! {
+                <div class="reference-features">
+                    {entries
+                        .into_iter()
+                        .map(|(label, summary, description)| view! {
+                            <div class="reference-feature">
+                                <h3>{label}</h3>
+                                <p class="feature-prerequisites">{summary}</p>
+                                {(!description.is_empty()).then(|| view! { <p>{description}</p> })}
+                            </div>
+                        })
+                        .collect_view()}
+                </div>
+            })
+        })
+    };
+
+    view! {
+        <Title text=Signal::derive(move || i18n.tr("ref-equipment")) />
+        <div class="reference-page">
+            <div class="reference-layout">
+                <ReferenceSidebar current_label>
+                    {move || {
+                        let all_label = i18n.tr("ref-equipment-all");
+                        let kinds = EquipmentKind::iter().map(|kind| {
+                            let name = kind.to_string();
+                            let label = i18n.tr(kind.tr_key());
+                            view! {
+                                <A href=format!("{BASE_URL}/r/equipment/{name}") attr:class="reference-nav-item">
+                                    {label}
+                                </A>
+                            }
+                        }).collect_view();
+                        view! {
+                            <A href=format!("{BASE_URL}/r/equipment") attr:class="reference-nav-item" exact=true>
+                                {all_label}
+                            </A>
+                            {kinds}
+                        }
+                    }}
+                </ReferenceSidebar>
+                <div class="reference-feature-page">
+                    <div class="reference-feature-header">
+                        <input
+                            type="search"
+                            class="reference-search"
+                            placeholder=move_tr!("ref-search-equipment")
+                            prop:value=move || search.get().unwrap_or_default()
+                            on:input=move |event| on_search(event_target_value(&event))
+                        />
+                    </div>
+                    <main class="reference-main">
+                        {equipment_view}
+                    </main>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -1,6 +1,8 @@
 pub mod background;
 pub mod class;
+pub mod equipment;
 pub mod feature;
+pub mod packs;
 pub mod sidebar;
 pub mod species;
 pub mod spell;
@@ -139,6 +141,7 @@ pub fn feature_choices(
 /// Stack entry: display string, optional numeric value, and optional
 /// "compound base" variable (tracks `X` in `X + expr` for compound
 /// assignment detection).
+#[derive(Clone)]
 struct SumEntry {
     text: String,
     num: Option<i32>,
@@ -170,6 +173,8 @@ impl SumEntry {
 
 struct AssignmentSummarizer<'a> {
     stack: Vec<SumEntry>,
+    /// Values of `let` bindings, indexed by slot.
+    locals: Vec<SumEntry>,
     i18n: &'a leptos_fluent::I18n,
     registry: RulesRegistry,
     abilities: Vec<String>,
@@ -184,6 +189,7 @@ impl<'a> AssignmentSummarizer<'a> {
     fn new(i18n: &'a leptos_fluent::I18n, registry: RulesRegistry) -> Self {
         Self {
             stack: Vec::new(),
+            locals: Vec::new(),
             i18n,
             registry,
             abilities: Vec::new(),
@@ -378,6 +384,23 @@ impl Interpreter<Attribute, i32> for AssignmentSummarizer<'_> {
                     return Ok(Some(idx));
                 }
             }
+            // Bindings are inlined: a local shows as the value it holds
+            Op::Store(slot) => {
+                let value = self.pop();
+                let idx = slot as usize;
+                if self.locals.len() <= idx {
+                    self.locals.resize(idx + 1, SumEntry::constant(0));
+                }
+                self.locals[idx] = value;
+            }
+            Op::Load(slot) => {
+                let value = self
+                    .locals
+                    .get(slot as usize)
+                    .cloned()
+                    .unwrap_or(SumEntry::constant(0));
+                self.stack.push(value);
+            }
             // Dice/roll ops: push a placeholder
             Op::Roll | Op::Sum | Op::Explode => {
                 self.pop();
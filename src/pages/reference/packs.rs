@@ -0,0 +1,130 @@
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_meta::Title;
+
+use crate::{
+    rules::{ContentPack, RulesRegistry},
+    storage,
+};
+
+/// Counts of what a pack provides, e.g. "2 classes · 14 features".
+fn pack_summary(pack: &ContentPack) -> String {
+    let spells: usize = pack.spells.values().map(|list| list.len()).sum();
+    let counts = [
+        (
+            pack.classes.len(),
+            tr!("pack-count-classes", { "count" => pack.classes.len() }),
+        ),
+        (
+            pack.species.len(),
+            tr!("pack-count-species", { "count" => pack.species.len() }),
+        ),
+        (
+            pack.backgrounds.len(),
+            tr!("pack-count-backgrounds", { "count" => pack.backgrounds.len() }),
+        ),
+        (
+            pack.features.len(),
+            tr!("pack-count-features", { "count" => pack.features.len() }),
+        ),
+        (spells, tr!("pack-count-spells", { "count" => spells })),
+        (
+            pack.effects.len(),
+            tr!("pack-count-effects", { "count" => pack.effects.len() }),
+        ),
+    ];
+    counts
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(_, label)| label)
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+#[component]
+pub fn PacksReference() -> impl IntoView {
+    let registry = expect_context::<RulesRegistry>();
+    let i18n = expect_context::<leptos_fluent::I18n>();
+    let error = RwSignal::new(None::<String>);
+
+    let import_pack = move |_| {
+        storage::pick_json_file(move |text| match ContentPack::parse(&text) {
+            Ok(pack) => {
+                let conflicts = registry.pack_conflicts(&pack);
+                if conflicts.is_empty() {
+                    storage::save_and_sync_pack(&pack.id, &text);
+                    error.set(None);
+                } else {
+                    let names = conflicts.join(", ");
+                    error.set(Some(tr!("pack-conflicts", { "names" => names })));
+                }
+            }
+            Err(message) => {
+                error.set(Some(tr!("pack-invalid", { "error" => message })));
+            }
+        });
+    };
+
+    let packs_view = move || {
+        registry.with_packs(|packs| {
+            if packs.is_empty() {
+                return view! {
+                    <div class="reference-empty">
+                        <p>{move_tr!("packs-empty")}</p>
+                    </div>
+                }
+                .into_any();
+            }
+            packs
+                .iter()
+                .map(|pack| {
+                    let id = pack.id.clone();
+                    let heading = if pack.version.is_empty() {
+                        pack.name.clone()
+                    } else {
+                        format!("{} {}", pack.name, pack.version)
+                    };
+                    let summary = format!("{} · {}", pack.id, pack_summary(pack));
+                    let description = pack.description.clone();
+                    view! {
+                        <div class="reference-feature pack-entry">
+                            <div class="pack-entry-header">
+                                <h3>{heading}</h3>
+                                <button
+                                    class="btn-danger"
+                                    on:click=move |_| {
+                                        let msg = tr!("confirm-remove-pack");
+                                        if window().confirm_with_message(&msg).unwrap_or(false) {
+                                            storage::delete_pack(&id);
+                                        }
+                                    }
+                                >
+                                    {move_tr!("btn-delete")}
+                                </button>
+                            </div>
+                            <p class="feature-prerequisites">{summary}</p>
+                            {(!description.is_empty()).then(|| view! { <p>{description}</p> })}
+                        </div>
+                    }
+                })
+                .collect_view()
+                .into_any()
+        })
+    };
+
+    view! {
+        <Title text=Signal::derive(move || i18n.tr("ref-packs")) />
+        <div class="reference-page packs-page">
+            <div class="packs-header">
+                <p>{move_tr!("packs-hint")}</p>
+                <button class="btn-primary" on:click=import_pack>
+                    {move_tr!("btn-import-pack")}
+                </button>
+            </div>
+            {move || error.get().map(|message| view! { <p class="packs-error">{message}</p> })}
+            <main class="reference-main">
+                <div class="reference-features">{packs_view}</div>
+            </main>
+        </div>
+    }
+}
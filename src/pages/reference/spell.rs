@@ -1,19 +1,19 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
 
 use leptos::prelude::*;
 use leptos_fluent::move_tr;
 use leptos_meta::Title;
 use leptos_router::{components::A, hooks::use_params, params::Params};
+use strum::IntoEnumIterator;
 use wasm_bindgen::JsCast;
 
 use crate::{
     BASE_URL,
     components::{expr_view::ExprView, spinner::Spinner},
-    expr::Expr,
-    hooks::use_hash_href,
-    model::Attribute,
+    hooks::{use_hash_href, use_query_signal},
+    model::{CastingTime, Money, SpellComponent, SpellRange, SpellSchool, Translatable},
     pages::reference::ReferenceSidebar,
-    rules::{RulesRegistry, SpellList},
+    rules::{RulesRegistry, SpellDefinition, SpellList},
 };
 
 #[derive(Params, Clone, Debug, PartialEq, Eq)]
@@ -21,6 +21,138 @@ struct SpellRefParams {
     list: Option<String>,
 }
 
+/// Facets selected in the spell reference, read from the page query.
+#[derive(Clone, PartialEq, Default)]
+struct SpellFilter {
+    school: Option<SpellSchool>,
+    casting_time: Option<CastingTime>,
+    range: Option<SpellRange>,
+    /// Components the caster can't provide; spells needing any are hidden.
+    without: Vec<SpellComponent>,
+    concentration: Option<bool>,
+    ritual: Option<bool>,
+}
+
+impl SpellFilter {
+    fn matches(&self, spell: &SpellDefinition) -> bool {
+        self.school
+            .is_none_or(|school| spell.school == Some(school))
+            && self
+                .casting_time
+                .is_none_or(|time| spell.casting_time == Some(time))
+            && self.range.is_none_or(|range| spell.range == Some(range))
+            && !self.without.iter().any(|c| spell.components.contains(c))
+            && self
+                .concentration
+                .is_none_or(|concentration| spell.concentration == concentration)
+            && self.ritual.is_none_or(|ritual| spell.ritual == ritual)
+    }
+}
+
+/// One-line summary of a spell's casting details: school, casting time,
+/// range, components and duration. Prefers the localized wording and falls
+/// back to the kind labels.
+fn spell_summary(spell: &SpellDefinition, i18n: leptos_fluent::I18n) -> String {
+    let mut parts = Vec::new();
+    if let Some(school) = spell.school {
+        parts.push(i18n.tr(school.tr_key()));
+    }
+    match (&spell.casting_time_text, spell.casting_time) {
+        (Some(text), _) => parts.push(text.clone()),
+        (None, Some(time)) => parts.push(i18n.tr(time.tr_key())),
+        (None, None) if spell.ritual => parts.push(i18n.tr("ref-spell-ritual")),
+        _ => {}
+    }
+    match (&spell.range_text, spell.range) {
+        (Some(text), _) => parts.push(text.clone()),
+        (None, Some(range)) => parts.push(i18n.tr(range.tr_key())),
+        _ => {}
+    }
+    if !spell.components.is_empty() {
+        let mut components = spell
+            .components
+            .iter()
+            .map(|c| i18n.tr(c.tr_abbr_key()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match (&spell.materials, spell.material_cost) {
+            (Some(materials), _) => components.push_str(&format!(" ({materials})")),
+            (None, cost) if cost > 0 => {
+                components.push_str(&format!(" ({})", Money::from_gp(cost)));
+            }
+            _ => {}
+        }
+        parts.push(components);
+    }
+    match &spell.duration {
+        Some(duration) => parts.push(duration.clone()),
+        None if spell.concentration => parts.push(i18n.tr("ref-spell-concentration")),
+        None => {}
+    }
+    parts.join(" · ")
+}
+
+/// A `<select>` over an enum facet stored in the page query; the empty
+/// option clears it.
+fn facet_select<T>(
+    i18n: leptos_fluent::I18n,
+    label_key: &'static str,
+    value: Memo<Option<T>>,
+    set_value: SignalSetter<Option<T>>,
+) -> impl IntoView
+where
+    T: IntoEnumIterator
+        + Translatable
+        + FromStr
+        + ToString
+        + PartialEq
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    view! {
+        <label class="spell-filter">
+            <span>{move || i18n.tr(label_key)}</span>
+            <select
+                prop:value=move || value.get().map(|v| v.to_string()).unwrap_or_default()
+                on:change=move |e| set_value.set(event_target_value(&e).parse().ok())
+            >
+                <option value="">{move_tr!("ref-spell-filter-any")}</option>
+                {T::iter()
+                    .map(|option| {
+                        let name = option.to_string();
+                        let key = option.tr_key();
+                        view! { <option value=name>{move || i18n.tr(key)}</option> }
+                    })
+                    .collect_view()}
+            </select>
+        </label>
+    }
+}
+
+/// A yes/no/any `<select>` for a boolean facet stored in the page query.
+fn flag_select(
+    i18n: leptos_fluent::I18n,
+    label_key: &'static str,
+    value: Memo<Option<bool>>,
+    set_value: SignalSetter<Option<bool>>,
+) -> impl IntoView {
+    view! {
+        <label class="spell-filter">
+            <span>{move || i18n.tr(label_key)}</span>
+            <select
+                prop:value=move || value.get().map(|v| v.to_string()).unwrap_or_default()
+                on:change=move |e| set_value.set(event_target_value(&e).parse().ok())
+            >
+                <option value="">{move_tr!("ref-spell-filter-any")}</option>
+                <option value="true">{move_tr!("ref-spell-filter-yes")}</option>
+                <option value="false">{move_tr!("ref-spell-filter-no")}</option>
+            </select>
+        </label>
+    }
+}
+
 #[component]
 pub fn SpellReference() -> impl IntoView {
     let i18n = expect_context::<leptos_fluent::I18n>();
@@ -39,6 +171,83 @@ pub fn SpellReference() -> impl IntoView {
 
     let current_label = Signal::derive(move || registry.spell_label_by_name(&list_name()));
 
+    let (school, set_school) = use_query_signal::<SpellSchool>("school");
+    let (casting_time, set_casting_time) = use_query_signal::<CastingTime>("time");
+    let (range, set_range) = use_query_signal::<SpellRange>("range");
+    let (without, set_without) = use_query_signal::<String>("without");
+    let (concentration, set_concentration) = use_query_signal::<bool>("concentration");
+    let (ritual, set_ritual) = use_query_signal::<bool>("ritual");
+
+    let without_components = Memo::new(move |_| {
+        without
+            .get()
+            .map(|names| {
+                names
+                    .split(',')
+                    .filter_map(|name| name.parse::<SpellComponent>().ok())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    });
+    let toggle_component = move |component: SpellComponent| {
+        let mut names = without_components.get_untracked();
+        if let Some(pos) = names.iter().position(|c| *c == component) {
+            names.remove(pos);
+        } else {
+            names.push(component);
+        }
+        set_without.set((!names.is_empty()).then(|| {
+            names
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        }));
+    };
+
+    let filter = Memo::new(move |_| SpellFilter {
+        school: school.get(),
+        casting_time: casting_time.get(),
+        range: range.get(),
+        without: without_components.get(),
+        concentration: concentration.get(),
+        ritual: ritual.get(),
+    });
+
+    let filters = move || {
+        view! {
+            <div class="spell-filters">
+                {facet_select(i18n, "ref-spell-school", school, set_school)}
+                {facet_select(i18n, "ref-spell-casting-time", casting_time, set_casting_time)}
+                {facet_select(i18n, "ref-spell-range", range, set_range)}
+                {flag_select(i18n, "ref-spell-concentration", concentration, set_concentration)}
+                {flag_select(i18n, "ref-spell-ritual", ritual, set_ritual)}
+                <div class="spell-filter">
+                    <span>{move_tr!("ref-spell-without")}</span>
+                    <div class="spell-filter-chips">
+                        {SpellComponent::iter()
+                            .map(|component| {
+                                let key = component.tr_key();
+                                view! {
+                                    <button
+                                        type="button"
+                                        class="spell-filter-chip"
+                                        class:active=move || {
+                                            without_components.read().contains(&component)
+                                        }
+                                        on:click=move |_| toggle_component(component)
+                                    >
+                                        {move || i18n.tr(key)}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+            </div>
+        }
+    };
+
     let detail = move || {
         let name = list_name();
 
@@ -62,54 +271,23 @@ pub fn SpellReference() -> impl IntoView {
                         .unwrap_or_else(|| name.clone())
                 });
 
-                // Group spells by level
-                struct SpellEntry {
-                    name: String,
-                    label: String,
-                    description: String,
-                    min_level: u32,
-                    sticky: bool,
-                    effects: Vec<(String, Expr<Attribute>)>,
-                }
-                struct SpellGroup {
-                    level: u32,
-                    spells: Vec<SpellEntry>,
-                }
-                let mut by_level_map: BTreeMap<u32, Vec<SpellEntry>> =
-                    BTreeMap::new();
-                for spell in spells.values() {
-                    by_level_map
-                        .entry(spell.level)
-                        .or_default()
-                        .push(SpellEntry {
-                            name: spell.name.clone(),
-                            label: spell.label().to_string(),
-                            description: spell.description.clone(),
-                            min_level: spell.min_level,
-                            sticky: spell.sticky,
-                            effects: spell
-                                .effects
-                                .iter()
-                                .map(|e| (e.label().to_string(), e.expr.clone()))
-                                .collect(),
-                        });
-                }
-                let by_level: Vec<SpellGroup> = by_level_map
-                    .into_iter()
-                    .map(|(level, spells)| SpellGroup { level, spells })
-                    .collect();
-
-                let levels: Vec<u32> =
-                    by_level.iter().map(|group| group.level).collect();
-                let title_for_heading = title.clone();
-                view! {
-                    <Title text=title />
-                    <div class="reference-detail">
-                        <h1>{title_for_heading}</h1>
+                let spells: Arc<Vec<SpellDefinition>> = Arc::new(spells.values().cloned().collect());
+                let list = move || {
+                    let filter = filter.get();
+                    let mut by_level: BTreeMap<u32, Vec<&SpellDefinition>> = BTreeMap::new();
+                    for spell in spells.iter().filter(|spell| filter.matches(spell)) {
+                        by_level.entry(spell.level).or_default().push(spell);
+                    }
+                    if by_level.is_empty() {
+                        return view! {
+                            <p class="reference-empty">{move_tr!("ref-spell-no-matches")}</p>
+                        }
+                        .into_any();
+                    }
 
-                        {by_level.into_iter().map(|group| {
-                            let level = group.level;
-                            let spells = group.spells;
+                    let levels: Vec<u32> = by_level.keys().copied().collect();
+                    view! {
+                        {by_level.into_iter().map(|(level, spells)| {
                             let section_id = format!("spell-level-{level}");
                             let heading = if level == 0 {
                                 move_tr!("ref-cantrips")
@@ -123,10 +301,16 @@ pub fn SpellReference() -> impl IntoView {
                                         let anchor_id = format!("spell-{}", spell.name);
                                         let min_level = spell.min_level;
                                         let sticky = spell.sticky;
+                                        let summary = spell_summary(spell, i18n);
+                                        let effects: Vec<_> = spell
+                                            .effects
+                                            .iter()
+                                            .map(|e| (e.label().to_string(), e.expr.clone()))
+                                            .collect();
                                         view! {
                                             <div class="reference-feature" id=anchor_id>
                                                 <h3>
-                                                    {spell.label}
+                                                    {spell.label().to_string()}
                                                     {(min_level > 0 || sticky).then(|| {
                                                         let mut parts = Vec::new();
                                                         if sticky {
@@ -142,10 +326,13 @@ pub fn SpellReference() -> impl IntoView {
                                                         }
                                                     })}
                                                 </h3>
-                                                <p>{spell.description}</p>
-                                                {(!spell.effects.is_empty()).then(|| view! {
+                                                {(!summary.is_empty()).then(|| view! {
+                                                    <p class="feature-prerequisites">{summary}</p>
+                                                })}
+                                                <p>{spell.description.clone()}</p>
+                                                {(!effects.is_empty()).then(|| view! {
                                                     <div class="spell-effects">
-                                                        {spell.effects.into_iter().map(|(name, expr)| view! {
+                                                        {effects.into_iter().map(|(name, expr)| view! {
                                                             <div class="spell-effect">
                                                                 <strong>{name}</strong>
                                                                 <ExprView expr />
@@ -159,8 +346,19 @@ pub fn SpellReference() -> impl IntoView {
                                 </div>
                             }
                         }).collect_view()}
+                        <SpellLevelNav levels />
+                    }
+                    .into_any()
+                };
+
+                let title_for_heading = title.clone();
+                view! {
+                    <Title text=title />
+                    <div class="reference-detail">
+                        <h1>{title_for_heading}</h1>
+                        {filters}
+                        {list}
                     </div>
-                    <SpellLevelNav levels />
                 }
                 .into_any()
             })
@@ -0,0 +1,117 @@
+use leptos::{either::EitherOf3, prelude::*};
+use leptos_fluent::move_tr;
+use leptos_meta::Title;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use reactive_stores::Store;
+
+use crate::{
+    BASE_URL,
+    components::icon::Icon,
+    effective::EffectiveCharacter,
+    firebase::{self, Subscription},
+    model::{
+        ActiveEffects, Character, CharacterIdentityStoreFields, CharacterStoreFields, RollLog,
+    },
+    pages::character::session::CharacterSession,
+    rules::RulesRegistry,
+    storage::live,
+};
+
+#[derive(Params, Clone, Debug, PartialEq, Eq)]
+struct SpectateParams {
+    user_id: String,
+    char_id: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LiveState {
+    Connecting,
+    Live,
+    Gone,
+}
+
+/// Follows a shared character's session view as its owner plays, without
+/// importing a copy. Nothing here is saved: the sheet is disabled and every
+/// cloud update replaces it.
+#[component]
+pub fn SpectateCharacter() -> impl IntoView {
+    let params = use_params::<SpectateParams>().get_untracked().ok();
+    let registry = expect_context::<RulesRegistry>();
+
+    let store = Store::new(Character::default());
+    let effects = RwSignal::new(ActiveEffects::default());
+    provide_context(store);
+    provide_context(EffectiveCharacter::new(store, effects));
+    provide_context(RwSignal::new(RollLog::default()));
+
+    let state = RwSignal::new(LiveState::Connecting);
+    let subscription = StoredValue::new_local(Option::<Subscription>::None);
+
+    if let Some(SpectateParams { user_id, char_id }) = params {
+        wasm_bindgen_futures::spawn_local(async move {
+            if !firebase::wait_ready().await {
+                state.set(LiveState::Gone);
+                return;
+            }
+            let subscribed = live::subscribe_shared_character(&user_id, &char_id, move |live| {
+                let Some((mut character, active_effects)) = live else {
+                    state.set(LiveState::Gone);
+                    return;
+                };
+                registry.fill_from_registry(&mut character);
+                store.set(character);
+                effects.set(active_effects);
+                state.set(LiveState::Live);
+            });
+            match subscribed {
+                Ok(live_subscription) => {
+                    subscription.try_set_value(Some(live_subscription));
+                }
+                Err(error) => {
+                    log::error!("Failed to follow shared character: {error}");
+                    state.set(LiveState::Gone);
+                }
+            }
+        });
+    } else {
+        state.set(LiveState::Gone);
+    }
+
+    Effect::new(move || {
+        store.with(|c| registry.ensure_definitions_fetched(c));
+    });
+    Effect::new(move || {
+        store.update(|c| registry.fill_from_registry(c));
+    });
+
+    // Each cloud update sets the state again; only a change should rebuild
+    // the page.
+    let state = Memo::new(move |_| state.get());
+    let name = Memo::new(move |_| store.identity().name().get());
+
+    view! {
+        {move || match state.get() {
+            LiveState::Connecting => EitherOf3::A(view! {
+                <div class="panel">
+                    <p>{move_tr!("share-loading")}</p>
+                </div>
+            }),
+            LiveState::Gone => EitherOf3::B(view! {
+                <div class="panel">
+                    <h2>{move_tr!("share-not-found")}</h2>
+                    <A href=format!("{BASE_URL}/")>{move_tr!("back-to-list")}</A>
+                </div>
+            }),
+            LiveState::Live => EitherOf3::C(view! {
+                <Title text=move || name.get() />
+                <div class="spectate-banner">
+                    <Icon name="eye" size=16 />
+                    {move_tr!("spectate-banner", { "name" => name.get() })}
+                </div>
+                <fieldset class="spectate-session" disabled>
+                    <CharacterSession />
+                </fieldset>
+            }),
+        }}
+    }
+}
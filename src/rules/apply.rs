@@ -96,7 +96,8 @@ impl PendingFeature {
 // ── Collect functions ────────────────────────────────────────────────
 
 /// Collect new features for a class level-up from class + subclass level rules.
-/// Filters out already-applied features via dedup check.
+/// Classes after the first get the multiclass proficiencies instead of the
+/// starting-class ones. Filters out already-applied features via dedup check.
 pub fn collect_class_features<'a>(
     character: &'a Character,
     class_idx: usize,
@@ -107,7 +108,6 @@ pub fn collect_class_features<'a>(
     let class_level = &character.identity.classes[class_idx];
     let source = FeatureSource::Class(class_def.name.clone(), level);
 
-    let rules = class_def.levels.get(level as usize - 1);
     let subclass_rules = class_level
         .subclass
         .as_deref()
@@ -115,9 +115,8 @@ pub fn collect_class_features<'a>(
         .and_then(|sc| sc.levels.get(&level));
 
     let filter_source = source.clone();
-    rules
-        .into_iter()
-        .flat_map(|r| r.features.iter())
+    class_def
+        .level_features(level, class_idx > 0)
         .chain(subclass_rules.into_iter().flat_map(|r| r.features.iter()))
         .filter(move |feat_name| {
             features_index.get(feat_name.as_str()).is_none_or(|feat| {
@@ -401,7 +400,9 @@ impl RulesRegistry {
         character.compute();
         self.assign(character, WhenCondition::OnCompute);
         character.compute_armor_class();
+        character.apply_item_assignments();
         self.recompute_dynamic_fields(character);
+        self.recompute_prepare_limits(character);
     }
 
     /// Re-evaluate dynamic field values (Points max, Die amount) after
@@ -456,6 +457,40 @@ impl RulesRegistry {
         });
     }
 
+    /// Look up each spellcasting feature's preparation limit in its level
+    /// table.
+    fn recompute_prepare_limits(&self, character: &mut Character) {
+        self.with_features_index_untracked(|features_index| {
+            let class_cache = self.class_cache.read_untracked();
+
+            let limits: Vec<_> = character
+                .feature_data
+                .iter()
+                .filter(|(_, entry)| entry.spells.is_some())
+                .filter_map(|(feat_name, _)| {
+                    let (feat_def, class_level) = find_feature_with_class_level(
+                        &character.identity,
+                        feat_name,
+                        features_index,
+                        &class_cache,
+                    )?;
+                    let spells_def = feat_def.spells.as_ref()?;
+                    Some((feat_name.clone(), spells_def.prepare_limit(class_level)))
+                })
+                .collect();
+
+            for (feat_name, limit) in limits {
+                if let Some(spell_data) = character
+                    .feature_data
+                    .get_mut(&feat_name)
+                    .and_then(|entry| entry.spells.as_mut())
+                {
+                    spell_data.set_prepare_limit(limit);
+                }
+            }
+        });
+    }
+
     /// Evaluate assignment expressions across all features for the given
     /// condition.
     ///
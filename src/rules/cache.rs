@@ -48,6 +48,20 @@ impl<T: Clone + Send + Sync + 'static> FetchCache<T> {
         !self.pending.read().is_empty()
     }
 
+    /// Insert a definition that was not fetched (e.g. from a content pack).
+    /// It has no raw entry, so locale batches leave it alone.
+    pub fn insert(&self, name: &str, def: T) {
+        self.data.update(|m| {
+            m.insert(name.into(), def);
+        });
+    }
+
+    pub fn remove(&self, name: &str) {
+        self.data.update(|m| {
+            m.remove(name);
+        });
+    }
+
     pub fn clear(&self) {
         self.raw.update(|m| m.clear());
         self.data.update(|m| m.clear());
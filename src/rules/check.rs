@@ -0,0 +1,266 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    expr::Expr,
+    model::{ActiveEffect, Attribute, EffectDefinition},
+    rules::{
+        ChoiceOptions, ConditionDefinition, EquipmentDefinition, FeatureDefinition, FeaturesIndex,
+        FieldKind, SpellDefinition, ValueOrExpr,
+    },
+};
+
+/// Where an expression is evaluated, which decides what it may touch.
+#[derive(Clone, Copy)]
+enum Site<'a> {
+    /// Feature assignment: writes go to the character, `POINTS.n` to the
+    /// fields of the target feature and ARGs come from the args modal.
+    Assignment(&'a FeatureDefinition),
+    /// Active effect: writes become overrides on top of the character.
+    /// Effects of feature options see the owning feature's `POINTS.n`.
+    Effect(Option<&'a FeatureDefinition>),
+    /// Evaluated for its value only (prerequisites, field levels).
+    Value,
+}
+
+/// Static checker for expressions in rules data. Catches mistakes that
+/// otherwise only show up as runtime errors in the browser: writes to
+/// read-only attributes, unknown scopes, `POINTS.n` without a matching
+/// Points/Die field and ARGs the args modal never asks for.
+pub struct ExprChecker<'a> {
+    features: &'a FeaturesIndex,
+    problems: Vec<String>,
+}
+
+impl<'a> ExprChecker<'a> {
+    pub fn new(features: &'a FeaturesIndex) -> Self {
+        Self {
+            features,
+            problems: Vec::new(),
+        }
+    }
+
+    /// Problems found so far, one `location: message` line each.
+    pub fn problems(self) -> Vec<String> {
+        self.problems
+    }
+
+    pub fn feature(&mut self, feature: &FeatureDefinition) {
+        let location = format!("feature {}", feature.name);
+        if let Some(expr) = &feature.prerequisites {
+            self.expr(&format!("{location} prerequisites"), expr, Site::Value);
+        }
+
+        for field in feature.fields.values() {
+            let location = format!("{location} field {}", field.name);
+            match &field.kind {
+                FieldKind::Points { levels } | FieldKind::FreeUses { levels } => {
+                    for value in levels.values() {
+                        self.value(&location, value);
+                    }
+                }
+                FieldKind::Die { levels } => {
+                    for die in levels.values() {
+                        self.value(&location, &die.amount);
+                    }
+                }
+                FieldKind::Choice {
+                    options: ChoiceOptions::List(options),
+                    ..
+                } => {
+                    for option in options {
+                        for effect in &option.effects {
+                            let location = format!("{location} option {}", option.name);
+                            self.effect(&location, effect, Some(feature));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (idx, assignment) in feature.assign.iter().flatten().enumerate() {
+            let location = format!("{location} assignment #{idx}");
+            let target = match assignment.scope.as_deref() {
+                None => feature,
+                Some(scope) => match self.features.0.get(scope) {
+                    Some(target) => target,
+                    None => {
+                        self.report(&location, format!("unknown scope {scope}"));
+                        continue;
+                    }
+                },
+            };
+            self.expr(&location, &assignment.expr, Site::Assignment(target));
+        }
+    }
+
+    pub fn prerequisites(&mut self, location: &str, expr: &Expr<Attribute>) {
+        self.expr(&format!("{location} prerequisites"), expr, Site::Value);
+    }
+
+    pub fn spell(&mut self, location: &str, spell: &SpellDefinition) {
+        for effect in &spell.effects {
+            let location = format!("{location} spell {} effect {}", spell.name, effect.name);
+            self.effect(&location, effect, None);
+        }
+    }
+
+    /// An entry of the effects catalog (bundled or from a content pack).
+    pub fn active_effect(&mut self, location: &str, effect: &ActiveEffect) {
+        let location = format!("{location} effect {}", effect.name);
+        if let Some(scope) = effect.scope.as_deref()
+            && !self.features.0.contains_key(scope)
+        {
+            self.report(&location, format!("unknown scope {scope}"));
+        }
+        if let Some(expr) = &effect.expr {
+            self.expr(&location, expr, Site::Effect(None));
+        }
+    }
+
+    pub fn condition(&mut self, condition: &ConditionDefinition) {
+        let location = format!("condition {}", condition.name);
+        self.expr(&location, &condition.expr, Site::Effect(None));
+    }
+
+    pub fn equipment(&mut self, item: &EquipmentDefinition) {
+        let location = format!("equipment {}", item.name);
+        for expr in item.damage.iter().chain(&item.versatile) {
+            self.expr(&location, expr, Site::Value);
+        }
+    }
+
+    fn effect(
+        &mut self,
+        location: &str,
+        effect: &EffectDefinition,
+        owner: Option<&FeatureDefinition>,
+    ) {
+        if let Some(scope) = effect.scope.as_deref()
+            && !self.features.0.contains_key(scope)
+        {
+            self.report(location, format!("unknown scope {scope}"));
+        }
+        self.expr(location, &effect.expr, Site::Effect(owner));
+    }
+
+    fn value(&mut self, location: &str, value: &ValueOrExpr) {
+        if let ValueOrExpr::Expr(expr) = value {
+            self.expr(location, expr, Site::Value);
+        }
+    }
+
+    fn expr(&mut self, location: &str, expr: &Expr<Attribute>, site: Site) {
+        let assigned: BTreeSet<_> = expr.assigned_vars().copied().collect();
+        for var in assigned {
+            let writable = match site {
+                Site::Assignment(_) => var.is_writable(),
+                Site::Effect(_) => true,
+                Site::Value => false,
+            };
+            if !writable {
+                self.report(location, format!("assigns to read-only {var}"));
+            }
+        }
+
+        let inputs = match site {
+            Site::Assignment(_) => {
+                expr.analyze_all(|var| match var {
+                    Attribute::Arg(n) => Some(*n),
+                    _ => None,
+                })
+                .active_args
+            }
+            Site::Effect(_) | Site::Value => Vec::new(),
+        };
+        let vars: BTreeSet<_> = expr.vars().copied().collect();
+        for var in vars {
+            match (var, site) {
+                (
+                    Attribute::Points(n) | Attribute::PointsMax(n),
+                    Site::Assignment(target) | Site::Effect(Some(target)),
+                ) if target.points_field(n).is_none() => {
+                    let name = &target.name;
+                    self.report(location, format!("{var} has no Points/Die field in {name}"));
+                }
+                // Spell effects see the fields of whichever feature casts them,
+                // so only feature-owned indices can be checked.
+                (
+                    Attribute::Points(_) | Attribute::PointsMax(_),
+                    Site::Assignment(_) | Site::Effect(_),
+                ) => {}
+                (Attribute::Points(_) | Attribute::PointsMax(_), Site::Value) => {
+                    self.report(location, format!("{var} is not available here"));
+                }
+                (Attribute::Arg(n), _) if !inputs.contains(&n) => {
+                    self.report(location, format!("{var} is never asked for"));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn report(&mut self, location: &str, message: String) {
+        self.problems.push(format!("{location}: {message}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_bad_expressions() {
+        let features: FeaturesIndex = serde_json::from_str(
+            r#"[{
+                "name": "Test",
+                "prerequisites": "AC = 1",
+                "fields": [
+                    {"name": "Uses", "kind": "Points", "levels": {"1": 2}},
+                    {"name": "Actions", "kind": "Choice", "options": [
+                        {"name": "Boost", "effects": [{"name": "Boost", "expr": "POINTS.1", "scope": "Nope"}]}
+                    ]}
+                ],
+                "assign": [
+                    {"expr": "PROF_BONUS += 1", "when": "OnCompute"},
+                    {"expr": "POINTS.1 = POINTS_MAX.1", "when": "OnLongRest"},
+                    {"expr": "guard(ARG.1 > 0, AC += ARG.0)", "when": "OnFeatureAdd"},
+                    {"expr": "POINTS = 0", "when": "OnLongRest", "scope": "Missing"}
+                ]
+            }]"#,
+        )
+        .unwrap();
+        let mut checker = ExprChecker::new(&features);
+        checker.feature(&features.0["Test"]);
+        assert_eq!(
+            checker.problems(),
+            [
+                "feature Test prerequisites: assigns to read-only AC",
+                "feature Test field Actions option Boost: unknown scope Nope",
+                "feature Test assignment #0: assigns to read-only PROF_BONUS",
+                "feature Test assignment #2: ARG.1 is never asked for",
+                "feature Test assignment #3: unknown scope Missing",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_bad_catalog_expressions() {
+        let features = FeaturesIndex(Default::default());
+        let effect: ActiveEffect =
+            serde_json::from_str(r#"{"name": "Boost", "expr": "POINTS.1", "scope": "Nope"}"#)
+                .unwrap();
+        let item: EquipmentDefinition =
+            serde_json::from_str(r#"{"name": "Club", "damage": "STR.MOD = 1d4"}"#).unwrap();
+        let mut checker = ExprChecker::new(&features);
+        checker.active_effect("effects.json", &effect);
+        checker.equipment(&item);
+        assert_eq!(
+            checker.problems(),
+            [
+                "effects.json effect Boost: unknown scope Nope",
+                "equipment Club: assigns to read-only STR.MOD",
+            ]
+        );
+    }
+}
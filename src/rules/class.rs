@@ -20,6 +20,19 @@ pub struct ClassDefinition {
     pub levels: Vec<ClassLevelRules>,
     #[serde(default, deserialize_with = "demap::named_map")]
     pub subclasses: BTreeMap<Box<str>, SubclassDefinition>,
+    /// Level-1 feature granting the starting-class proficiencies.
+    #[serde(default)]
+    pub proficiencies: Option<String>,
+    /// Feature granting the smaller proficiency set of a multiclass entry.
+    /// Taken instead of `proficiencies` by every class but the first.
+    #[serde(default)]
+    pub multiclass_proficiencies: Option<String>,
+}
+
+impl Named for ClassDefinition {
+    fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 impl ClassDefinition {
@@ -30,6 +43,9 @@ impl ClassDefinition {
     /// Find the class level at which a feature first appears (checking both
     /// base class and subclass level tables). Returns 0 if not found.
     pub fn feature_level(&self, subclass: Option<&str>, feature_name: &str) -> u32 {
+        if self.multiclass_proficiencies.as_deref() == Some(feature_name) {
+            return 1;
+        }
         for (index, level_rules) in self.levels.iter().enumerate() {
             if level_rules.features.iter().any(|name| name == feature_name) {
                 return index as u32 + 1;
@@ -45,7 +61,8 @@ impl ClassDefinition {
         0
     }
 
-    /// Iterate all feature names from class levels and subclass levels.
+    /// Iterate all feature names from class levels and subclass levels,
+    /// including the multiclass proficiencies.
     pub fn feature_names<'a>(&'a self, subclass: Option<&str>) -> impl Iterator<Item = &'a str> {
         let sc_features = subclass
             .and_then(|s| self.subclasses.get(s))
@@ -55,8 +72,26 @@ impl ClassDefinition {
         self.levels
             .iter()
             .flat_map(|lr| lr.features.iter().map(String::as_str))
+            .chain(self.multiclass_proficiencies.as_deref())
             .chain(sc_features)
     }
+
+    /// Base class features granted at `level`. A multiclass entry (any
+    /// class but the first) swaps the starting-class proficiencies for the
+    /// multiclass subset.
+    pub fn level_features(&self, level: u32, multiclass: bool) -> impl Iterator<Item = &String> {
+        let skipped = self.proficiencies.as_ref().filter(|_| multiclass);
+        let added = self
+            .multiclass_proficiencies
+            .as_ref()
+            .filter(|_| multiclass && level == 1);
+        self.levels
+            .get(level as usize - 1)
+            .into_iter()
+            .flat_map(|rules| rules.features.iter())
+            .filter(move |name| Some(*name) != skipped)
+            .chain(added)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -98,3 +133,33 @@ pub struct ClassLevelRules {
     #[serde(default)]
     pub features: VecSet<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_features_swap_proficiencies_for_multiclass() {
+        let class: ClassDefinition = serde_json::from_str(
+            r#"{"name": "Fighter", "hit_die": 10,
+                "proficiencies": "Fighter Proficiencies",
+                "multiclass_proficiencies": "Fighter Multiclass Proficiencies",
+                "levels": [{"features": ["Fighter Proficiencies", "Second Wind"]},
+                           {"features": ["Action Surge"]}]}"#,
+        )
+        .unwrap();
+        let features = |level, multiclass| {
+            class
+                .level_features(level, multiclass)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(features(1, false), ["Fighter Proficiencies", "Second Wind"]);
+        assert_eq!(
+            features(1, true),
+            ["Second Wind", "Fighter Multiclass Proficiencies"]
+        );
+        assert_eq!(features(2, true), ["Action Surge"]);
+    }
+}
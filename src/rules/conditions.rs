@@ -0,0 +1,51 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::{
+    demap::{self, Named},
+    expr::Expr,
+    model::Attribute,
+};
+
+/// A 5e condition (Poisoned, Prone, Exhaustion, …) and the expression it
+/// applies to the character while active.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConditionDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub expr: Expr<Attribute>,
+    /// Highest level of a leveled condition (Exhaustion). Each level applies
+    /// `expr` once more, so penalties stack.
+    #[serde(default)]
+    pub max_level: Option<u32>,
+}
+
+impl ConditionDefinition {
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn is_leveled(&self) -> bool {
+        self.max_level.is_some_and(|max| max > 1)
+    }
+}
+
+impl Named for ConditionDefinition {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConditionsIndex(pub BTreeMap<Box<str>, ConditionDefinition>);
+
+impl<'de> Deserialize<'de> for ConditionsIndex {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        demap::named_map(deserializer).map(Self)
+    }
+}
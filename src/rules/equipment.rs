@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use strum::{Display, EnumIter, EnumString};
+
+use crate::{
+    demap::{self, Named},
+    expr::Expr,
+    model::{
+        Armor, ArmorType, Attribute, DamageType, Item, Money, Translatable, Weapon, WeaponCategory,
+        WeaponEffect, WeaponProperty,
+    },
+    vecset::VecSet,
+};
+
+/// Catalog section an entry belongs to, inferred from which fields it sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Display, EnumString)]
+pub enum EquipmentKind {
+    Weapon,
+    Armor,
+    Gear,
+}
+
+impl Translatable for EquipmentKind {
+    fn tr_key(&self) -> &'static str {
+        match self {
+            Self::Weapon => "weapons",
+            Self::Armor => "armor",
+            Self::Gear => "ref-equipment-gear",
+        }
+    }
+}
+
+/// A catalog entry for a weapon, armor piece or adventuring gear.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EquipmentDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    /// Weight in pounds.
+    #[serde(default)]
+    pub weight: f32,
+    /// Price in gold pieces, parsed with `Money::from_gp_str` (`"0.05"` is
+    /// 5 cp).
+    #[serde(default)]
+    pub cost: String,
+
+    // -- Weapons --
+    #[serde(default)]
+    pub category: Option<WeaponCategory>,
+    #[serde(default)]
+    pub properties: VecSet<WeaponProperty>,
+    #[serde(default)]
+    pub damage: Option<Expr<Attribute>>,
+    #[serde(default)]
+    pub damage_type: Option<DamageType>,
+    #[serde(default)]
+    pub versatile: Option<Expr<Attribute>>,
+    /// Gear entry consumed by an ammunition weapon (e.g. "Arrows").
+    #[serde(default)]
+    pub ammunition: String,
+
+    // -- Armor --
+    #[serde(default)]
+    pub armor_type: Option<ArmorType>,
+    #[serde(default)]
+    pub base_ac: u32,
+}
+
+impl EquipmentDefinition {
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn kind(&self) -> EquipmentKind {
+        if self.category.is_some() {
+            EquipmentKind::Weapon
+        } else if self.armor_type.is_some() {
+            EquipmentKind::Armor
+        } else {
+            EquipmentKind::Gear
+        }
+    }
+
+    pub fn cost(&self) -> Money {
+        Money::from_gp_str(&self.cost).unwrap_or_default()
+    }
+
+    /// Fill `weapon` from this entry, keeping its extra (magic) attack bonus.
+    pub fn fill_weapon(&self, weapon: &mut Weapon) {
+        weapon.name = self.label().to_string();
+        weapon.category = self.category;
+        weapon.properties = self.properties.clone();
+        weapon.effects = self
+            .damage
+            .iter()
+            .map(|expr| WeaponEffect {
+                name: String::new(),
+                damage_type: self.damage_type,
+                expr: expr.clone(),
+            })
+            .collect();
+        weapon.versatile = self.versatile.clone();
+        weapon.ammunition = self.ammunition.clone();
+        weapon.weight = self.weight;
+        weapon.cost = self.cost();
+    }
+
+    pub fn fill_armor(&self, armor: &mut Armor) {
+        let armor_type = self.armor_type.unwrap_or_default();
+        armor.name = self.label().to_string();
+        armor.armor_type = armor_type;
+        armor.base_ac = self.base_ac;
+        armor.ac_expr = Armor::default_ac_expr(armor_type, self.base_ac);
+        armor.weight = self.weight;
+        armor.cost = self.cost();
+    }
+
+    /// Fill `item` from this entry, keeping its quantity and magic state.
+    pub fn fill_item(&self, item: &mut Item) {
+        item.name = self.label().to_string();
+        item.description = self.description.clone();
+        item.weight = self.weight;
+        item.cost = self.cost();
+    }
+}
+
+impl Named for EquipmentDefinition {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EquipmentIndex(pub BTreeMap<Box<str>, EquipmentDefinition>);
+
+impl<'de> Deserialize<'de> for EquipmentIndex {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        demap::named_map(deserializer).map(Self)
+    }
+}
+
+impl EquipmentIndex {
+    /// Find an entry of `kind` by its display label (what the datalist puts
+    /// into the input) or canonical name.
+    pub fn find(&self, kind: EquipmentKind, label: &str) -> Option<&EquipmentDefinition> {
+        let label = label.trim();
+        let lowercase = label.to_lowercase();
+        self.0
+            .values()
+            .filter(|def| def.kind() == kind)
+            .find(|def| def.label().to_lowercase() == lowercase || def.name == label)
+    }
+
+    /// Like [`find`](Self::find), with the weapon's ammunition named by its
+    /// display label: backpack gear filled from the catalog carries the
+    /// label, and ammunition is matched against it by name.
+    pub fn find_localized(&self, kind: EquipmentKind, label: &str) -> Option<EquipmentDefinition> {
+        let mut def = self.find(kind, label)?.clone();
+        if let Some(ammunition) = self.0.get(def.ammunition.as_str()) {
+            def.ammunition = ammunition.label().to_string();
+        }
+        Some(def)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> EquipmentDefinition {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn fill_weapon_from_catalog() {
+        let def = parse(
+            r#"{"name": "Longsword", "category": "Martial", "properties": ["Versatile"],
+                "damage": "1d8 + STR.MOD", "damage_type": "Slashing",
+                "versatile": "1d10 + STR.MOD", "weight": 3, "cost": "15"}"#,
+        );
+        assert_eq!(def.kind(), EquipmentKind::Weapon);
+
+        let mut weapon = Weapon {
+            attack_bonus: 1,
+            ..Weapon::default()
+        };
+        def.fill_weapon(&mut weapon);
+        assert_eq!(weapon.name, "Longsword");
+        assert_eq!(weapon.attack_bonus, 1);
+        assert_eq!(weapon.category, Some(WeaponCategory::Martial));
+        assert!(weapon.has(WeaponProperty::Versatile));
+        assert_eq!(weapon.effects.len(), 1);
+        assert_eq!(weapon.effects[0].damage_type, Some(DamageType::Slashing));
+        assert_eq!(weapon.cost, Money::from_gp(15));
+    }
+
+    #[test]
+    fn fill_armor_uses_default_ac_expr() {
+        let def = parse(
+            r#"{"name": "Chain Shirt", "armor_type": "Medium", "base_ac": 13,
+                "weight": 20, "cost": "50"}"#,
+        );
+        assert_eq!(def.kind(), EquipmentKind::Armor);
+
+        let mut armor = Armor::default();
+        def.fill_armor(&mut armor);
+        assert_eq!(armor.armor_type, ArmorType::Medium);
+        assert_eq!(armor.base_ac, 13);
+        assert_eq!(armor.ac_expr, Armor::default_ac_expr(ArmorType::Medium, 13));
+        assert_eq!(armor.weight, 20.0);
+    }
+
+    #[test]
+    fn localized_weapon_names_ammunition_by_label() {
+        let index: EquipmentIndex = serde_json::from_str(
+            r#"[
+                {"name": "Longbow", "label": "Длинный лук", "category": "Martial",
+                 "damage": "1d8 + DEX.MOD", "ammunition": "Arrows"},
+                {"name": "Arrows", "label": "Стрелы"}
+            ]"#,
+        )
+        .unwrap();
+        let def = index
+            .find_localized(EquipmentKind::Weapon, "длинный лук")
+            .unwrap();
+        assert_eq!(def.ammunition, "Стрелы");
+
+        let mut weapon = Weapon::default();
+        def.fill_weapon(&mut weapon);
+        let mut arrows = Item::default();
+        index.0["Arrows"].fill_item(&mut arrows);
+        assert_eq!(weapon.ammunition, arrows.name);
+    }
+}
@@ -494,6 +494,25 @@ impl FeatureDefinition {
             .unwrap_or_default()
     }
 
+    /// Field definitions in the order they are stored in the character's
+    /// feature data. Free uses of spellcasting features are tracked on the
+    /// spells instead.
+    fn stored_fields(&self) -> impl Iterator<Item = &FieldDefinition> {
+        self.fields.values().filter(|field_def| {
+            !(self.spells.is_some() && matches!(field_def.kind, FieldKind::FreeUses { .. }))
+        })
+    }
+
+    /// The Points or Die field that `POINTS.n` / `POINTS_MAX.n` refer to.
+    pub fn points_field(&self, idx: u8) -> Option<&FieldDefinition> {
+        self.stored_fields().nth(idx as usize).filter(|field_def| {
+            matches!(
+                field_def.kind,
+                FieldKind::Points { .. } | FieldKind::Die { .. }
+            )
+        })
+    }
+
     fn apply_fields(&self, level: u32, character: &mut Character) {
         // Always ensure feature_data entry exists, even for field-less features
         character.feature_data.entry(self.name.clone()).or_default();
@@ -510,11 +529,7 @@ impl FeatureDefinition {
         if is_new {
             // Pre-compute values before mutating feature_data
             let new_fields: Vec<_> = self
-                .fields
-                .values()
-                .filter(|field_def| {
-                    !(self.spells.is_some() && matches!(field_def.kind, FieldKind::FreeUses { .. }))
-                })
+                .stored_fields()
                 .map(|field_def| FeatureField {
                     name: field_def.name.clone(),
                     label: field_def.label.clone(),
@@ -801,4 +816,19 @@ mod tests {
             index.0.len()
         );
     }
+
+    #[test]
+    fn point_buy_asks_for_every_ability() {
+        let data = include_str!("../../public/data/features.json");
+        let index: FeaturesIndex = serde_json::from_str(data).unwrap();
+        let assign = index.0["Generation: Point Buy"].assign.as_deref().unwrap();
+        let analysis = assign[0].expr.analyze_all(|var| match var {
+            Attribute::Arg(n) => Some(*n),
+            _ => None,
+        });
+        let mut args = analysis.active_args;
+        args.sort_unstable();
+        args.dedup();
+        assert_eq!(args, [0, 1, 2, 3, 4, 5]);
+    }
 }
@@ -20,6 +20,21 @@ pub struct Index {
     pub spells: BTreeMap<Box<str>, SpellIndexEntry>,
 }
 
+impl Index {
+    /// Add entries from `other` whose names are not taken yet.
+    pub fn merge(&mut self, other: Index) {
+        fn merge_map<T>(into: &mut BTreeMap<Box<str>, T>, from: BTreeMap<Box<str>, T>) {
+            for (name, entry) in from {
+                into.entry(name).or_insert(entry);
+            }
+        }
+        merge_map(&mut self.classes, other.classes);
+        merge_map(&mut self.species, other.species);
+        merge_map(&mut self.backgrounds, other.backgrounds);
+        merge_map(&mut self.spells, other.spells);
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ClassIndexEntry {
     pub name: String,
@@ -5,6 +5,8 @@ use serde::Deserialize;
 use super::{
     background::BackgroundDefinition,
     class::ClassDefinition,
+    conditions::ConditionsIndex,
+    equipment::EquipmentIndex,
     feature::{ChoiceOptions, FeaturesIndex, FieldKind},
     index::Index,
     species::SpeciesDefinition,
@@ -189,8 +191,30 @@ pub type IndexLocaleMap = BTreeMap<Box<str>, LocaleText>;
 /// Effects locale map: keys are effect names.
 pub type EffectsLocaleMap = BTreeMap<Box<str>, LocaleText>;
 
+/// Conditions locale map: keys are condition names.
+pub type ConditionsLocaleMap = BTreeMap<Box<str>, LocaleText>;
+
+/// Equipment catalog locale map: keys are item names.
+pub type EquipmentLocaleMap = BTreeMap<Box<str>, LocaleText>;
+
+/// Spell locale entry: the common label/description plus the spell's
+/// free-text casting time, range, duration and materials.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SpellLocaleText {
+    #[serde(flatten)]
+    pub text: LocaleText,
+    #[serde(default)]
+    pub casting_time_text: Option<String>,
+    #[serde(default)]
+    pub range_text: Option<String>,
+    #[serde(default)]
+    pub duration: Option<String>,
+    #[serde(default)]
+    pub materials: Option<String>,
+}
+
 /// Spell list locale map: keys are spell names.
-pub type SpellLocaleMap = BTreeMap<Box<str>, LocaleText>;
+pub type SpellLocaleMap = BTreeMap<Box<str>, SpellLocaleText>;
 
 /// Apply locale to an `Index`.
 pub(super) fn apply_index_locale(index: &mut Index, locale: &IndexLocaleMap) {
@@ -237,6 +261,26 @@ pub fn apply_effects_locale(effects: &mut EffectsIndex, locale: &EffectsLocaleMa
     }
 }
 
+/// Apply locale to a `ConditionsIndex`.
+pub fn apply_conditions_locale(conditions: &mut ConditionsIndex, locale: &ConditionsLocaleMap) {
+    for (name, text) in locale {
+        if let Some(condition) = conditions.0.get_mut(name.as_ref()) {
+            text.apply_label(&mut condition.label);
+            text.apply_description(&mut condition.description);
+        }
+    }
+}
+
+/// Apply locale to an `EquipmentIndex`.
+pub fn apply_equipment_locale(equipment: &mut EquipmentIndex, locale: &EquipmentLocaleMap) {
+    for (name, text) in locale {
+        if let Some(entry) = equipment.0.get_mut(name.as_ref()) {
+            text.apply_label(&mut entry.label);
+            text.apply_description(&mut entry.description);
+        }
+    }
+}
+
 /// Apply locale to a `FeaturesIndex`.
 /// Keys are flat: `"Rage"` for label/description, `"Rage.field.X"` for
 /// sub-paths.
@@ -301,8 +345,12 @@ pub fn apply_features_locale(features: &mut FeaturesIndex, locale: &LocaleMap) {
 pub fn apply_spell_map_locale(spells: &mut SpellMap, locale: &SpellLocaleMap) {
     for (name, text) in locale {
         if let Some(spell_def) = spells.0.get_mut(name.as_ref()) {
-            text.apply_label(&mut spell_def.label);
-            text.apply_description(&mut spell_def.description);
+            text.text.apply_label(&mut spell_def.label);
+            text.text.apply_description(&mut spell_def.description);
+            spell_def.casting_time_text = text.casting_time_text.clone();
+            spell_def.range_text = text.range_text.clone();
+            spell_def.duration = text.duration.clone();
+            spell_def.materials = text.materials.clone();
         }
     }
 }
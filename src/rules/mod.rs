@@ -2,11 +2,15 @@ pub mod apply;
 pub use apply::{ApplyInputs, PendingInputs};
 pub mod background;
 mod cache;
+mod check;
 pub mod class;
+pub mod conditions;
+pub mod equipment;
 pub mod feature;
 mod index;
 mod labels;
 pub mod locale;
+pub mod pack;
 mod registry;
 mod resolve;
 pub mod species;
@@ -15,13 +19,21 @@ pub mod utils;
 
 pub use background::BackgroundDefinition;
 pub use cache::DefinitionStore;
+pub use check::ExprChecker;
 pub use class::{ClassDefinition, ClassLevelRules, SubclassDefinition, SubclassLevelRules};
+pub use conditions::{ConditionDefinition, ConditionsIndex};
+pub use equipment::{EquipmentDefinition, EquipmentIndex, EquipmentKind};
 pub use feature::{
     ActionType, Assignment, ChoiceOption, ChoiceOptions, DieOrExpr, FeatureDefinition,
     FeaturesIndex, FieldDefinition, FieldKind, ReplaceWith, ValueOrExpr, WhenCondition,
 };
 pub use index::{BackgroundIndexEntry, ClassIndexEntry, Index, SpeciesIndexEntry, SpellIndexEntry};
+pub use pack::ContentPack;
 pub use registry::RulesRegistry;
 pub use species::SpeciesDefinition;
 pub use spells::{SpellDefinition, SpellLevelRules, SpellList, SpellMap, SpellsDefinition};
 pub use utils::LevelRules;
+
+/// The effects catalog (`effects.json`), read by the registry alongside the
+/// rules data.
+pub use crate::model::EffectsIndex;
@@ -0,0 +1,397 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use super::{
+    background::BackgroundDefinition,
+    class::ClassDefinition,
+    feature::{FeatureDefinition, FeaturesIndex},
+    index::{BackgroundIndexEntry, ClassIndexEntry, Index, SpeciesIndexEntry, SpellIndexEntry},
+    locale::{self, EffectsLocaleMap, IndexLocaleMap, LocaleMap, SpellLocaleMap},
+    species::SpeciesDefinition,
+    spells::{SpellList, SpellMap},
+};
+use crate::{
+    demap,
+    model::{ActiveEffect, Character, EffectsIndex},
+};
+
+/// URL scheme for index entries provided by a pack. These never hit the
+/// network: the registry installs pack definitions into its caches directly.
+pub const PACK_URL_PREFIX: &str = "pack:";
+
+/// A homebrew content bundle imported by the user.
+///
+/// Classes, species, backgrounds, features and effects keep their names and
+/// must not collide with bundled content or other packs. Spell lists are
+/// namespaced as `"{id}.{list}"`, and pack features referencing their own
+/// lists (`"from": "spells/{list}.json"`) are rewritten on parse.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContentPack {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default, deserialize_with = "demap::named_map")]
+    pub classes: BTreeMap<Box<str>, ClassDefinition>,
+    #[serde(default, deserialize_with = "demap::named_map")]
+    pub species: BTreeMap<Box<str>, SpeciesDefinition>,
+    #[serde(default, deserialize_with = "demap::named_map")]
+    pub backgrounds: BTreeMap<Box<str>, BackgroundDefinition>,
+    #[serde(default, deserialize_with = "demap::named_map")]
+    pub features: BTreeMap<Box<str>, FeatureDefinition>,
+    /// Spell lists keyed by short list name.
+    #[serde(default)]
+    pub spells: BTreeMap<Box<str>, SpellMap>,
+    #[serde(default, deserialize_with = "demap::named_map")]
+    pub effects: BTreeMap<Box<str>, ActiveEffect>,
+    /// Locale overlays keyed by language id (e.g. `"ru"`).
+    #[serde(default)]
+    pub locales: BTreeMap<Box<str>, PackLocale>,
+}
+
+/// Translations bundled with a pack, in the same shapes as the app's own
+/// locale files.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PackLocale {
+    /// Index labels: `"class.X"`, `"species.X"`, `"background.X"`,
+    /// `"spell.{list}"`.
+    #[serde(default)]
+    pub index: IndexLocaleMap,
+    #[serde(default)]
+    pub classes: BTreeMap<Box<str>, LocaleMap>,
+    #[serde(default)]
+    pub species: BTreeMap<Box<str>, LocaleMap>,
+    #[serde(default)]
+    pub backgrounds: BTreeMap<Box<str>, LocaleMap>,
+    #[serde(default)]
+    pub features: LocaleMap,
+    #[serde(default)]
+    pub spells: BTreeMap<Box<str>, SpellLocaleMap>,
+    #[serde(default)]
+    pub effects: EffectsLocaleMap,
+}
+
+impl ContentPack {
+    /// Deserialize, validate and namespace a pack from its JSON text.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let mut pack: Self = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        pack.validate()?;
+        pack.namespace_spell_refs();
+        Ok(pack)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty()
+            || !self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(format!(
+                "invalid pack id {:?}: use lowercase letters, digits and dashes",
+                self.id
+            ));
+        }
+        if self.name.trim().is_empty() {
+            return Err("pack name is empty".into());
+        }
+        if self.is_empty() {
+            return Err(format!("pack {:?} provides no content", self.id));
+        }
+        for (list, spells) in &self.spells {
+            if list.is_empty() || list.contains(['/', '.']) {
+                return Err(format!("invalid spell list name {list:?}"));
+            }
+            if spells.is_empty() {
+                return Err(format!("spell list {list:?} is empty"));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+            && self.species.is_empty()
+            && self.backgrounds.is_empty()
+            && self.features.is_empty()
+            && self.spells.is_empty()
+            && self.effects.is_empty()
+    }
+
+    /// Namespaced spell list name, as it appears in the spells index.
+    pub fn spell_list_name(&self, list: &str) -> String {
+        format!("{}.{list}", self.id)
+    }
+
+    fn url(&self, kind: &str, name: &str) -> String {
+        format!("{PACK_URL_PREFIX}{}/{kind}/{name}", self.id)
+    }
+
+    fn namespace_spell_refs(&mut self) {
+        let id = self.id.clone();
+        for feature in self.features.values_mut() {
+            if let Some(spells) = &mut feature.spells
+                && let Some(list) = spells.list.ref_name()
+                && self.spells.contains_key(list)
+            {
+                let from = SpellList::ref_path(&format!("{id}.{list}"));
+                spells.list = SpellList::Ref { from };
+            }
+        }
+    }
+
+    /// Every non-namespaced name this pack adds, paired with its kind, for
+    /// conflict reporting.
+    pub fn provided_names(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        let classes = self.classes.keys().map(|n| ("class", &**n));
+        let species = self.species.keys().map(|n| ("species", &**n));
+        let backgrounds = self.backgrounds.keys().map(|n| ("background", &**n));
+        let features = self.features.keys().map(|n| ("feature", &**n));
+        let effects = self.effects.keys().map(|n| ("effect", &**n));
+        classes
+            .chain(species)
+            .chain(backgrounds)
+            .chain(features)
+            .chain(effects)
+    }
+
+    /// Whether `character` uses any of this pack's classes, species,
+    /// background or features.
+    pub fn is_used_by(&self, character: &Character) -> bool {
+        let identity = &character.identity;
+        identity
+            .classes
+            .iter()
+            .any(|cl| self.classes.contains_key(cl.class.as_str()))
+            || self.species.contains_key(identity.species.as_str())
+            || self.backgrounds.contains_key(identity.background.as_str())
+            || character
+                .features
+                .iter()
+                .any(|feature| self.features.contains_key(feature.name.as_str()))
+    }
+
+    fn locale(&self, locale: &str) -> Option<&PackLocale> {
+        self.locales.get(locale)
+    }
+
+    // ---- Localized views ----
+
+    pub fn localized_classes(&self, locale: &str) -> Vec<ClassDefinition> {
+        let overlay = self.locale(locale);
+        self.classes
+            .iter()
+            .map(|(name, def)| {
+                let mut def = def.clone();
+                if let Some(map) = overlay.and_then(|l| l.classes.get(name)) {
+                    locale::apply_class_locale(&mut def, map);
+                }
+                def
+            })
+            .collect()
+    }
+
+    pub fn localized_species(&self, locale: &str) -> Vec<SpeciesDefinition> {
+        let overlay = self.locale(locale);
+        self.species
+            .iter()
+            .map(|(name, def)| {
+                let mut def = def.clone();
+                if let Some(map) = overlay.and_then(|l| l.species.get(name)) {
+                    locale::apply_species_locale(&mut def, map);
+                }
+                def
+            })
+            .collect()
+    }
+
+    pub fn localized_backgrounds(&self, locale: &str) -> Vec<BackgroundDefinition> {
+        let overlay = self.locale(locale);
+        self.backgrounds
+            .iter()
+            .map(|(name, def)| {
+                let mut def = def.clone();
+                if let Some(map) = overlay.and_then(|l| l.backgrounds.get(name)) {
+                    locale::apply_background_locale(&mut def, map);
+                }
+                def
+            })
+            .collect()
+    }
+
+    /// Spell lists keyed by the cache path they are installed under.
+    pub fn localized_spell_lists(&self, locale: &str) -> Vec<(String, SpellMap)> {
+        let overlay = self.locale(locale);
+        self.spells
+            .iter()
+            .map(|(list, spells)| {
+                let mut spells = spells.clone();
+                if let Some(map) = overlay.and_then(|l| l.spells.get(list)) {
+                    locale::apply_spell_map_locale(&mut spells, map);
+                }
+                (SpellList::ref_path(&self.spell_list_name(list)), spells)
+            })
+            .collect()
+    }
+
+    pub fn localized_features(&self, locale: &str) -> FeaturesIndex {
+        let mut features = FeaturesIndex(self.features.clone());
+        if let Some(overlay) = self.locale(locale) {
+            locale::apply_features_locale(&mut features, &overlay.features);
+        }
+        features
+    }
+
+    pub fn localized_effects(&self, locale: &str) -> EffectsIndex {
+        let mut effects = EffectsIndex(self.effects.clone());
+        if let Some(overlay) = self.locale(locale) {
+            locale::apply_effects_locale(&mut effects, &overlay.effects);
+        }
+        effects
+    }
+
+    /// Index entries for this pack's content, localized for `locale`.
+    pub fn index(&self, locale: &str) -> Index {
+        let mut index = Index {
+            classes: self
+                .classes
+                .iter()
+                .map(|(name, def)| {
+                    let entry = ClassIndexEntry {
+                        name: name.to_string(),
+                        label: def.label.clone(),
+                        url: self.url("classes", name),
+                        description: def.description.clone(),
+                        prerequisites: None,
+                    };
+                    (name.clone(), entry)
+                })
+                .collect(),
+            species: self
+                .species
+                .iter()
+                .map(|(name, def)| {
+                    let entry = SpeciesIndexEntry {
+                        name: name.to_string(),
+                        label: def.label.clone(),
+                        url: self.url("species", name),
+                        description: def.description.clone(),
+                    };
+                    (name.clone(), entry)
+                })
+                .collect(),
+            backgrounds: self
+                .backgrounds
+                .iter()
+                .map(|(name, def)| {
+                    let entry = BackgroundIndexEntry {
+                        name: name.to_string(),
+                        label: def.label.clone(),
+                        url: self.url("backgrounds", name),
+                        description: def.description.clone(),
+                    };
+                    (name.clone(), entry)
+                })
+                .collect(),
+            spells: self
+                .spells
+                .keys()
+                .map(|list| {
+                    let entry = SpellIndexEntry {
+                        name: list.to_string(),
+                        label: None,
+                        url: String::new(),
+                    };
+                    (list.clone(), entry)
+                })
+                .collect(),
+        };
+        if let Some(overlay) = self.locale(locale) {
+            locale::apply_index_locale(&mut index, &overlay.index);
+        }
+        // Namespace spell lists only after the locale is applied, so pack
+        // authors can key translations by their short list names.
+        index.spells = std::mem::take(&mut index.spells)
+            .into_values()
+            .map(|mut entry| {
+                let name = self.spell_list_name(&entry.name);
+                entry.label.get_or_insert_with(|| entry.name.clone());
+                entry.url = SpellList::ref_path(&name);
+                entry.name = name.clone();
+                (name.into(), entry)
+            })
+            .collect();
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACK: &str = r#"{
+        "id": "grim-harvest",
+        "name": "Grim Harvest",
+        "features": [
+            {"name": "Reaper Spellcasting", "spells": {
+                "casting_ability": "Intelligence",
+                "list": {"from": "spells/reaper.json"}
+            }},
+            {"name": "Grave Touch", "description": "Necrotic touch."}
+        ],
+        "spells": {"reaper": [{"name": "Soul Sickle", "level": 1}]},
+        "locales": {
+            "ru": {
+                "index": {"spell.reaper": {"label": "Жнец"}},
+                "features": {"Grave Touch": {"label": "Касание могилы"}}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parse_namespaces_own_spell_lists() {
+        let pack = ContentPack::parse(PACK).unwrap();
+        let spells = pack.features["Reaper Spellcasting"]
+            .spells
+            .as_ref()
+            .unwrap();
+        assert!(matches!(
+            &spells.list,
+            SpellList::Ref { from } if from == "spells/grim-harvest.reaper.json"
+        ));
+
+        let lists = pack.localized_spell_lists("en");
+        assert_eq!(lists[0].0, "spells/grim-harvest.reaper.json");
+    }
+
+    #[test]
+    fn index_entries_are_localized_and_namespaced() {
+        let pack = ContentPack::parse(PACK).unwrap();
+        let index = pack.index("ru");
+        let entry = &index.spells["grim-harvest.reaper"];
+        assert_eq!(entry.label(), "Жнец");
+        assert_eq!(entry.url, "spells/grim-harvest.reaper.json");
+        assert_eq!(
+            pack.index("en").spells["grim-harvest.reaper"].label(),
+            "reaper"
+        );
+
+        let features = pack.localized_features("ru");
+        assert_eq!(features.0["Grave Touch"].label(), "Касание могилы");
+    }
+
+    #[test]
+    fn validate_rejects_bad_packs() {
+        let bad_id = PACK.replace("grim-harvest", "Grim Harvest");
+        assert!(ContentPack::parse(&bad_id).is_err());
+
+        let empty = r#"{"id": "empty", "name": "Empty"}"#;
+        assert!(ContentPack::parse(empty).is_err());
+
+        let bad_list = PACK.replace("\"reaper\": [", "\"re.aper\": [");
+        assert!(ContentPack::parse(&bad_list).is_err());
+    }
+}
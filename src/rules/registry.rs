@@ -6,10 +6,13 @@ use super::{
     background::BackgroundDefinition,
     cache::{DefinitionStore, FetchCache},
     class::ClassDefinition,
+    conditions::{ConditionDefinition, ConditionsIndex},
+    equipment::{EquipmentDefinition, EquipmentIndex, EquipmentKind},
     feature::{ChoiceOption, FeatureDefinition, FeaturesIndex, FieldKind},
     index::{BackgroundIndexEntry, ClassIndexEntry, Index, SpeciesIndexEntry, SpellIndexEntry},
     labels,
     locale::{self, LocaleMap, SpellLocaleMap},
+    pack::ContentPack,
     resolve,
     species::SpeciesDefinition,
     spells::{SpellDefinition, SpellList, SpellMap},
@@ -21,6 +24,7 @@ use crate::{
         ActiveEffect, Character, CharacterIdentity, ClassLevel, EffectsIndex, FeatureField,
         FeatureSource, FreeUses,
     },
+    vecset::VecSet,
 };
 
 // ---- DefinitionStore newtype wrappers ----
@@ -149,7 +153,10 @@ pub struct RulesRegistry {
     pub(super) background_cache: FetchCache<BackgroundDefinition>,
     spell_list_cache: FetchCache<SpellMap>,
     effects_index: LocalResource<Result<EffectsIndex, String>>,
+    conditions_index: LocalResource<Result<ConditionsIndex, String>>,
+    equipment_index: LocalResource<Result<EquipmentIndex, String>>,
     pub(super) features_index: LocalResource<Result<FeaturesIndex, String>>,
+    packs: RwSignal<Vec<ContentPack>>,
 }
 
 impl RulesRegistry {
@@ -196,9 +203,16 @@ impl RulesRegistry {
         // re-fetched when the language changes.
         let raw_index: RwSignal<Option<Index>> = RwSignal::new(None);
         let raw_effects: RwSignal<Option<EffectsIndex>> = RwSignal::new(None);
+        let packs: RwSignal<Vec<ContentPack>> = RwSignal::new(Vec::new());
 
         let class_index = LocalResource::new(move || {
             let current_locale = locale.get();
+            let pack_indexes: Vec<Index> = packs.with(|packs| {
+                packs
+                    .iter()
+                    .map(|pack| pack.index(&current_locale))
+                    .collect()
+            });
             let data_url = format!("{BASE_URL}/data/index.json");
             let locale_url = format!("{BASE_URL}/{current_locale}/index.json");
             async move {
@@ -219,12 +233,21 @@ impl RulesRegistry {
                 if let Ok(locale_map) = locale_result {
                     locale::apply_index_locale(&mut result, &locale_map);
                 }
+                for pack_index in pack_indexes {
+                    result.merge(pack_index);
+                }
                 Ok(result)
             }
         });
 
         let effects_index = LocalResource::new(move || {
             let current_locale = locale.get();
+            let pack_effects: Vec<EffectsIndex> = packs.with(|packs| {
+                packs
+                    .iter()
+                    .map(|pack| pack.localized_effects(&current_locale))
+                    .collect()
+            });
             let data_url = format!("{BASE_URL}/data/effects.json");
             let locale_url = format!("{BASE_URL}/{current_locale}/effects.json");
             async move {
@@ -245,6 +268,63 @@ impl RulesRegistry {
                 if let Ok(locale_map) = locale_result {
                     locale::apply_effects_locale(&mut result, &locale_map);
                 }
+                for (name, effect) in pack_effects.into_iter().flat_map(|idx| idx.0) {
+                    result.0.entry(name).or_insert(effect);
+                }
+                Ok(result)
+            }
+        });
+
+        let raw_conditions: RwSignal<Option<ConditionsIndex>> = RwSignal::new(None);
+        let conditions_index = LocalResource::new(move || {
+            let current_locale = locale.get();
+            let data_url = format!("{BASE_URL}/data/conditions.json");
+            let locale_url = format!("{BASE_URL}/{current_locale}/conditions.json");
+            async move {
+                let cached = raw_conditions.get_untracked();
+                let (conditions, locale_result) = if let Some(c) = cached {
+                    let lr = fetch_json::<locale::ConditionsLocaleMap>(&locale_url).await;
+                    (c, lr)
+                } else {
+                    let (dr, lr) = futures::join!(
+                        fetch_json::<ConditionsIndex>(&data_url),
+                        fetch_json::<locale::ConditionsLocaleMap>(&locale_url),
+                    );
+                    let c = dr?;
+                    raw_conditions.set(Some(c.clone()));
+                    (c, lr)
+                };
+                let mut result = conditions;
+                if let Ok(locale_map) = locale_result {
+                    locale::apply_conditions_locale(&mut result, &locale_map);
+                }
+                Ok(result)
+            }
+        });
+
+        let raw_equipment: RwSignal<Option<EquipmentIndex>> = RwSignal::new(None);
+        let equipment_index = LocalResource::new(move || {
+            let current_locale = locale.get();
+            let data_url = format!("{BASE_URL}/data/equipment.json");
+            let locale_url = format!("{BASE_URL}/{current_locale}/equipment.json");
+            async move {
+                let cached = raw_equipment.get_untracked();
+                let (equipment, locale_result) = if let Some(e) = cached {
+                    let lr = fetch_json::<locale::EquipmentLocaleMap>(&locale_url).await;
+                    (e, lr)
+                } else {
+                    let (dr, lr) = futures::join!(
+                        fetch_json::<EquipmentIndex>(&data_url),
+                        fetch_json::<locale::EquipmentLocaleMap>(&locale_url),
+                    );
+                    let e = dr?;
+                    raw_equipment.set(Some(e.clone()));
+                    (e, lr)
+                };
+                let mut result = equipment;
+                if let Ok(locale_map) = locale_result {
+                    locale::apply_equipment_locale(&mut result, &locale_map);
+                }
                 Ok(result)
             }
         });
@@ -252,6 +332,12 @@ impl RulesRegistry {
         let raw_features: RwSignal<Option<FeaturesIndex>> = RwSignal::new(None);
         let features_index = LocalResource::new(move || {
             let current_locale = locale.get();
+            let pack_features: Vec<FeaturesIndex> = packs.with(|packs| {
+                packs
+                    .iter()
+                    .map(|pack| pack.localized_features(&current_locale))
+                    .collect()
+            });
             let data_url = format!("{BASE_URL}/data/features.json");
             let locale_url = format!("{BASE_URL}/{current_locale}/features.json");
             async move {
@@ -272,6 +358,9 @@ impl RulesRegistry {
                 if let Ok(locale_map) = locale_result {
                     locale::apply_features_locale(&mut result, &locale_map);
                 }
+                for (name, feature) in pack_features.into_iter().flat_map(|idx| idx.0) {
+                    result.0.entry(name).or_insert(feature);
+                }
                 Ok(result)
             }
         });
@@ -320,15 +409,117 @@ impl RulesRegistry {
             spell_list_cache.notify();
         });
 
-        Self {
+        let registry = Self {
             locale,
             class_index,
             effects_index,
+            conditions_index,
+            equipment_index,
             features_index,
             class_cache,
             species_cache,
             background_cache,
             spell_list_cache,
+            packs,
+        };
+
+        // Pack definitions have no raw cache entries for the locale batch to
+        // reset, so re-localize them here when the language changes.
+        Effect::new(move |prev: Option<()>| {
+            let current = locale.get();
+            if prev.is_some() {
+                packs.with_untracked(|packs| {
+                    for pack in packs {
+                        registry.install_pack_definitions(pack, &current);
+                    }
+                });
+            }
+        });
+
+        registry
+    }
+
+    // ---- Content packs ----
+
+    pub fn with_packs<R>(&self, f: impl FnOnce(&[ContentPack]) -> R) -> R {
+        self.packs.with(|packs| f(packs))
+    }
+
+    /// Replace the installed packs, swapping their definitions in the caches
+    /// before the indexes pick up the new entries.
+    pub fn set_packs(&self, packs: Vec<ContentPack>) {
+        self.packs.with_untracked(|old| {
+            for pack in old {
+                self.uninstall_pack_definitions(pack);
+            }
+        });
+        let locale = self.locale.get_untracked();
+        for pack in &packs {
+            self.install_pack_definitions(pack, &locale);
+        }
+        self.packs.set(packs);
+    }
+
+    /// Names in `pack` already taken by bundled content or another installed
+    /// pack. Re-importing a pack with the same id only conflicts on names its
+    /// previous version did not provide.
+    pub fn pack_conflicts(&self, pack: &ContentPack) -> Vec<String> {
+        let packs = self.packs.read_untracked();
+        let previous = packs.iter().find(|installed| installed.id == pack.id);
+        let index_guard = self.class_index.read_untracked();
+        let index = index_guard.as_ref().and_then(|r| r.as_ref().ok());
+        let features_guard = self.features_index.read_untracked();
+        let features = features_guard.as_ref().and_then(|r| r.as_ref().ok());
+        let effects_guard = self.effects_index.read_untracked();
+        let effects = effects_guard.as_ref().and_then(|r| r.as_ref().ok());
+
+        pack.provided_names()
+            .filter(|&(kind, name)| {
+                let taken = match kind {
+                    "class" => index.is_some_and(|idx| idx.classes.contains_key(name)),
+                    "species" => index.is_some_and(|idx| idx.species.contains_key(name)),
+                    "background" => index.is_some_and(|idx| idx.backgrounds.contains_key(name)),
+                    "feature" => features.is_some_and(|idx| idx.0.contains_key(name)),
+                    "effect" => effects.is_some_and(|idx| idx.0.contains_key(name)),
+                    _ => false,
+                };
+                taken
+                    && !previous.is_some_and(|previous| {
+                        previous.provided_names().any(|entry| entry == (kind, name))
+                    })
+            })
+            .map(|(kind, name)| format!("{kind} {name}"))
+            .collect()
+    }
+
+    fn install_pack_definitions(&self, pack: &ContentPack, locale: &str) {
+        for def in pack.localized_classes(locale) {
+            self.class_cache.insert(&def.name.clone(), def);
+        }
+        for def in pack.localized_species(locale) {
+            self.species_cache.insert(&def.name.clone(), def);
+        }
+        for def in pack.localized_backgrounds(locale) {
+            self.background_cache.insert(&def.name.clone(), def);
+        }
+        for (path, spells) in pack.localized_spell_lists(locale) {
+            self.spell_list_cache.insert(&path, spells);
+        }
+    }
+
+    fn uninstall_pack_definitions(&self, pack: &ContentPack) {
+        for name in pack.classes.keys() {
+            self.class_cache.remove(name);
+        }
+        for name in pack.species.keys() {
+            self.species_cache.remove(name);
+        }
+        for name in pack.backgrounds.keys() {
+            self.background_cache.remove(name);
+        }
+        for list in pack.spells.keys() {
+            self.spell_list_cache
+                .remove(&SpellList::ref_path(&pack.spell_list_name(list)));
         }
     }
 
@@ -432,6 +623,37 @@ impl RulesRegistry {
         f(index.map_or(&EMPTY, |idx| &idx.0))
     }
 
+    // ---- Conditions ----
+
+    pub fn with_conditions_index<R>(
+        &self,
+        f: impl FnOnce(&BTreeMap<Box<str>, ConditionDefinition>) -> R,
+    ) -> R {
+        static EMPTY: BTreeMap<Box<str>, ConditionDefinition> = BTreeMap::new();
+        let guard = self.conditions_index.read();
+        let index: Option<&ConditionsIndex> = guard.as_ref().and_then(|r| r.as_ref().ok());
+        f(index.map_or(&EMPTY, |idx| &idx.0))
+    }
+
+    // ---- Equipment catalog ----
+
+    pub fn with_equipment_index<R>(
+        &self,
+        f: impl FnOnce(&BTreeMap<Box<str>, EquipmentDefinition>) -> R,
+    ) -> R {
+        static EMPTY: BTreeMap<Box<str>, EquipmentDefinition> = BTreeMap::new();
+        let guard = self.equipment_index.read();
+        let index: Option<&EquipmentIndex> = guard.as_ref().and_then(|r| r.as_ref().ok());
+        f(index.map_or(&EMPTY, |idx| &idx.0))
+    }
+
+    /// Look up a catalog entry of `kind` by label, without tracking.
+    pub fn find_equipment(&self, kind: EquipmentKind, label: &str) -> Option<EquipmentDefinition> {
+        let guard = self.equipment_index.read_untracked();
+        let index = guard.as_ref()?.as_ref().ok()?;
+        index.find_localized(kind, label)
+    }
+
     // ---- Features index ----
 
     pub fn with_features_index<R>(
@@ -632,6 +854,7 @@ impl RulesRegistry {
     /// with tracked reads so the calling Effect re-runs when definitions
     /// arrive or locale changes.
     pub fn fill_from_registry(&self, character: &mut Character) {
+        self.record_packs(character);
         let class_cache = self.class_cache.read();
 
         // Fix Feature.source levels from class definitions (corrects
@@ -693,6 +916,39 @@ impl RulesRegistry {
         });
     }
 
+    /// Record which installed packs the character depends on. Recorded packs
+    /// that are no longer installed are kept so the UI can warn about them.
+    fn record_packs(&self, character: &mut Character) {
+        let packs = self.packs.read();
+        let mut recorded: VecSet<String> = character
+            .packs
+            .iter()
+            .filter(|id| !packs.iter().any(|pack| pack.id == **id))
+            .cloned()
+            .collect();
+        recorded.extend(
+            packs
+                .iter()
+                .filter(|pack| pack.is_used_by(character))
+                .map(|pack| pack.id.clone()),
+        );
+        if recorded != character.packs {
+            character.packs = recorded;
+        }
+    }
+
+    /// Recorded packs of `character` that are not installed.
+    pub fn missing_packs(&self, character: &Character) -> Vec<String> {
+        self.packs.with(|packs| {
+            character
+                .packs
+                .iter()
+                .filter(|id| !packs.iter().any(|pack| pack.id == **id))
+                .cloned()
+                .collect()
+        })
+    }
+
     pub fn clear_from_registry(&self, character: &mut Character) {
         let class_cache = self.class_cache.read_untracked();
         self.with_features_index_untracked(|features_index| {
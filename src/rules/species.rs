@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use crate::vecset::VecSet;
+use crate::{demap::Named, vecset::VecSet};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct SpeciesDefinition {
@@ -18,3 +18,9 @@ impl SpeciesDefinition {
         self.label.as_deref().unwrap_or(&self.name)
     }
 }
+
+impl Named for SpeciesDefinition {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
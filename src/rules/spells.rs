@@ -5,7 +5,11 @@ use serde::Deserialize;
 use super::utils::LevelRules;
 use crate::{
     demap::{self, Named},
-    model::{Ability, Character, EffectDefinition, FreeUses, Spell, SpellData, SpellSlotPool},
+    model::{
+        Ability, CastingTime, Character, EffectDefinition, FreeUses, Spell, SpellComponent,
+        SpellData, SpellRange, SpellSchool, SpellSlotPool,
+    },
+    vecset::VecSet,
 };
 
 #[derive(Debug, Clone, Deserialize)]
@@ -26,6 +30,31 @@ pub struct SpellDefinition {
     #[serde(default)]
     pub ritual: bool,
     #[serde(default)]
+    pub concentration: bool,
+    #[serde(default)]
+    pub school: Option<SpellSchool>,
+    #[serde(default)]
+    pub casting_time: Option<CastingTime>,
+    #[serde(default)]
+    pub range: Option<SpellRange>,
+    #[serde(default)]
+    pub components: VecSet<SpellComponent>,
+    /// Price in gold pieces of a material component the spell consumes or
+    /// requires; 0 when the components carry no cost.
+    #[serde(default)]
+    pub material_cost: u32,
+    /// Localized wording of the casting time, e.g. "1 minute" or "Reaction,
+    /// which you take when...".
+    #[serde(default)]
+    pub casting_time_text: Option<String>,
+    #[serde(default)]
+    pub range_text: Option<String>,
+    #[serde(default)]
+    pub duration: Option<String>,
+    /// Localized list of material components.
+    #[serde(default)]
+    pub materials: Option<String>,
+    #[serde(default)]
     pub effects: Vec<EffectDefinition>,
 }
 
@@ -33,6 +62,19 @@ impl SpellDefinition {
     pub fn label(&self) -> &str {
         self.label.as_deref().unwrap_or(&self.name)
     }
+
+    /// Effects produced by casting the spell, inheriting the spell's
+    /// concentration requirement.
+    pub fn cast_effects(&self) -> Vec<EffectDefinition> {
+        self.effects
+            .iter()
+            .cloned()
+            .map(|mut effect| {
+                effect.concentration |= self.concentration;
+                effect
+            })
+            .collect()
+    }
 }
 
 impl Named for SpellDefinition {
@@ -54,9 +96,31 @@ pub struct SpellsDefinition {
     pub levels: LevelRules<SpellLevelRules>,
     #[serde(default)]
     pub cost: Option<String>,
+    /// The caster prepares spells: the `spells` counts of the level table
+    /// are their prepared spells, changed only after a long rest. Other
+    /// casters change their spells on level-up.
+    #[serde(default)]
+    pub prepares: bool,
+    /// Known spells form a spellbook: besides those gained on level-up, spells
+    /// from the class list found in the world can be copied in for gold and
+    /// time.
+    #[serde(default)]
+    pub spellbook: bool,
 }
 
 impl SpellsDefinition {
+    /// How many leveled spells a preparing caster of `level` has prepared at
+    /// once.
+    pub fn prepare_limit(&self, level: u32) -> Option<u32> {
+        if !self.prepares {
+            return None;
+        }
+        self.levels
+            .range(..=level)
+            .rev()
+            .find_map(|(_, rules)| rules.spells)
+    }
+
     /// Ensure SpellData exists on the feature_data entry and update spell
     /// slots.
     pub fn apply(
@@ -167,6 +231,7 @@ impl SpellsDefinition {
                         sticky: true,
                         cost: s.cost,
                         free_uses,
+                        copied: None,
                     });
                 }
             }
@@ -270,6 +335,25 @@ mod tests {
             .unwrap_or_else(|e| panic!("failed to parse {path}: {e}"))
     }
 
+    #[test]
+    fn prepare_limit_follows_level_table() {
+        let def: SpellsDefinition = serde_json::from_str(
+            r#"{"casting_ability": 4,
+                "levels": {"1": {"spells": 2, "slots": [2]}, "2": {"slots": [3]},
+                "5": {"spells": 9}}, "prepares": true}"#,
+        )
+        .unwrap();
+        assert_eq!(def.prepare_limit(1), Some(2));
+        // Levels that only change slots keep the previous count
+        assert_eq!(def.prepare_limit(2), Some(2));
+        assert_eq!(def.prepare_limit(7), Some(9));
+
+        let known: SpellsDefinition =
+            serde_json::from_str(r#"{"casting_ability": 5, "levels": {"1": {"spells": 4}}}"#)
+                .unwrap();
+        assert_eq!(known.prepare_limit(1), None);
+    }
+
     #[test]
     fn parse_expr_with_mul_dice() {
         use crate::{expr::Expr, model::Attribute};
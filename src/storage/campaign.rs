@@ -0,0 +1,347 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use gloo_storage::{LocalStorage, Storage};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    firebase::{self, ChangeType, FirebaseError, Subscription, WhereClause},
+    model::{ActiveEffects, Attribute, Character, Skill, SpellSlotPool},
+    storage::{local, migrate},
+};
+
+const CAMPAIGNS_KEY: &str = "dnd_pc_campaigns";
+
+/// Letters and digits that can't be mistaken for one another when an invite
+/// code is read out loud or copied by hand.
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LEN: usize = 6;
+
+#[derive(Debug)]
+pub enum CampaignError {
+    NotSignedIn,
+    NotFound,
+    Firebase(FirebaseError),
+}
+
+impl std::fmt::Display for CampaignError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotSignedIn => write!(f, "Not signed in"),
+            Self::NotFound => write!(f, "No campaign with this invite code"),
+            Self::Firebase(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl From<FirebaseError> for CampaignError {
+    fn from(error: FirebaseError) -> Self {
+        Self::Firebase(error)
+    }
+}
+
+/// Firestore document `campaigns/{code}`. The invite code doubles as the
+/// document id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Campaign {
+    pub code: String,
+    pub name: String,
+    pub gm_uid: String,
+}
+
+/// Roster entry `campaigns/{code}/members/{char_id}`, written by the player
+/// joining with a character.
+#[derive(Serialize, Deserialize)]
+struct CampaignMember {
+    uid: String,
+    char_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartySlots {
+    pub pool: SpellSlotPool,
+    pub level: u32,
+    pub used: u32,
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartyCondition {
+    pub name: String,
+    pub level: u32,
+}
+
+/// What the GM dashboard shows for a character. Active effects are kept on
+/// the player's device only, so the player publishes their result alongside
+/// the character in the cloud.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PartyStatus {
+    pub name: String,
+    pub class_summary: String,
+    pub ac: i32,
+    pub hp: i32,
+    pub hp_max: i32,
+    pub hp_temp: i32,
+    pub passive_perception: i32,
+    #[serde(default)]
+    pub spell_slots: Vec<PartySlots>,
+    #[serde(default)]
+    pub conditions: Vec<PartyCondition>,
+    #[serde(default)]
+    pub effects: Vec<String>,
+}
+
+impl PartyStatus {
+    pub fn new(character: &Character, effects: &ActiveEffects) -> Self {
+        let resolve = |attr| effects.resolve(character, attr);
+        Self {
+            name: character.identity.name.clone(),
+            class_summary: character.class_summary(),
+            ac: resolve(Attribute::Ac),
+            hp: resolve(Attribute::Hp),
+            hp_max: resolve(Attribute::MaxHp),
+            hp_temp: resolve(Attribute::TempHp),
+            passive_perception: 10 + resolve(Attribute::Skill(Skill::Perception)),
+            spell_slots: character
+                .active_pools()
+                .flat_map(|pool| {
+                    character
+                        .all_spell_slots_for_pool(pool)
+                        .filter(|(_, slot)| slot.total > 0)
+                        .map(move |(level, slot)| PartySlots {
+                            pool,
+                            level,
+                            used: slot.used,
+                            total: slot.total,
+                        })
+                })
+                .collect(),
+            conditions: effects
+                .conditions()
+                .map(|(name, level)| PartyCondition {
+                    name: name.to_string(),
+                    level,
+                })
+                .collect(),
+            effects: effects
+                .effects()
+                .iter()
+                .filter(|effect| effect.enabled)
+                .map(|effect| effect.label().to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Party status of a locally stored character, with its active effects.
+pub(super) fn local_party_status(id: &Uuid) -> Option<PartyStatus> {
+    let character = local::load_character(id)?;
+    let mut effects = local::load_effects(id);
+    effects.recompute(&character);
+    Some(PartyStatus::new(&character, &effects))
+}
+
+fn generate_code() -> String {
+    Uuid::new_v4()
+        .as_bytes()
+        .iter()
+        .take(CODE_LEN)
+        .map(|byte| CODE_ALPHABET[*byte as usize % CODE_ALPHABET.len()] as char)
+        .collect()
+}
+
+/// Normalize a typed invite code: trimmed and upper case.
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
+
+/// Campaigns this device created, newest first.
+pub fn load_campaigns() -> Vec<Campaign> {
+    LocalStorage::get(CAMPAIGNS_KEY).unwrap_or_default()
+}
+
+fn save_campaigns(campaigns: &[Campaign]) {
+    if let Err(error) = LocalStorage::set(CAMPAIGNS_KEY, campaigns) {
+        log::error!("Failed to save campaigns: {error}");
+    }
+}
+
+pub async fn create_campaign(name: String) -> Result<Campaign, CampaignError> {
+    let gm_uid = firebase::current_uid().ok_or(CampaignError::NotSignedIn)?;
+    let mut code = generate_code();
+    while firebase::get_doc::<Campaign>(&["campaigns", &code])
+        .await?
+        .is_some()
+    {
+        code = generate_code();
+    }
+    let campaign = Campaign { code, name, gm_uid };
+    firebase::set_doc(&campaign, &["campaigns", &campaign.code]).await?;
+
+    let mut campaigns = load_campaigns();
+    campaigns.insert(0, campaign.clone());
+    save_campaigns(&campaigns);
+    Ok(campaign)
+}
+
+/// Forget a campaign on this device. The cloud document stays, so members
+/// keep their characters' campaign code.
+pub fn forget_campaign(code: &str) {
+    let mut campaigns = load_campaigns();
+    campaigns.retain(|campaign| campaign.code != code);
+    save_campaigns(&campaigns);
+}
+
+/// Add a character to the roster of the campaign with the given invite
+/// code. The caller sets `Character::campaign`, which grants the GM read
+/// access once synced.
+pub async fn join_campaign(code: &str, char_id: Uuid) -> Result<Campaign, CampaignError> {
+    let uid = firebase::current_uid().ok_or(CampaignError::NotSignedIn)?;
+    let campaign = firebase::get_doc::<Campaign>(&["campaigns", code])
+        .await?
+        .ok_or(CampaignError::NotFound)?;
+    let member = CampaignMember { uid, char_id };
+    firebase::set_doc(
+        &member,
+        &["campaigns", code, "members", &char_id.to_string()],
+    )
+    .await?;
+    Ok(campaign)
+}
+
+pub async fn leave_campaign(code: &str, char_id: Uuid) -> Result<(), CampaignError> {
+    firebase::delete_doc(&["campaigns", code, "members", &char_id.to_string()]).await?;
+    Ok(())
+}
+
+pub async fn load_campaign(code: &str) -> Result<Option<Campaign>, CampaignError> {
+    Ok(firebase::get_doc(&["campaigns", code]).await?)
+}
+
+/// Live subscriptions behind the GM dashboard: the campaign roster plus one
+/// query per member for their characters in the campaign. Dropping it
+/// unsubscribes from everything.
+pub struct PartySubscription {
+    _roster: Subscription,
+    _members: Rc<RefCell<HashMap<String, Subscription>>>,
+}
+
+/// Keep `party` up to date with the status of every character in the
+/// campaign.
+pub fn subscribe_party(
+    code: &str,
+    party: RwSignal<Vec<(Uuid, PartyStatus)>>,
+) -> Result<PartySubscription, FirebaseError> {
+    let members: Rc<RefCell<HashMap<String, Subscription>>> = Rc::default();
+    let code = code.to_string();
+
+    let roster = {
+        let members = members.clone();
+        let member_code = code.clone();
+        firebase::subscribe_collection(&["campaigns", &code, "members"], &[], move |changes| {
+            for change in changes {
+                let Ok(member) = serde_json::from_value::<CampaignMember>(change.data) else {
+                    continue;
+                };
+                match change.change_type {
+                    ChangeType::Added | ChangeType::Modified => {
+                        if members.borrow().contains_key(&member.uid) {
+                            continue;
+                        }
+                        match subscribe_member(&member_code, &member.uid, party) {
+                            Ok(subscription) => {
+                                members.borrow_mut().insert(member.uid, subscription);
+                            }
+                            Err(error) => {
+                                log::warn!("Failed to subscribe to {}: {error}", member.uid)
+                            }
+                        }
+                    }
+                    ChangeType::Removed => {
+                        // Dropping the member's query unsubscribes from it
+                        members.borrow_mut().remove(&member.uid);
+                        party.update(|party| party.retain(|(id, _)| *id != member.char_id));
+                    }
+                }
+            }
+        })?
+    };
+
+    Ok(PartySubscription {
+        _roster: roster,
+        _members: members,
+    })
+}
+
+fn subscribe_member(
+    code: &str,
+    uid: &str,
+    party: RwSignal<Vec<(Uuid, PartyStatus)>>,
+) -> Result<Subscription, FirebaseError> {
+    firebase::subscribe_collection(
+        &["users", uid, "characters"],
+        &[WhereClause("campaign", "==", JsValue::from_str(code))],
+        move |changes| {
+            party.update(|party| {
+                for change in changes {
+                    let Ok(id) = change.id.parse::<Uuid>() else {
+                        continue;
+                    };
+                    party.retain(|(member_id, _)| *member_id != id);
+                    if matches!(change.change_type, ChangeType::Removed) {
+                        continue;
+                    }
+                    let published = change
+                        .data
+                        .get(super::live::PARTY_STATUS_FIELD)
+                        .and_then(|status| serde_json::from_value(status.clone()).ok());
+                    let status = match published {
+                        Some(status) => status,
+                        None => match migrate::deserialize_character_value(change.data) {
+                            Ok(character) => {
+                                let mut effects = ActiveEffects::default();
+                                effects.recompute(&character);
+                                PartyStatus::new(&character, &effects)
+                            }
+                            Err(error) => {
+                                log::warn!("Failed to read party character {id}: {error}");
+                                continue;
+                            }
+                        },
+                    };
+                    party.push((id, status));
+                }
+                party.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+            });
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn party_status_reads_character() {
+        let character = Character::test_character();
+        let status = PartyStatus::new(&character, &ActiveEffects::default());
+        assert_eq!(status.name, character.identity.name);
+        assert_eq!(status.ac, character.armor_class() as i32);
+        assert_eq!(status.hp_max, character.hp_max() as i32);
+        assert_eq!(
+            status.passive_perception,
+            10 + character.skill_bonus(Skill::Perception)
+        );
+        assert!(status.spell_slots.iter().all(|slots| slots.total > 0));
+    }
+
+    #[test]
+    fn invite_codes_use_unambiguous_alphabet() {
+        let code = generate_code();
+        assert_eq!(code.len(), CODE_LEN);
+        assert!(code.bytes().all(|byte| CODE_ALPHABET.contains(&byte)));
+        assert_eq!(normalize_code(&format!(" {} ", code.to_lowercase())), code);
+    }
+}
@@ -0,0 +1,194 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    model::{Character, now_epoch_secs},
+    storage::migrate,
+};
+
+/// Automatic snapshots kept per character; named checkpoints don't count.
+const MAX_AUTO_SNAPSHOTS: usize = 20;
+
+fn history_key(id: &Uuid) -> String {
+    format!("dnd_pc_history_{id}")
+}
+
+/// What prompted a snapshot. The snapshot holds the character as it was
+/// right before that event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotReason {
+    LevelUp,
+    LongRest,
+    Replay,
+    Reset,
+    Import,
+    Restore,
+    Checkpoint,
+}
+
+impl SnapshotReason {
+    pub fn tr_key(self) -> &'static str {
+        match self {
+            Self::LevelUp => "history-level-up",
+            Self::LongRest => "history-long-rest",
+            Self::Replay => "history-replay",
+            Self::Reset => "history-reset",
+            Self::Import => "history-import",
+            Self::Restore => "history-restore",
+            Self::Checkpoint => "history-checkpoint",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: Uuid,
+    pub taken_at: u64,
+    pub reason: SnapshotReason,
+    /// Name given to a manual checkpoint.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    /// Base64 of the deflated character JSON. JSON keeps its schema version,
+    /// so snapshots from older app versions are migrated on restore.
+    data: String,
+}
+
+impl Snapshot {
+    fn new(
+        character: &Character,
+        reason: SnapshotReason,
+        name: String,
+        taken_at: u64,
+    ) -> Option<Self> {
+        let json = serde_json::to_vec(character)
+            .inspect_err(|error| log::error!("Failed to encode snapshot: {error}"))
+            .ok()?;
+        let bytes = miniz_oxide::deflate::compress_to_vec(&json, 6);
+        Some(Self {
+            id: Uuid::new_v4(),
+            taken_at,
+            reason,
+            name,
+            data: STANDARD.encode(bytes),
+        })
+    }
+
+    pub fn is_checkpoint(&self) -> bool {
+        self.reason == SnapshotReason::Checkpoint
+    }
+
+    /// The character stored in the snapshot, migrated to the current
+    /// schema. `None` if the data is unreadable.
+    pub fn character(&self) -> Option<Character> {
+        let bytes = STANDARD.decode(&self.data).ok()?;
+        let json = miniz_oxide::inflate::decompress_to_vec(&bytes)
+            .inspect_err(|error| log::warn!("Failed to inflate snapshot {}: {error}", self.id))
+            .ok()?;
+        let value = serde_json::from_slice(&json)
+            .inspect_err(|error| log::warn!("Failed to parse snapshot {}: {error}", self.id))
+            .ok()?;
+        migrate::deserialize_character_value(value)
+            .inspect_err(|error| log::warn!("Failed to decode snapshot {}: {error}", self.id))
+            .ok()
+    }
+}
+
+/// Snapshots of a character, newest first.
+pub fn load_history(id: &Uuid) -> Vec<Snapshot> {
+    LocalStorage::get(history_key(id)).unwrap_or_default()
+}
+
+fn save_history(id: &Uuid, history: &[Snapshot]) {
+    if let Err(error) = LocalStorage::set(history_key(id), history) {
+        log::error!("Failed to save character history: {error}");
+    }
+}
+
+/// Record the character before an event that may be hard to undo. Skipped
+/// when nothing changed since the newest snapshot; only the latest
+/// [`MAX_AUTO_SNAPSHOTS`] automatic snapshots are kept.
+pub fn snapshot_character(character: &Character, reason: SnapshotReason) {
+    let Some(snapshot) = Snapshot::new(character, reason, String::new(), now_epoch_secs()) else {
+        return;
+    };
+    let mut history = load_history(&character.id);
+    if history
+        .first()
+        .is_some_and(|newest| newest.data == snapshot.data)
+    {
+        return;
+    }
+    history.insert(0, snapshot);
+
+    let mut autos = 0;
+    history.retain(|snapshot| {
+        if snapshot.is_checkpoint() {
+            return true;
+        }
+        autos += 1;
+        autos <= MAX_AUTO_SNAPSHOTS
+    });
+    save_history(&character.id, &history);
+}
+
+/// Record a named checkpoint, kept until deleted by hand.
+pub fn save_checkpoint(character: &Character, name: String) {
+    let Some(snapshot) = Snapshot::new(
+        character,
+        SnapshotReason::Checkpoint,
+        name,
+        now_epoch_secs(),
+    ) else {
+        return;
+    };
+    let mut history = load_history(&character.id);
+    history.insert(0, snapshot);
+    save_history(&character.id, &history);
+}
+
+pub fn delete_snapshot(char_id: &Uuid, snapshot_id: &Uuid) {
+    let mut history = load_history(char_id);
+    history.retain(|snapshot| snapshot.id != *snapshot_id);
+    save_history(char_id, &history);
+}
+
+pub(super) fn delete_history(id: &Uuid) {
+    LocalStorage::delete(history_key(id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_roundtrips_character() {
+        let character = Character::test_character();
+        let snapshot =
+            Snapshot::new(&character, SnapshotReason::LevelUp, String::new(), 0).unwrap();
+        let restored = snapshot.character().unwrap();
+        assert_eq!(restored.id, character.id);
+        assert_eq!(restored.identity.name, character.identity.name);
+        assert_eq!(restored.combat.hp_max, character.combat.hp_max);
+    }
+
+    #[test]
+    fn snapshot_survives_added_fields() {
+        let character = Character::test_character();
+        let mut value = serde_json::to_value(&character).unwrap();
+        // A field added to the model after the snapshot was taken.
+        value.as_object_mut().unwrap().remove("campaign");
+        let json = serde_json::to_vec(&value).unwrap();
+        let snapshot = Snapshot {
+            id: Uuid::new_v4(),
+            taken_at: 0,
+            reason: SnapshotReason::LevelUp,
+            name: String::new(),
+            data: STANDARD.encode(miniz_oxide::deflate::compress_to_vec(&json, 6)),
+        };
+        let restored = snapshot.character().unwrap();
+        assert_eq!(restored.id, character.id);
+        assert_eq!(restored.campaign, None);
+    }
+}
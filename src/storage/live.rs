@@ -0,0 +1,83 @@
+use crate::{
+    firebase::{self, FirebaseError, Subscription},
+    model::{ActiveEffects, Character},
+    storage::{campaign, local, migrate},
+};
+
+/// Cloud field with the owner's active effects, JSON-encoded: Firestore
+/// can't hold the nested arrays of effect expressions.
+const EFFECTS_FIELD: &str = "active_effects";
+
+/// Cloud field with the effective status shown on the GM dashboard.
+pub(super) const PARTY_STATUS_FIELD: &str = "party_status";
+
+/// Add what others following the character need to a document about to be
+/// pushed. Active effects are kept on this device only, so shared and
+/// campaign characters carry them (and, in a campaign, the effective party
+/// status) along.
+pub(super) fn attach_published_state(json: &mut serde_json::Value, id: &uuid::Uuid) {
+    let in_campaign = json["campaign"].is_string();
+    if in_campaign
+        && let Some(status) = campaign::local_party_status(id)
+        && let Ok(status) = serde_json::to_value(status)
+    {
+        json[PARTY_STATUS_FIELD] = status;
+    }
+    if in_campaign || json["shared"] == true {
+        match serde_json::to_string(&local::load_effects(id)) {
+            Ok(effects) => json[EFFECTS_FIELD] = effects.into(),
+            Err(error) => log::warn!("Failed to encode effects of {id}: {error}"),
+        }
+    }
+}
+
+/// The shared character in a cloud document with its published effects,
+/// `None` if it's not shared (any more) or unreadable.
+fn read_shared_character(mut value: serde_json::Value) -> Option<(Character, ActiveEffects)> {
+    let effects = value
+        .as_object_mut()
+        .and_then(|fields| fields.remove(EFFECTS_FIELD));
+    let character = migrate::deserialize_character_value(value)
+        .inspect_err(|error| log::error!("Failed to load shared character: {error}"))
+        .ok()
+        .filter(|character| character.shared)?;
+    let mut effects = effects
+        .and_then(|effects| serde_json::from_str::<ActiveEffects>(effects.as_str()?).ok())
+        .unwrap_or_default();
+    effects.recompute(&character);
+    Some((character, effects))
+}
+
+/// Follow a shared character as its owner plays. `on_change` gets `None`
+/// once the character is deleted or no longer shared.
+pub fn subscribe_shared_character(
+    user_id: &str,
+    char_id: &str,
+    on_change: impl Fn(Option<(Character, ActiveEffects)>) + 'static,
+) -> Result<Subscription, FirebaseError> {
+    firebase::subscribe_doc(&["users", user_id, "characters", char_id], move |value| {
+        on_change(value.and_then(read_shared_character));
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_character_carries_published_effects() {
+        let mut character = Character::test_character();
+        character.shared = true;
+        let mut value = serde_json::to_value(&character).unwrap();
+        value[EFFECTS_FIELD] = serde_json::to_string(&ActiveEffects::default())
+            .unwrap()
+            .into();
+
+        let (live, _) = read_shared_character(value.clone()).unwrap();
+        assert_eq!(live.id, character.id);
+        assert_eq!(live.identity.name, character.identity.name);
+
+        value["shared"] = false.into();
+        assert!(read_shared_character(value).is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::BTreeMap};
 
 use gloo_storage::{LocalStorage, Storage};
 use uuid::Uuid;
@@ -6,7 +6,8 @@ use wasm_bindgen::{JsCast, prelude::*};
 
 use crate::{
     ai::{AiSettings, Story},
-    model::{ActiveEffects, Character, CharacterIndex, CharacterSummary},
+    model::{ActiveEffects, Character, CharacterIndex, CharacterSummary, RollLog},
+    rules::ContentPack,
     storage::migrate::deserialize_character_value,
 };
 
@@ -20,10 +21,26 @@ fn effects_key(id: &Uuid) -> String {
     format!("dnd_pc_effects_{id}")
 }
 
+fn rolls_key(id: &Uuid) -> String {
+    format!("dnd_pc_rolls_{id}")
+}
+
 pub fn stories_key(id: &Uuid) -> String {
     format!("dnd_pc_stories_{id}")
 }
 
+/// Copy of the character as last synced with the cloud, the common base for
+/// three-way merges.
+fn sync_base_key(id: &Uuid) -> String {
+    format!("dnd_pc_base_{id}")
+}
+
+/// Cloud copy of a character whose merge with the local one needs manual
+/// resolution.
+fn sync_conflict_key(id: &Uuid) -> String {
+    format!("dnd_pc_conflict_{id}")
+}
+
 thread_local! {
     /// Cached character index to avoid repeated localStorage round-trips on every
     /// save. Lazily populated on first access; kept in sync with localStorage.
@@ -40,6 +57,16 @@ pub fn save_effects(id: &Uuid, effects: &ActiveEffects) {
     }
 }
 
+pub fn load_rolls(id: &Uuid) -> RollLog {
+    LocalStorage::get(rolls_key(id)).unwrap_or_default()
+}
+
+pub fn save_rolls(id: &Uuid, rolls: &RollLog) {
+    if let Err(error) = LocalStorage::set(rolls_key(id), rolls) {
+        log::error!("Failed to save rolls: {error}");
+    }
+}
+
 pub fn load_index() -> CharacterIndex {
     INDEX_CACHE.with(|cell| {
         let mut cache = cell.borrow_mut();
@@ -77,14 +104,52 @@ fn upsert_index_entry(index: &mut CharacterIndex, summary: CharacterSummary) {
 
 /// Update the index with a summary and persist to localStorage.
 pub fn load_character(id: &Uuid) -> Option<Character> {
-    let key = character_key(id);
-    if let Ok(character) = LocalStorage::get::<Character>(&key) {
-        return Some(character);
-    }
-    // Fallback: migrate legacy format
-    let raw = LocalStorage::raw().get_item(&key).ok()??;
+    load_character_at(&character_key(id))
+}
+
+fn load_character_at(key: &str) -> Option<Character> {
+    let raw = LocalStorage::raw().get_item(key).ok()??;
     let value: serde_json::Value = serde_json::from_str(&raw).ok()?;
     deserialize_character_value(value)
+        .inspect_err(|error| log::error!("Failed to load {key}: {error}"))
+        .ok()
+}
+
+pub(super) fn load_sync_base(id: &Uuid) -> Option<Character> {
+    load_character_at(&sync_base_key(id))
+}
+
+/// Record the JSON of a character as just pushed to or pulled from the cloud.
+pub(super) fn save_sync_base_raw(id: &Uuid, raw: &str) {
+    if let Err(error) = LocalStorage::raw().set_item(&sync_base_key(id), raw) {
+        log::error!("Failed to save sync base: {error:?}");
+    }
+}
+
+pub(super) fn save_sync_base(character: &Character) {
+    if let Err(error) = LocalStorage::set(sync_base_key(&character.id), character) {
+        log::error!("Failed to save sync base: {error}");
+    }
+}
+
+pub fn has_sync_conflict(id: &Uuid) -> bool {
+    LocalStorage::raw()
+        .get_item(&sync_conflict_key(id))
+        .is_ok_and(|raw| raw.is_some())
+}
+
+pub(super) fn load_sync_conflict(id: &Uuid) -> Option<Character> {
+    load_character_at(&sync_conflict_key(id))
+}
+
+pub(super) fn save_sync_conflict(remote: &Character) {
+    if let Err(error) = LocalStorage::set(sync_conflict_key(&remote.id), remote) {
+        log::error!("Failed to save sync conflict: {error}");
+    }
+}
+
+pub(super) fn delete_sync_conflict(id: &Uuid) {
+    LocalStorage::delete(sync_conflict_key(id));
 }
 
 /// Pure save: write character to localStorage and update index.
@@ -102,6 +167,10 @@ pub fn save_character(character: &Character) {
 pub fn delete_character_local_only(id: &Uuid) {
     LocalStorage::delete(character_key(id));
     LocalStorage::delete(stories_key(id));
+    LocalStorage::delete(rolls_key(id));
+    LocalStorage::delete(sync_base_key(id));
+    LocalStorage::delete(sync_conflict_key(id));
+    super::history::delete_history(id);
     let id = *id;
     update_index(|index| {
         index.characters.shift_remove(&id);
@@ -130,10 +199,48 @@ pub fn save_stories(id: &Uuid, stories: &[Story]) {
     }
 }
 
-/// Open a `.json` file picker, read the selected file, and call `on_character`
-/// with the parsed [`Character`]. Shows a browser alert and logs on error.
-pub fn pick_character_from_file<F: Fn(Character) + 'static>(on_character: F) {
-    let on_character = Rc::new(on_character);
+const PACKS_KEY: &str = "dnd_pc_packs";
+
+/// Raw pack JSON keyed by pack id. Packs are stored as imported and parsed on
+/// load, so a newer app version re-validates them with its own rules.
+pub(super) fn load_pack_sources() -> BTreeMap<String, String> {
+    LocalStorage::get(PACKS_KEY).unwrap_or_default()
+}
+
+fn save_pack_sources(sources: &BTreeMap<String, String>) {
+    if let Err(error) = LocalStorage::set(PACKS_KEY, sources) {
+        log::error!("Failed to save content packs: {error}");
+    }
+}
+
+pub fn load_packs() -> Vec<ContentPack> {
+    load_pack_sources()
+        .into_iter()
+        .filter_map(|(id, json)| match ContentPack::parse(&json) {
+            Ok(pack) => Some(pack),
+            Err(error) => {
+                log::error!("Failed to load content pack {id}: {error}");
+                None
+            }
+        })
+        .collect()
+}
+
+pub(super) fn save_pack_local_only(id: &str, json: &str) {
+    let mut sources = load_pack_sources();
+    sources.insert(id.to_string(), json.to_string());
+    save_pack_sources(&sources);
+}
+
+pub(super) fn delete_pack_local_only(id: &str) {
+    let mut sources = load_pack_sources();
+    if sources.remove(id).is_some() {
+        save_pack_sources(&sources);
+    }
+}
+
+/// Open a file picker for a `.json` file and call `on_text` with its contents.
+pub fn pick_json_file<F: Fn(String) + 'static>(on_text: F) {
     let input: web_sys::HtmlInputElement = leptos::prelude::document()
         .create_element("input")
         .unwrap()
@@ -168,21 +275,9 @@ pub fn pick_character_from_file<F: Fn(Character) + 'static>(on_character: F) {
                     return;
                 }
             };
-            let Some(text) = result.as_string() else {
-                log::error!("File result is not a string");
-                return;
-            };
-            match serde_json::from_str(&text)
-                .ok()
-                .and_then(deserialize_character_value)
-            {
-                Some(character) => on_character(character),
-                None => {
-                    log::error!("Failed to parse character JSON");
-                    leptos::prelude::window()
-                        .alert_with_message("Invalid character file")
-                        .ok();
-                }
+            match result.as_string() {
+                Some(text) => on_text(text),
+                None => log::error!("File result is not a string"),
             }
         });
 
@@ -197,3 +292,21 @@ pub fn pick_character_from_file<F: Fn(Character) + 'static>(on_character: F) {
 
     input.click();
 }
+
+/// Open a `.json` file picker, read the selected file, and call `on_character`
+/// with the parsed [`Character`]. Shows a browser alert and logs on error.
+pub fn pick_character_from_file<F: Fn(Character) + 'static>(on_character: F) {
+    pick_json_file(move |text| {
+        let result = match serde_json::from_str(&text) {
+            Ok(value) => deserialize_character_value(value).map_err(|error| error.to_string()),
+            Err(_) => Err("Invalid character file".to_string()),
+        };
+        match result {
+            Ok(character) => on_character(character),
+            Err(message) => {
+                log::error!("Failed to parse character JSON: {message}");
+                leptos::prelude::window().alert_with_message(&message).ok();
+            }
+        }
+    });
+}
@@ -0,0 +1,240 @@
+use std::collections::BTreeSet;
+
+use serde_json::{Map, Value};
+
+use crate::{model::Character, storage::migrate::MigrationError};
+
+/// Which copy wins a value both copies changed since the base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Local,
+    Remote,
+}
+
+pub struct Merged {
+    pub character: Character,
+    /// Dotted paths (`combat.hp_current`, `equipment.items.2`) of values both
+    /// copies changed in different ways.
+    pub conflicts: Vec<String>,
+}
+
+/// Three-way merge of two copies of a character against the copy both were
+/// last synced from. Edits only one side made are kept; values both sides
+/// changed are conflicts and are taken from `prefer`.
+///
+/// Objects merge key by key. Arrays merge element by element over the part
+/// the base had, and keep elements appended on one side (or the same ones on
+/// both); any other change replaces the value as a whole.
+pub fn merge_characters(
+    base: &Character,
+    local: &Character,
+    remote: &Character,
+    prefer: Side,
+) -> Result<Merged, MigrationError> {
+    let to_value = |character| serde_json::to_value(character).map_err(MigrationError::Invalid);
+    let base = to_value(base)?;
+    let local_value = to_value(local)?;
+    let remote_value = to_value(remote)?;
+
+    let mut conflicts = Vec::new();
+    let merged = merge_value(
+        &mut Vec::new(),
+        Some(&base),
+        Some(&local_value),
+        Some(&remote_value),
+        prefer,
+        &mut conflicts,
+    )
+    .unwrap_or(local_value);
+
+    let mut character: Character =
+        serde_json::from_value(merged).map_err(MigrationError::Invalid)?;
+    character.updated_at = local.updated_at.max(remote.updated_at);
+    conflicts.retain(|path| path != "updated_at");
+    Ok(Merged {
+        character,
+        conflicts,
+    })
+}
+
+fn merge_value(
+    path: &mut Vec<String>,
+    base: Option<&Value>,
+    local: Option<&Value>,
+    remote: Option<&Value>,
+    prefer: Side,
+    conflicts: &mut Vec<String>,
+) -> Option<Value> {
+    if local == remote || base == remote {
+        return local.cloned();
+    }
+    if base == local {
+        return remote.cloned();
+    }
+
+    match (base, local, remote) {
+        (
+            None | Some(Value::Object(_)),
+            Some(Value::Object(local)),
+            Some(Value::Object(remote)),
+        ) => {
+            let empty = Map::new();
+            let base = match base {
+                Some(Value::Object(base)) => base,
+                _ => &empty,
+            };
+            let keys: BTreeSet<&String> = local.keys().chain(remote.keys()).collect();
+            let mut merged = Map::new();
+            for key in keys {
+                path.push(key.clone());
+                let value = merge_value(
+                    path,
+                    base.get(key),
+                    local.get(key),
+                    remote.get(key),
+                    prefer,
+                    conflicts,
+                );
+                path.pop();
+                if let Some(value) = value {
+                    merged.insert(key.clone(), value);
+                }
+            }
+            Some(Value::Object(merged))
+        }
+        (Some(Value::Array(base)), Some(Value::Array(local)), Some(Value::Array(remote)))
+            if is_append_only(base.len(), local, remote) =>
+        {
+            let tail = if local.len() > base.len() {
+                &local[base.len()..]
+            } else {
+                &remote[base.len()..]
+            };
+            let merged = (0..base.len())
+                .filter_map(|idx| {
+                    path.push(idx.to_string());
+                    let value = merge_value(
+                        path,
+                        base.get(idx),
+                        local.get(idx),
+                        remote.get(idx),
+                        prefer,
+                        conflicts,
+                    );
+                    path.pop();
+                    value
+                })
+                .chain(tail.iter().cloned())
+                .collect();
+            Some(Value::Array(merged))
+        }
+        _ => {
+            conflicts.push(path.join("."));
+            match prefer {
+                Side::Local => local.cloned(),
+                Side::Remote => remote.cloned(),
+            }
+        }
+    }
+}
+
+/// Both copies still hold an element for every index of the base, and at
+/// most one of them appended anything past it (or both appended the same).
+fn is_append_only(base_len: usize, local: &[Value], remote: &[Value]) -> bool {
+    local.len() >= base_len
+        && remote.len() >= base_len
+        && (local.len() == base_len
+            || remote.len() == base_len
+            || local[base_len..] == remote[base_len..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Item;
+
+    #[test]
+    fn merges_edits_to_different_fields() {
+        let base = Character::test_character();
+
+        let mut local = base.clone();
+        local.identity.classes[0].level += 1;
+        local.combat.hp_max += 8;
+        local.updated_at += 10;
+
+        let mut remote = base.clone();
+        remote.combat.hp_current -= 5;
+        remote.notes = "Owes the innkeeper".to_string();
+        remote.updated_at += 20;
+
+        let merged = merge_characters(&base, &local, &remote, Side::Local).unwrap();
+        assert!(merged.conflicts.is_empty(), "{:?}", merged.conflicts);
+        let character = merged.character;
+        assert_eq!(
+            character.identity.classes[0].level,
+            local.identity.classes[0].level
+        );
+        assert_eq!(character.combat.hp_max, local.combat.hp_max);
+        assert_eq!(character.combat.hp_current, remote.combat.hp_current);
+        assert_eq!(character.notes, remote.notes);
+        assert_eq!(character.updated_at, remote.updated_at);
+    }
+
+    #[test]
+    fn reports_conflicting_edits() {
+        let base = Character::test_character();
+
+        let mut local = base.clone();
+        local.combat.hp_current = 3;
+        local.notes = "Local".to_string();
+
+        let mut remote = base.clone();
+        remote.combat.hp_current = 7;
+        remote.notes = "Remote".to_string();
+
+        let merged = merge_characters(&base, &local, &remote, Side::Local).unwrap();
+        assert_eq!(merged.conflicts, ["combat.hp_current", "notes"]);
+        assert_eq!(merged.character.combat.hp_current, 3);
+        assert_eq!(merged.character.notes, "Local");
+
+        let merged = merge_characters(&base, &local, &remote, Side::Remote).unwrap();
+        assert_eq!(merged.character.combat.hp_current, 7);
+        assert_eq!(merged.character.notes, "Remote");
+    }
+
+    #[test]
+    fn merges_appended_elements() {
+        let mut base = Character::test_character();
+        base.equipment.items = vec![Item {
+            name: "Rope".to_string(),
+            quantity: 1,
+            ..Item::default()
+        }];
+
+        let mut local = base.clone();
+        local.equipment.items.push(Item {
+            name: "Torch".to_string(),
+            quantity: 5,
+            ..Item::default()
+        });
+
+        let mut remote = base.clone();
+        remote.equipment.items[0].quantity = 2;
+
+        let merged = merge_characters(&base, &local, &remote, Side::Local).unwrap();
+        assert!(merged.conflicts.is_empty(), "{:?}", merged.conflicts);
+        let items = &merged.character.equipment.items;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].quantity, 2);
+        assert_eq!(items[1].name, "Torch");
+
+        // Both sides appending different elements is still a conflict
+        remote.equipment.items.push(Item {
+            name: "Lantern".to_string(),
+            ..Item::default()
+        });
+        let merged = merge_characters(&base, &local, &remote, Side::Local).unwrap();
+        assert_eq!(merged.conflicts, ["equipment.items"]);
+        assert_eq!(merged.character.equipment.items, local.equipment.items);
+    }
+}
@@ -1,6 +1,29 @@
 use std::collections::HashMap;
 
-use crate::model::{Character, DamageType};
+use crate::model::{Character, DamageType, SCHEMA_VERSION};
+
+#[derive(Debug)]
+pub enum MigrationError {
+    /// The document was written by a newer app whose schema this build does
+    /// not know how to read.
+    NewerSchema {
+        found: u32,
+    },
+    Invalid(serde_json::Error),
+}
+
+impl std::fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NewerSchema { found } => write!(
+                f,
+                "Character was written by a newer app (schema v{found}, this app reads up to \
+                 v{SCHEMA_VERSION}); update the app to open it"
+            ),
+            Self::Invalid(error) => write!(f, "Invalid character data: {error}"),
+        }
+    }
+}
 
 /// Migrate legacy string damage_type values to u8 enum representation.
 fn migrate_v1(value: &mut serde_json::Value) {
@@ -299,17 +322,94 @@ fn migrate_v9(value: &mut serde_json::Value) {
     }
 }
 
-/// Deserialize a `serde_json::Value` into a `Character`, applying all
-/// migrations. Used for cloud-fetched data.
-pub fn deserialize_character_value(mut value: serde_json::Value) -> Option<Character> {
-    migrate_v1(&mut value);
-    migrate_v2(&mut value);
-    migrate_v3(&mut value);
-    migrate_v4(&mut value);
-    migrate_v5(&mut value);
-    migrate_v6(&mut value);
-    migrate_v7(&mut value);
-    migrate_v8(&mut value);
-    migrate_v9(&mut value);
-    serde_json::from_value(value).ok()
+type Migration = fn(&mut serde_json::Value);
+
+/// Migrations keyed by the schema version they upgrade a document to. A
+/// document stamped with version `n` only runs the entries after `n`;
+/// documents without a `schema_version` predate versioning and run them all.
+const MIGRATIONS: &[(u32, Migration)] = &[
+    (1, migrate_v1),
+    (2, migrate_v2),
+    (3, migrate_v3),
+    (4, migrate_v4),
+    (5, migrate_v5),
+    (6, migrate_v6),
+    (7, migrate_v7),
+    (8, migrate_v8),
+    (9, migrate_v9),
+];
+
+/// Bring `value` up to [`SCHEMA_VERSION`] and stamp it.
+fn migrate(value: &mut serde_json::Value) -> Result<(), MigrationError> {
+    let version = value
+        .get("schema_version")
+        .and_then(serde_json::Value::as_u64)
+        .map_or(0, |version| version.min(u32::MAX as u64) as u32);
+    if version > SCHEMA_VERSION {
+        return Err(MigrationError::NewerSchema { found: version });
+    }
+    for (target, migration) in MIGRATIONS {
+        if *target > version {
+            migration(value);
+        }
+    }
+    value["schema_version"] = SCHEMA_VERSION.into();
+    Ok(())
+}
+
+/// Deserialize a `serde_json::Value` into a `Character`, running the
+/// migrations its schema version still needs.
+pub fn deserialize_character_value(
+    mut value: serde_json::Value,
+) -> Result<Character, MigrationError> {
+    migrate(&mut value)?;
+    serde_json::from_value(value).map_err(MigrationError::Invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn latest_migration_matches_schema_version() {
+        assert_eq!(MIGRATIONS.last().map(|(v, _)| *v), Some(SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn unversioned_documents_run_every_migration() {
+        let mut value = json!({
+            "id": "00000000-0000-0000-0000-000000000000",
+            "spell_slots": [],
+            "features": [{"name": "Rage"}],
+        });
+        migrate(&mut value).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["spell_slots"], json!({"0": []}));
+        assert_eq!(value["features"][0]["applied"], true);
+    }
+
+    #[test]
+    fn current_documents_skip_migrations() {
+        let mut value = json!({
+            "id": "00000000-0000-0000-0000-000000000000",
+            "schema_version": SCHEMA_VERSION,
+            "features": [{"name": "Rage"}],
+        });
+        migrate(&mut value).unwrap();
+        assert!(value["features"][0].get("applied").is_none());
+    }
+
+    #[test]
+    fn newer_documents_are_rejected() {
+        let value = json!({
+            "id": "00000000-0000-0000-0000-000000000000",
+            "schema_version": SCHEMA_VERSION + 1,
+        });
+        assert!(matches!(
+            deserialize_character_value(value),
+            Err(MigrationError::NewerSchema { found }) if found == SCHEMA_VERSION + 1
+        ));
+    }
 }
@@ -1,14 +1,21 @@
+pub mod campaign;
+pub mod history;
+pub mod live;
 mod local;
+mod merge;
 mod migrate;
 pub mod queue;
 mod sync;
 
 pub use local::{
-    load_ai_settings, load_character, load_effects, load_index, load_stories,
-    pick_character_from_file, save_ai_settings, save_effects, save_stories,
+    has_sync_conflict, load_ai_settings, load_character, load_effects, load_index, load_packs,
+    load_rolls, load_stories, pick_character_from_file, pick_json_file, save_ai_settings,
+    save_effects, save_rolls, save_stories,
 };
 pub use migrate::deserialize_character_value;
 pub use sync::{
-    SyncStatus, delete_character, init_sync, retry_sync, save_and_sync_character, setup_auto_save,
-    sign_in_with_google, sync_index_version, sync_is_anonymous, sync_last_error, sync_status,
+    SyncConflict, SyncStatus, delete_character, delete_pack, delete_story, init_sync,
+    load_sync_conflict, resolve_sync_conflict, retry_sync, save_and_sync_character,
+    save_and_sync_pack, setup_auto_save, sign_in_with_google, sync_effects, sync_index_version,
+    sync_is_anonymous, sync_last_error, sync_packs_version, sync_status,
 };
@@ -1,7 +1,11 @@
-use std::cell::RefCell;
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeSet,
+};
 
 use gloo_storage::{LocalStorage, Storage};
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::spawn_local;
@@ -11,6 +15,7 @@ use crate::{
     storage::sync::get_or_init_sync,
 };
 
+#[derive(Clone, Serialize, Deserialize)]
 pub enum CloudOp {
     PushCharacter {
         uid: String,
@@ -29,13 +34,33 @@ pub enum CloudOp {
         char_id: Uuid,
         story_id: Uuid,
     },
+    PushPack {
+        uid: String,
+        pack_id: String,
+    },
+    DeletePack {
+        uid: String,
+        pack_id: String,
+    },
+}
+
+/// Firestore document for a content pack. The pack is kept as its original
+/// JSON text: Firestore rejects nested arrays, which spell lists and level
+/// tables may contain.
+#[derive(Serialize, Deserialize)]
+pub(super) struct PackDoc {
+    pub id: String,
+    pub json: String,
 }
 
-#[derive(Hash, PartialEq, Eq)]
-enum QueueKey {
+/// What a queued op acts on. A newer op for the same key replaces the pending
+/// one, and deletions are remembered by key as tombstones.
+#[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(super) enum QueueKey {
     Character(Uuid),
     Stories(Uuid),
     Story(Uuid, Uuid),
+    Pack(String),
 }
 
 impl CloudOp {
@@ -48,20 +73,131 @@ impl CloudOp {
             Self::DeleteStory {
                 char_id, story_id, ..
             } => QueueKey::Story(*char_id, *story_id),
+            Self::PushPack { pack_id, .. } | Self::DeletePack { pack_id, .. } => {
+                QueueKey::Pack(pack_id.clone())
+            }
         }
     }
+
+    fn is_delete(&self) -> bool {
+        matches!(
+            self,
+            Self::DeleteCharacter { .. } | Self::DeleteStory { .. } | Self::DeletePack { .. }
+        )
+    }
+}
+
+const QUEUE_KEY: &str = "dnd_pc_queue";
+const TOMBSTONES_KEY: &str = "dnd_pc_tombstones";
+
+const RETRY_BASE_MS: f64 = 2_000.0;
+const RETRY_MAX_MS: f64 = 300_000.0;
+
+/// A queued op with its retry state, persisted so pending writes survive a
+/// page reload.
+#[derive(Serialize, Deserialize)]
+struct PendingOp {
+    op: CloudOp,
+    #[serde(default)]
+    attempts: u32,
+    /// `Date.now()` before which the op is not retried.
+    #[serde(default)]
+    retry_at: f64,
+    /// Bumped when the op is replaced, so a flush finishing an older op
+    /// doesn't drop its replacement.
+    #[serde(skip)]
+    generation: u64,
+}
+
+impl PendingOp {
+    fn backoff_ms(&self) -> f64 {
+        (RETRY_BASE_MS * 2f64.powi(self.attempts.min(16) as i32)).min(RETRY_MAX_MS)
+    }
 }
 
 thread_local! {
-    static QUEUE: RefCell<IndexMap<QueueKey, CloudOp>> = RefCell::new(IndexMap::new());
+    static QUEUE: RefCell<IndexMap<QueueKey, PendingOp>> = RefCell::new(load_queue());
+    static TOMBSTONES: RefCell<BTreeSet<QueueKey>> =
+        RefCell::new(LocalStorage::get(TOMBSTONES_KEY).unwrap_or_default());
+    static GENERATION: Cell<u64> = const { Cell::new(0) };
+    /// Ops are only sent once auth has settled, see [`resume`].
+    static RESUMED: Cell<bool> = const { Cell::new(false) };
+    static FLUSHING: Cell<bool> = const { Cell::new(false) };
+}
+
+fn load_queue() -> IndexMap<QueueKey, PendingOp> {
+    let ops: Vec<PendingOp> = LocalStorage::get(QUEUE_KEY).unwrap_or_default();
+    ops.into_iter()
+        .map(|pending| (pending.op.queue_key(), pending))
+        .collect()
+}
+
+fn save_queue(queue: &IndexMap<QueueKey, PendingOp>) {
+    let ops: Vec<&PendingOp> = queue.values().collect();
+    if let Err(error) = LocalStorage::set(QUEUE_KEY, ops) {
+        log::error!("Failed to save cloud queue: {error}");
+    }
+}
+
+fn update_tombstones(f: impl FnOnce(&mut BTreeSet<QueueKey>) -> bool) {
+    TOMBSTONES.with(|tombstones| {
+        let mut tombstones = tombstones.borrow_mut();
+        if f(&mut tombstones)
+            && let Err(error) = LocalStorage::set(TOMBSTONES_KEY, &*tombstones)
+        {
+            log::error!("Failed to save tombstones: {error}");
+        }
+    });
 }
 
 pub fn push(op: CloudOp) {
+    let key = op.queue_key();
+    let is_delete = op.is_delete();
+    update_tombstones(|tombstones| {
+        if is_delete {
+            tombstones.insert(key.clone())
+        } else {
+            tombstones.remove(&key)
+        }
+    });
+
+    let generation = GENERATION.with(|generation| {
+        generation.set(generation.get() + 1);
+        generation.get()
+    });
     QUEUE.with(|queue| {
-        queue.borrow_mut().insert(op.queue_key(), op);
+        let mut queue = queue.borrow_mut();
+        queue.insert(
+            key,
+            PendingOp {
+                op,
+                attempts: 0,
+                retry_at: 0.0,
+                generation,
+            },
+        );
+        save_queue(&queue);
     });
 }
 
+/// Remember a deletion that can't be queued yet (signed out), so the cloud
+/// copy is deleted rather than pulled back on the next sync.
+pub(super) fn bury(key: QueueKey) {
+    update_tombstones(|tombstones| tombstones.insert(key));
+}
+
+/// Whether the item was deleted locally and the cloud copy may still exist.
+pub(super) fn is_buried(key: &QueueKey) -> bool {
+    TOMBSTONES.with(|tombstones| tombstones.borrow().contains(key))
+}
+
+/// Start sending queued ops, including those left over from a previous
+/// session. Call once auth has settled.
+pub(super) fn resume() {
+    RESUMED.with(|resumed| resumed.set(true));
+    flush();
+}
+
 /// Start the flush interval. Call once at app init.
 pub fn start_flush_interval(interval_ms: u32) {
     let interval_ms = interval_ms.min(i32::MAX as u32) as i32;
@@ -77,21 +213,54 @@ pub fn start_flush_interval(interval_ms: u32) {
 }
 
 fn flush() {
-    let ops: Vec<CloudOp> =
-        QUEUE.with(|queue| queue.borrow_mut().drain(..).map(|(_, op)| op).collect());
-    if ops.is_empty() {
+    if !RESUMED.with(Cell::get) || FLUSHING.with(Cell::get) {
         return;
     }
+    let now = js_sys::Date::now();
+    let due: Vec<(QueueKey, CloudOp, u64)> = QUEUE.with(|queue| {
+        queue
+            .borrow()
+            .iter()
+            .filter(|(_, pending)| pending.retry_at <= now)
+            .map(|(key, pending)| (key.clone(), pending.op.clone(), pending.generation))
+            .collect()
+    });
+    if due.is_empty() {
+        return;
+    }
+    FLUSHING.with(|flushing| flushing.set(true));
     spawn_local(async move {
         let state = get_or_init_sync();
         state.set_syncing();
         let mut had_error = false;
-        for op in ops {
-            if let Err(error) = execute_op(op).await {
+        for (key, op, generation) in due {
+            let is_delete = op.is_delete();
+            let result = execute_op(op).await;
+            if let Err(error) = &result {
                 log::warn!("Cloud op failed: {error}");
                 had_error = true;
             }
+            QUEUE.with(|queue| {
+                let mut queue = queue.borrow_mut();
+                let Some(pending) = queue
+                    .get_mut(&key)
+                    .filter(|pending| pending.generation == generation)
+                else {
+                    return;
+                };
+                if result.is_ok() {
+                    queue.shift_remove(&key);
+                    if is_delete {
+                        update_tombstones(|tombstones| tombstones.remove(&key));
+                    }
+                } else {
+                    pending.retry_at = js_sys::Date::now() + pending.backoff_ms();
+                    pending.attempts += 1;
+                }
+                save_queue(&queue);
+            });
         }
+        FLUSHING.with(|flushing| flushing.set(false));
         if had_error {
             state.set_error("Some cloud operations failed".into());
         } else {
@@ -103,15 +272,22 @@ fn flush() {
 async fn execute_op(op: CloudOp) -> Result<(), FirebaseError> {
     match op {
         CloudOp::PushCharacter { uid, char_id } => {
+            // Waits for the user to settle the conflict, which pushes again.
+            if super::local::has_sync_conflict(&char_id) {
+                return Ok(());
+            }
             let char_key = super::local::character_key(&char_id);
             let Ok(Some(raw)) = LocalStorage::raw().get_item(&char_key) else {
                 return Ok(());
             };
-            let json: serde_json::Value = serde_json::from_str(&raw).map_err(|error| {
+            let mut json: serde_json::Value = serde_json::from_str(&raw).map_err(|error| {
                 FirebaseError::Js(JsValue::from_str(&format!("JSON parse: {error}")))
             })?;
+            super::live::attach_published_state(&mut json, &char_id);
             let char_id_str = char_id.to_string();
-            firebase::set_doc(&json, &["users", &uid, "characters", &char_id_str]).await
+            firebase::set_doc(&json, &["users", &uid, "characters", &char_id_str]).await?;
+            super::local::save_sync_base_raw(&char_id, &raw);
+            Ok(())
         }
         CloudOp::DeleteCharacter { uid, char_id } => {
             let char_id_str = char_id.to_string();
@@ -162,5 +338,51 @@ async fn execute_op(op: CloudOp) -> Result<(), FirebaseError> {
             ])
             .await
         }
+        CloudOp::PushPack { uid, pack_id } => {
+            let Some(json) = super::local::load_pack_sources().remove(&pack_id) else {
+                return Ok(());
+            };
+            let doc = PackDoc {
+                id: pack_id.clone(),
+                json,
+            };
+            firebase::set_doc(&doc, &["users", &uid, "packs", &pack_id]).await
+        }
+        CloudOp::DeletePack { uid, pack_id } => {
+            firebase::delete_doc(&["users", &uid, "packs", &pack_id]).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(attempts: u32) -> PendingOp {
+        PendingOp {
+            op: CloudOp::DeletePack {
+                uid: "user".into(),
+                pack_id: "pack".into(),
+            },
+            attempts,
+            retry_at: 0.0,
+            generation: 0,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        assert_eq!(pending(0).backoff_ms(), 2_000.0);
+        assert_eq!(pending(3).backoff_ms(), 16_000.0);
+        assert_eq!(pending(40).backoff_ms(), RETRY_MAX_MS);
+    }
+
+    #[test]
+    fn pending_op_roundtrips() {
+        let json = serde_json::to_string(&pending(2)).unwrap();
+        let restored: PendingOp = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.attempts, 2);
+        assert!(restored.op.is_delete());
+        assert!(restored.op.queue_key() == QueueKey::Pack("pack".into()));
     }
 }
@@ -12,7 +12,13 @@ use crate::{
     ai::Story,
     firebase::{self, ChangeType, FirebaseError},
     model::Character,
-    storage::{local, migrate, queue, queue::CloudOp},
+    rules::ContentPack,
+    storage::{
+        local,
+        merge::{self, Side},
+        migrate, queue,
+        queue::{CloudOp, PackDoc, QueueKey},
+    },
 };
 
 /// 2 s debounce — balances responsiveness vs. Firestore write-per-second cost.
@@ -35,6 +41,9 @@ pub(super) struct SyncState {
     /// Bumped after cloud pull modifies the character index, so the UI can
     /// react.
     pub(super) index_version: RwSignal<u32>,
+    /// Bumped whenever the stored content packs change, locally or by a
+    /// cloud pull.
+    packs_version: RwSignal<u32>,
     /// Set to `true` once the initial cloud sync completes (success or
     /// failure). Before this, auto-save skips `touch()` to preserve
     /// timestamps for accurate conflict resolution.
@@ -75,6 +84,7 @@ pub(super) fn get_or_init_sync() -> SyncState {
             anon: RwSignal::new(false),
             last_error: RwSignal::new(None),
             index_version: RwSignal::new(0),
+            packs_version: RwSignal::new(0),
             sync_done: RwSignal::new(false),
         })
     })
@@ -104,6 +114,11 @@ pub fn sync_index_version() -> ReadSignal<u32> {
     get_or_init_sync().index_version.read_only()
 }
 
+/// Reactive signal bumped whenever the stored content packs change.
+pub fn sync_packs_version() -> ReadSignal<u32> {
+    get_or_init_sync().packs_version.read_only()
+}
+
 /// Reactive signal that becomes `true` once the initial cloud sync completes
 /// (success, failure, or disabled). Before this, auto-save preserves existing
 /// timestamps so sync can compare them accurately.
@@ -167,8 +182,58 @@ pub fn save_and_sync_character(character: &mut Character) {
 pub fn delete_character(id: &Uuid) {
     local::delete_character_local_only(id);
 
+    match firebase::current_uid() {
+        Some(uid) => queue::push(CloudOp::DeleteCharacter { uid, char_id: *id }),
+        None => queue::bury(QueueKey::Character(*id)),
+    }
+}
+
+/// Store a validated pack's JSON locally and push it to the cloud.
+pub fn save_and_sync_pack(id: &str, json: &str) {
+    local::save_pack_local_only(id, json);
+    get_or_init_sync().packs_version.update(|v| *v += 1);
+
     if let Some(uid) = firebase::current_uid() {
-        queue::push(CloudOp::DeleteCharacter { uid, char_id: *id });
+        queue::push(CloudOp::PushPack {
+            uid,
+            pack_id: id.to_string(),
+        });
+    }
+}
+
+pub fn delete_pack(id: &str) {
+    local::delete_pack_local_only(id);
+    get_or_init_sync().packs_version.update(|v| *v += 1);
+
+    match firebase::current_uid() {
+        Some(uid) => queue::push(CloudOp::DeletePack {
+            uid,
+            pack_id: id.to_string(),
+        }),
+        None => queue::bury(QueueKey::Pack(id.to_string())),
+    }
+}
+
+pub fn delete_story(char_id: &Uuid, story_id: &Uuid) {
+    let mut stories = local::load_stories(char_id);
+    stories.retain(|story| story.id != *story_id);
+    local::save_stories(char_id, &stories);
+
+    match firebase::current_uid() {
+        Some(uid) => queue::push(CloudOp::DeleteStory {
+            uid,
+            char_id: *char_id,
+            story_id: *story_id,
+        }),
+        None => queue::bury(QueueKey::Story(*char_id, *story_id)),
+    }
+}
+
+/// Republish a character others follow (shared, or in a campaign) after its
+/// active effects changed.
+pub fn sync_effects(character: &Character) {
+    if character.shared || character.campaign.is_some() {
+        schedule_cloud_push(character);
     }
 }
 
@@ -179,12 +244,111 @@ fn schedule_cloud_push(character: &Character) {
     let Some(uid) = get_or_init_sync().uid.get_untracked() else {
         return;
     };
+    // Pushing now would overwrite the cloud edits still waiting to be merged.
+    if local::has_sync_conflict(&character.id) {
+        return;
+    }
     queue::push(CloudOp::PushCharacter {
         uid,
         char_id: character.id,
     });
 }
 
+/// The two ways to settle a pending sync conflict: merges of the local and
+/// cloud copies keeping the local or the cloud value wherever they disagree.
+pub struct SyncConflict {
+    pub local: Character,
+    pub remote: Character,
+}
+
+pub fn load_sync_conflict(id: &Uuid) -> Option<SyncConflict> {
+    let remote = local::load_sync_conflict(id)?;
+    let local_character = local::load_character(id)?;
+    let merged = local::load_sync_base(id).and_then(|base| {
+        let merge = |prefer| merge::merge_characters(&base, &local_character, &remote, prefer);
+        Some((merge(Side::Local).ok()?, merge(Side::Remote).ok()?))
+    });
+    Some(match merged {
+        Some((local_merge, remote_merge)) => SyncConflict {
+            local: local_merge.character,
+            remote: remote_merge.character,
+        },
+        None => SyncConflict {
+            local: local_character,
+            remote,
+        },
+    })
+}
+
+/// Save the chosen resolution of a sync conflict and push it to the cloud.
+pub fn resolve_sync_conflict(character: &mut Character) {
+    local::delete_sync_conflict(&character.id);
+    save_and_sync_character(character);
+    get_or_init_sync().index_version.update(|v| *v += 1);
+}
+
+/// How the cloud copy of a character relates to the local one.
+enum Reconciled {
+    /// Both copies are the same.
+    Unchanged,
+    /// Only the local copy changed; it should be pushed.
+    Push(Character),
+    /// Only the cloud copy changed; it replaces the local one.
+    Pull(Character),
+    /// Both changed different values; the merge replaces both copies.
+    Merged(Character),
+    /// Both changed the same values; the cloud copy waits for the user.
+    Conflict,
+    /// Deleted locally; the cloud copy is yet to be deleted.
+    Deleted,
+}
+
+fn reconcile(remote: &Character) -> Reconciled {
+    let Some(local_character) = local::load_character(&remote.id) else {
+        if queue::is_buried(&QueueKey::Character(remote.id)) {
+            return Reconciled::Deleted;
+        }
+        return Reconciled::Pull(remote.clone());
+    };
+    if local_character.updated_at == remote.updated_at {
+        return Reconciled::Unchanged;
+    }
+    let Some(base) = local::load_sync_base(&remote.id) else {
+        // Synced before bases were recorded: the newer copy wins.
+        return if local_character.updated_at > remote.updated_at {
+            Reconciled::Push(local_character)
+        } else {
+            Reconciled::Pull(remote.clone())
+        };
+    };
+    if remote.updated_at == base.updated_at {
+        return Reconciled::Push(local_character);
+    }
+    if local_character.updated_at == base.updated_at {
+        return Reconciled::Pull(remote.clone());
+    }
+
+    match merge::merge_characters(&base, &local_character, remote, Side::Local) {
+        Ok(merged) if merged.conflicts.is_empty() => {
+            let mut character = merged.character;
+            character.touch();
+            Reconciled::Merged(character)
+        }
+        Ok(merged) => {
+            log::info!(
+                "Sync conflict for {}: {}",
+                remote.id,
+                merged.conflicts.join(", ")
+            );
+            Reconciled::Conflict
+        }
+        Err(error) => {
+            log::warn!("Failed to merge character {}: {error}", remote.id);
+            Reconciled::Conflict
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum SyncOp {
     PullOnly,
@@ -290,6 +454,9 @@ async fn finish_sign_in(state: SyncState, is_anon: bool, sync_op: SyncOp) {
             };
             log::info!("finish_sign_in: done");
 
+            // Send writes queued while offline or before a reload.
+            queue::resume();
+
             // Subscribe to realtime changes after initial sync
             subscribe_to_changes(&uid);
 
@@ -327,26 +494,39 @@ fn subscribe_to_changes(uid: &str) {
             for change in changes {
                 match change.change_type {
                     ChangeType::Added | ChangeType::Modified => {
-                        let Some(character) = migrate::deserialize_character_value(change.data)
-                        else {
-                            log::warn!("Failed to deserialize snapshot character");
-                            continue;
+                        let character = match migrate::deserialize_character_value(change.data) {
+                            Ok(character) => character,
+                            Err(error) => {
+                                log::warn!("Failed to deserialize snapshot character: {error}");
+                                continue;
+                            }
                         };
-                        let local_at = index
-                            .characters
-                            .get(&character.id)
-                            .map(|summary| summary.updated_at)
-                            .unwrap_or(0);
-                        if character.updated_at > local_at {
-                            if let Err(error) =
-                                LocalStorage::set(local::character_key(&character.id), &character)
-                            {
-                                log::warn!("Failed to save pulled character: {error}");
+                        let (character, push) = match reconcile(&character) {
+                            Reconciled::Unchanged | Reconciled::Push(_) | Reconciled::Deleted => {
                                 continue;
                             }
-                            index.characters.insert(character.id, character.summary());
-                            index_dirty = true;
+                            Reconciled::Pull(character) => (character, false),
+                            Reconciled::Merged(character) => (character, true),
+                            Reconciled::Conflict => {
+                                local::save_sync_conflict(&character);
+                                index_dirty = true;
+                                continue;
+                            }
+                        };
+                        if let Err(error) =
+                            LocalStorage::set(local::character_key(&character.id), &character)
+                        {
+                            log::warn!("Failed to save pulled character: {error}");
+                            continue;
+                        }
+                        local::delete_sync_conflict(&character.id);
+                        if push {
+                            schedule_cloud_push(&character);
+                        } else {
+                            local::save_sync_base(&character);
                         }
+                        index.characters.insert(character.id, character.summary());
+                        index_dirty = true;
                     }
                     ChangeType::Removed => {
                         if let Ok(id) = change.id.parse::<Uuid>() {
@@ -372,11 +552,16 @@ fn subscribe_to_changes(uid: &str) {
 }
 
 async fn push_to_cloud(uid: &str, character: &Character) -> Result<(), FirebaseError> {
+    let mut json = serde_json::to_value(character)
+        .map_err(|error| FirebaseError::Js(JsValue::from_str(&format!("JSON encode: {error}"))))?;
+    super::live::attach_published_state(&mut json, &character.id);
     firebase::set_doc(
-        character,
+        &json,
         &["users", uid, "characters", &character.id.to_string()],
     )
-    .await
+    .await?;
+    local::save_sync_base(character);
+    Ok(())
 }
 
 async fn sync_all_with_cloud(push_local_only: bool) -> Result<(), FirebaseError> {
@@ -399,38 +584,55 @@ async fn sync_all_with_cloud(push_local_only: bool) -> Result<(), FirebaseError>
 
     for remote_value in remote_chars {
         let remote: Character = match migrate::deserialize_character_value(remote_value) {
-            Some(character) => character,
-            None => {
-                log::warn!("Failed to deserialize remote character (migration failed)");
+            Ok(character) => character,
+            Err(error) => {
+                log::warn!("Failed to deserialize remote character: {error}");
                 continue;
             }
         };
         seen_remote.insert(remote.id);
 
-        let local_updated_at = index
-            .characters
-            .get(&remote.id)
-            .map(|summary| summary.updated_at)
-            .unwrap_or(0);
-
-        if local_updated_at >= remote.updated_at {
-            if local_updated_at > remote.updated_at
-                && let Some(local_character) = local::load_character(&remote.id)
-                && let Err(error) = push_to_cloud(&uid, &local_character).await
-            {
-                log::warn!("Failed to push local-newer character: {error:?}");
-                push_failures += 1;
+        let (character, push) = match reconcile(&remote) {
+            Reconciled::Unchanged => continue,
+            Reconciled::Push(local_character) => {
+                if let Err(error) = push_to_cloud(&uid, &local_character).await {
+                    log::warn!("Failed to push local-newer character: {error:?}");
+                    push_failures += 1;
+                }
+                continue;
             }
-        } else {
-            if let Err(error) = LocalStorage::set(local::character_key(&remote.id), &remote) {
-                log::warn!("Failed to save pulled character {}: {error}", remote.id);
+            Reconciled::Pull(character) => (character, false),
+            Reconciled::Merged(character) => (character, true),
+            Reconciled::Conflict => {
+                local::save_sync_conflict(&remote);
+                index_dirty = true;
+                continue;
+            }
+            Reconciled::Deleted => {
+                seen_remote.remove(&remote.id);
+                queue::push(CloudOp::DeleteCharacter {
+                    uid: uid.clone(),
+                    char_id: remote.id,
+                });
                 continue;
             }
+        };
 
-            let summary = remote.summary();
-            index.characters.insert(summary.id, summary);
-            index_dirty = true;
+        if let Err(error) = LocalStorage::set(local::character_key(&character.id), &character) {
+            log::warn!("Failed to save pulled character {}: {error}", character.id);
+            continue;
         }
+        local::delete_sync_conflict(&character.id);
+        if !push {
+            local::save_sync_base(&character);
+        } else if let Err(error) = push_to_cloud(&uid, &character).await {
+            log::warn!("Failed to push merged character: {error:?}");
+            push_failures += 1;
+        }
+
+        let summary = character.summary();
+        index.characters.insert(summary.id, summary);
+        index_dirty = true;
     }
 
     if push_local_only {
@@ -471,6 +673,10 @@ async fn sync_all_with_cloud(push_local_only: bool) -> Result<(), FirebaseError>
         }
     }
 
+    if let Err(error) = sync_packs_with_cloud(&uid, push_local_only).await {
+        log::warn!("Pack sync failed: {error:?}");
+    }
+
     if push_failures > 0 {
         Err(FirebaseError::Js(JsValue::from_str(&format!(
             "Failed to push {push_failures} character(s)"
@@ -480,6 +686,50 @@ async fn sync_all_with_cloud(push_local_only: bool) -> Result<(), FirebaseError>
     }
 }
 
+/// Pull packs missing locally; with `push_local_only`, queue pushes for packs
+/// the cloud does not have yet.
+async fn sync_packs_with_cloud(uid: &str, push_local_only: bool) -> Result<(), FirebaseError> {
+    let remote_packs: Vec<PackDoc> = firebase::get_all_docs(&["users", uid, "packs"]).await?;
+    let local_packs = local::load_pack_sources();
+    let mut dirty = false;
+
+    for remote in &remote_packs {
+        if local_packs.contains_key(&remote.id) {
+            continue;
+        }
+        if queue::is_buried(&QueueKey::Pack(remote.id.clone())) {
+            queue::push(CloudOp::DeletePack {
+                uid: uid.to_string(),
+                pack_id: remote.id.clone(),
+            });
+            continue;
+        }
+        match ContentPack::parse(&remote.json) {
+            Ok(_) => {
+                local::save_pack_local_only(&remote.id, &remote.json);
+                dirty = true;
+            }
+            Err(error) => log::warn!("Skipping invalid remote pack {}: {error}", remote.id),
+        }
+    }
+
+    if push_local_only {
+        for id in local_packs.keys() {
+            if !remote_packs.iter().any(|remote| remote.id == *id) {
+                queue::push(CloudOp::PushPack {
+                    uid: uid.to_string(),
+                    pack_id: id.clone(),
+                });
+            }
+        }
+    }
+
+    if dirty {
+        get_or_init_sync().packs_version.update(|v| *v += 1);
+    }
+    Ok(())
+}
+
 async fn sync_stories_with_cloud(uid: &str, char_id: &Uuid) -> Result<(), FirebaseError> {
     let char_id_str = char_id.to_string();
     let remote_stories: Vec<Story> =
@@ -493,7 +743,9 @@ async fn sync_stories_with_cloud(uid: &str, char_id: &Uuid) -> Result<(), Fireba
     let mut dirty = false;
 
     for remote_story in remote_stories {
-        if !local_ids.contains(&remote_story.id) {
+        if !local_ids.contains(&remote_story.id)
+            && !queue::is_buried(&QueueKey::Story(*char_id, remote_story.id))
+        {
             local_stories.push(remote_story);
             dirty = true;
         }